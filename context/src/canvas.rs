@@ -2,7 +2,11 @@
 
 extern crate sdl2;
 
+use std::time::Duration;
+use std::time::Instant;
+
 use sdl2::event::Event;
+use sdl2::event::WindowEvent;
 use sdl2::video::GLContext;
 use sdl2::video::GLProfile;
 use sdl2::video::SwapInterval;
@@ -10,8 +14,12 @@ use sdl2::video::Window;
 use sdl2::EventPump;
 use sdl2::EventSubsystem;
 use sdl2::Sdl;
+use sdl2::TimerSubsystem;
 use sdl2::VideoSubsystem;
 
+use crate::event::EventError;
+use crate::event::EventSender;
+
 const OPENGL_MAJOR_VERSION: u8 = 4;
 const OPENGL_MINOR_VERSION: u8 = 0;
 
@@ -20,22 +28,123 @@ pub enum CanvasError {
     CreatingWindowFailed,
     CreatingContextFailed,
     CreatingEventHandlerFailed,
+    ActivatingContextFailed,
+    UnknownWindow,
 }
 
+pub type WindowId = u32;
+
 pub struct Canvas {
     title: String,
     width: u32,
     height: u32,
     sdl: Sdl,
-    window: Window,
+    windows: Vec<Window>,
     subsystem: VideoSubsystem,
     context: GLContext,
+    timer: TimerSubsystem,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Fullscreen {
+    Off,
+    Exclusive,
+    Borderless,
+}
+
+#[derive(Clone, Debug)]
+pub struct WindowConfig {
+    title: String,
+    width: u32,
+    height: u32,
+    resizable: bool,
+    fullscreen: Fullscreen,
+    vsync: bool,
+}
+
+impl WindowConfig {
+    pub fn new(title: &str, width: u32, height: u32) -> Self {
+        Self {
+            title: title.to_owned(),
+            width,
+            height,
+            resizable: false,
+            fullscreen: Fullscreen::Off,
+            vsync: true,
+        }
+    }
+
+    pub fn with_resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn with_fullscreen(mut self, fullscreen: Fullscreen) -> Self {
+        self.fullscreen = fullscreen;
+        self
+    }
+
+    pub fn with_vsync(mut self, vsync: bool) -> Self {
+        self.vsync = vsync;
+        self
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn resizable(&self) -> bool {
+        self.resizable
+    }
+
+    pub fn fullscreen(&self) -> Fullscreen {
+        self.fullscreen
+    }
+
+    pub fn vsync(&self) -> bool {
+        self.vsync
+    }
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self::new("pandix engine", 400, 400)
+    }
+}
+
+fn build_window(subsystem: &VideoSubsystem, config: &WindowConfig) -> Result<Window, CanvasError> {
+    let mut builder = subsystem.window(config.title(), config.width(), config.height());
+    builder.opengl();
+
+    if config.resizable() {
+        builder.resizable();
+    }
+
+    match config.fullscreen() {
+        Fullscreen::Exclusive => {
+            builder.fullscreen();
+        }
+        Fullscreen::Borderless => {
+            builder.fullscreen_desktop();
+        }
+        Fullscreen::Off => (),
+    }
+
+    builder
+        .build()
+        .map_err(|_| CanvasError::CreatingWindowFailed)
 }
 
 impl Canvas {
-    // TODO: more options like resizable, fullscreen | fullscreen borderless, vsync etc.
-    //       maybe create a CanvasBuilder?
-    pub fn new(title: &str, width: u32, height: u32) -> Result<(Canvas, CanvasLoop), CanvasError> {
+    pub fn new(config: &WindowConfig) -> Result<(Canvas, CanvasLoop), CanvasError> {
         let sdl = sdl2::init().map_err(|_| CanvasError::CreatingWindowFailed)?;
         let subsystem = sdl.video().map_err(|_| CanvasError::CreatingWindowFailed)?;
 
@@ -43,11 +152,7 @@ impl Canvas {
         gl_attr.set_context_profile(GLProfile::Core);
         gl_attr.set_context_version(OPENGL_MAJOR_VERSION, OPENGL_MINOR_VERSION);
 
-        let window = subsystem
-            .window(title, width, height)
-            .opengl()
-            .build()
-            .map_err(|_| CanvasError::CreatingWindowFailed)?;
+        let window = build_window(&subsystem, config)?;
         let context = window
             .gl_create_context()
             .map_err(|_| CanvasError::CreatingContextFailed)?;
@@ -57,21 +162,33 @@ impl Canvas {
         let event_subsystem = sdl
             .event()
             .map_err(|_| CanvasError::CreatingEventHandlerFailed)?;
+        let timer = sdl
+            .timer()
+            .map_err(|_| CanvasError::CreatingEventHandlerFailed)?;
         let cavas_loop = CanvasLoop(event_pump, event_subsystem);
 
-        if let Err(_error) = subsystem.gl_set_swap_interval(SwapInterval::LateSwapTearing) {
-            // TODO: if changing to vsync is not supported what to do?
+        if config.vsync() {
+            // not every driver supports adaptive vsync
+            if subsystem
+                .gl_set_swap_interval(SwapInterval::LateSwapTearing)
+                .is_err()
+            {
+                let _ = subsystem.gl_set_swap_interval(SwapInterval::VSync);
+            }
+        } else {
+            let _ = subsystem.gl_set_swap_interval(SwapInterval::Immediate);
         }
 
         Ok((
             Canvas {
-                title: title.to_owned(),
-                width,
-                height,
+                title: config.title().to_owned(),
+                width: config.width(),
+                height: config.height(),
                 sdl,
-                window,
+                windows: vec![window],
                 subsystem,
                 context,
+                timer,
             },
             cavas_loop,
         ))
@@ -94,7 +211,67 @@ impl Canvas {
     }
 
     pub fn window(&self) -> &Window {
-        &self.window
+        &self.windows[0]
+    }
+
+    pub fn windows(&self) -> &[Window] {
+        &self.windows
+    }
+
+    pub fn window_by_id(&self, id: WindowId) -> Option<&Window> {
+        self.windows.iter().find(|window| window.id() == id)
+    }
+
+    // the size in pixels that is drawn to, can differ from the window size on high dpi screens
+    pub fn drawable_size(&self, id: WindowId) -> Option<(u32, u32)> {
+        self.window_by_id(id).map(|window| window.drawable_size())
+    }
+
+    // all windows share the context of the main window
+    pub fn add_window(&mut self, config: &WindowConfig) -> Result<WindowId, CanvasError> {
+        let window = build_window(&self.subsystem, config)?;
+        let id = window.id();
+
+        self.windows.push(window);
+
+        Ok(id)
+    }
+
+    // the window is destroyed, the main window can not be closed as it ends the loop
+    pub fn close_window(&mut self, id: WindowId) {
+        if let Some(index) = self
+            .windows
+            .iter()
+            .skip(1)
+            .position(|window| window.id() == id)
+        {
+            self.windows.remove(index + 1);
+        }
+    }
+
+    pub fn make_current(&self, id: WindowId) -> Result<(), CanvasError> {
+        let window = self.window_by_id(id).ok_or(CanvasError::UnknownWindow)?;
+
+        window
+            .gl_make_current(&self.context)
+            .map_err(|_| CanvasError::ActivatingContextFailed)
+    }
+
+    pub fn swap_windows(&self) {
+        for window in &self.windows {
+            if window.gl_make_current(&self.context).is_ok() {
+                window.gl_swap_window();
+            }
+        }
+    }
+
+    // events carry the milliseconds since sdl got initialized when they happened
+    pub fn event_time(&self, timestamp: u32) -> Instant {
+        let elapsed = self.timer.ticks().saturating_sub(timestamp);
+        let now = Instant::now();
+
+        now.checked_sub(Duration::from_millis(u64::from(elapsed)))
+            .unwrap_or(now)
     }
 
     pub fn context(&self) -> &GLContext {
@@ -112,9 +289,23 @@ impl Canvas {
 pub struct CanvasLoop(EventPump, EventSubsystem);
 
 impl CanvasLoop {
-    pub fn run<F>(mut self, canvas: &Canvas, mut function: F)
+    // events of type T can only be pushed after the type got registered
+    pub fn register_event<T: std::any::Any>(&self) -> Result<(), EventError> {
+        self.1
+            .register_custom_event::<T>()
+            .map_err(EventError::RegisteringEventFailed)
+    }
+
+    // the events of the sender wake up the loop from now on
+    pub fn connect(&self, sender: &EventSender) -> Result<(), EventError> {
+        sender.connect(self.1.event_sender())
+    }
+
+    // stops on quit, closing the main window or as soon as the function returns an error, other
+    // windows are closed before the function gets their close event
+    pub fn run<F, E>(mut self, canvas: &mut Canvas, mut function: F) -> Result<(), E>
     where
-        F: FnMut(Vec<Event>),
+        F: FnMut(&Canvas, Vec<Event>) -> Result<(), E>,
     {
         'running: loop {
             // TODO try to get it to work without allocation on the heap
@@ -122,13 +313,25 @@ impl CanvasLoop {
             for event in &iter {
                 match event {
                     Event::Quit { .. } => break 'running,
+                    Event::Window {
+                        window_id,
+                        win_event: WindowEvent::Close,
+                        ..
+                    } if *window_id == canvas.window().id() => break 'running,
+                    Event::Window {
+                        window_id,
+                        win_event: WindowEvent::Close,
+                        ..
+                    } => canvas.close_window(*window_id),
                     _ => {}
                 }
             }
 
-            function(iter);
+            function(canvas, iter)?;
 
-            canvas.window().gl_swap_window();
+            canvas.swap_windows();
         }
+
+        Ok(())
     }
 }
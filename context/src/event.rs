@@ -0,0 +1,90 @@
+extern crate sdl2;
+
+use std::any::type_name;
+use std::any::Any;
+use std::any::TypeId;
+use std::sync::mpsc;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+#[derive(Debug)]
+pub enum EventError {
+    RegisteringEventFailed(String),
+    PushingEventFailed(String),
+}
+
+enum Sender {
+    Sdl(sdl2::event::EventSender),
+    Channel(mpsc::Sender<Box<dyn Any + Send>>),
+}
+
+// can be cloned and moved to other threads, pushed events wake up the canvas loop once the sender
+// is connected to it, the clones share the connection
+#[derive(Clone)]
+pub struct EventSender {
+    sender: Arc<Mutex<Sender>>,
+    // only registered types can be pushed, like the custom events of sdl
+    registered: Arc<Mutex<Vec<TypeId>>>,
+}
+
+impl EventSender {
+    // for loops without a canvas, the pushed events are taken from the receiver
+    pub fn channel() -> (Self, Receiver<Box<dyn Any + Send>>) {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Self {
+            sender: Arc::new(Mutex::new(Sender::Channel(sender))),
+            registered: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        (sender, receiver)
+    }
+
+    pub fn register<T: Any>(&self) {
+        let mut registered = self
+            .registered
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        if !registered.contains(&TypeId::of::<T>()) {
+            registered.push(TypeId::of::<T>());
+        }
+    }
+
+    // events pushed after this wake up the canvas loop instead of going to the receiver
+    pub(crate) fn connect(&self, sender: sdl2::event::EventSender) -> Result<(), EventError> {
+        *self
+            .sender
+            .lock()
+            .map_err(|error| EventError::RegisteringEventFailed(error.to_string()))? =
+            Sender::Sdl(sender);
+
+        Ok(())
+    }
+
+    pub fn push<T: Any + Send>(&self, event: T) -> Result<(), EventError> {
+        let registered = self
+            .registered
+            .lock()
+            .map_err(|error| EventError::PushingEventFailed(error.to_string()))?
+            .contains(&TypeId::of::<T>());
+        if !registered {
+            return Err(EventError::PushingEventFailed(format!(
+                "{} is not registered",
+                type_name::<T>()
+            )));
+        }
+
+        match &*self
+            .sender
+            .lock()
+            .map_err(|error| EventError::PushingEventFailed(error.to_string()))?
+        {
+            Sender::Sdl(sender) => sender
+                .push_custom_event(event)
+                .map_err(EventError::PushingEventFailed),
+            Sender::Channel(sender) => sender
+                .send(Box::new(event))
+                .map_err(|error| EventError::PushingEventFailed(error.to_string())),
+        }
+    }
+}
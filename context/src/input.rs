@@ -1,14 +1,58 @@
 #![allow(dead_code)]
 
+use std::collections::VecDeque;
+use std::time::Duration;
+use std::time::Instant;
+
 use super::keyboard;
 use super::keyboard::Action;
 use super::keyboard::Button;
 use super::keyboard::Key;
+use super::sequence::InputRecord;
+use super::sequence::InputSequence;
+
+const HISTORY_CAPACITY: usize = 32;
+const DEFAULT_TAP_WINDOW: Duration = Duration::from_millis(250);
 
 pub fn update(input: &mut Input, key: Key, button: Button) {
+    update_at(input, key, button, Instant::now());
+}
+
+pub fn update_at(input: &mut Input, key: Key, button: Button, time: Instant) {
+    let action = *button.action();
     let current = input.current[key as usize].clone();
     input.before[key as usize] = current;
     input.current[key as usize] = button;
+
+    advance(input, time);
+
+    let timing = &mut input.timings[key as usize];
+    match action {
+        Action::Press => {
+            timing.taps = match timing.pressed_at {
+                Some(pressed_at) if time.duration_since(pressed_at) <= input.tap_window => {
+                    timing.taps + 1
+                }
+                _ => 1,
+            };
+            timing.pressed_at = Some(time);
+            // only presses advance sequences
+            record(input, time);
+        }
+        Action::Release => {
+            if let Some(pressed_at) = timing.pressed_at {
+                timing.last_hold = time.duration_since(pressed_at);
+            }
+            timing.released_at = Some(time);
+        }
+        Action::Repeat => (),
+    }
+}
+
+pub fn advance(input: &mut Input, time: Instant) {
+    if time > input.time {
+        input.time = time;
+    }
 }
 
 pub fn clean_up(input: &mut Input) {
@@ -29,11 +73,42 @@ pub fn clean_up(input: &mut Input) {
                 before.set_action(Action::Release);
             }
         });
+
+    input.records_this_frame = 0;
+}
+
+fn record(input: &mut Input, time: Instant) {
+    let held: Vec<Key> = input
+        .current
+        .iter()
+        .filter(|button| *button.action() != Action::Release)
+        .map(|button| *button.key())
+        .collect();
+
+    if input.history.len() == HISTORY_CAPACITY {
+        input.history.pop_front();
+    }
+
+    input.history.push_back(InputRecord::new(time, held));
+    input.records_this_frame += 1;
+}
+
+#[derive(Copy, Clone, Default)]
+struct KeyTiming {
+    pressed_at: Option<Instant>,
+    released_at: Option<Instant>,
+    last_hold: Duration,
+    taps: u32,
 }
 
 pub struct Input {
     current: [Button; keyboard::KEYS_COUNT],
     before: [Button; keyboard::KEYS_COUNT],
+    timings: [KeyTiming; keyboard::KEYS_COUNT],
+    history: VecDeque<InputRecord>,
+    records_this_frame: usize,
+    tap_window: Duration,
+    time: Instant,
 }
 
 impl Input {
@@ -41,6 +116,11 @@ impl Input {
         Input {
             current: keyboard::keys_collection(),
             before: keyboard::keys_collection(),
+            timings: [KeyTiming::default(); keyboard::KEYS_COUNT],
+            history: VecDeque::with_capacity(HISTORY_CAPACITY),
+            records_this_frame: 0,
+            tap_window: DEFAULT_TAP_WINDOW,
+            time: Instant::now(),
         }
     }
 
@@ -63,6 +143,60 @@ impl Input {
 
         current && before
     }
+
+    pub fn pressed_at(&self, key: Key) -> Option<Instant> {
+        self.timings[key as usize].pressed_at
+    }
+
+    pub fn released_at(&self, key: Key) -> Option<Instant> {
+        self.timings[key as usize].released_at
+    }
+
+    // how long the key is held down so far, zero if it is not held
+    pub fn hold_duration(&self, key: Key) -> Duration {
+        match self.timings[key as usize].pressed_at {
+            Some(pressed_at) if self.is_key_hold(key) => self.time.duration_since(pressed_at),
+            _ => Duration::from_secs(0),
+        }
+    }
+
+    // how long the key was held down before its last release
+    pub fn last_hold_duration(&self, key: Key) -> Duration {
+        self.timings[key as usize].last_hold
+    }
+
+    pub fn tap_count(&self, key: Key) -> u32 {
+        self.timings[key as usize].taps
+    }
+
+    pub fn is_key_tapped(&self, key: Key, taps: u32) -> bool {
+        self.is_key_pressed(key) && self.tap_count(key) == taps
+    }
+
+    pub fn is_key_double_tapped(&self, key: Key) -> bool {
+        self.is_key_tapped(key, 2)
+    }
+
+    pub fn is_key_triple_tapped(&self, key: Key) -> bool {
+        self.is_key_tapped(key, 3)
+    }
+
+    pub fn tap_window(&self) -> Duration {
+        self.tap_window
+    }
+
+    pub fn set_tap_window(&mut self, tap_window: Duration) {
+        self.tap_window = tap_window;
+    }
+
+    pub fn history(&self) -> impl Iterator<Item = &InputRecord> {
+        self.history.iter()
+    }
+
+    // true in the frame the last step of the sequence got completed
+    pub fn is_sequence_matched(&self, sequence: &InputSequence) -> bool {
+        self.records_this_frame > 0 && sequence.matches(&self.history)
+    }
 }
 
 impl Default for Input {
@@ -1,7 +1,9 @@
 extern crate sdl2;
 
 pub mod canvas;
+pub mod event;
 pub mod input;
 pub mod keyboard;
+pub mod sequence;
 
 pub use sdl2::event::Event;
@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+use std::time::Instant;
+
+use super::keyboard::Key;
+
+#[derive(Clone, Debug)]
+pub struct InputRecord {
+    time: Instant,
+    held: Vec<Key>,
+}
+
+impl InputRecord {
+    pub(crate) fn new(time: Instant, held: Vec<Key>) -> Self {
+        Self { time, held }
+    }
+
+    pub fn time(&self) -> Instant {
+        self.time
+    }
+
+    pub fn held(&self) -> &[Key] {
+        &self.held
+    }
+}
+
+// a step is a set of keys which have to be held together, e.g. down + forward
+#[derive(Clone, Debug)]
+pub struct InputSequence {
+    steps: Vec<Vec<Key>>,
+    keys: Vec<Key>,
+    max_interval: Duration,
+}
+
+impl InputSequence {
+    pub fn new(max_interval: Duration) -> Self {
+        Self {
+            steps: Vec::new(),
+            keys: Vec::new(),
+            max_interval,
+        }
+    }
+
+    pub fn then(mut self, keys: &[Key]) -> Self {
+        for key in keys {
+            if !self.keys.contains(key) {
+                self.keys.push(*key);
+            }
+        }
+
+        self.steps.push(keys.to_vec());
+        self
+    }
+
+    pub fn steps(&self) -> &[Vec<Key>] {
+        &self.steps
+    }
+
+    pub fn max_interval(&self) -> Duration {
+        self.max_interval
+    }
+
+    // the newest record has to complete the last step, earlier steps are searched backwards
+    // and records which do not belong to the sequence are skipped as long as the
+    // interval between two matched steps is not exceeded
+    pub(crate) fn matches(&self, history: &VecDeque<InputRecord>) -> bool {
+        let mut records = history.iter().rev();
+        let mut steps = self.steps.iter().rev();
+
+        let mut previous = match (records.next(), steps.next()) {
+            (Some(record), Some(step)) if self.is_step(record, step) => record,
+            _ => return false,
+        };
+
+        for step in steps {
+            let matched = loop {
+                match records.next() {
+                    Some(record) => {
+                        if previous.time.duration_since(record.time) > self.max_interval {
+                            return false;
+                        }
+
+                        if self.is_step(record, step) {
+                            break record;
+                        }
+                    }
+                    None => return false,
+                }
+            };
+
+            previous = matched;
+        }
+
+        true
+    }
+
+    fn is_step(&self, record: &InputRecord, step: &[Key]) -> bool {
+        let held = record
+            .held
+            .iter()
+            .filter(|key| self.keys.contains(key))
+            .count();
+
+        held == step.len() && step.iter().all(|key| record.held.contains(key))
+    }
+}
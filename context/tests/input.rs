@@ -1,5 +1,8 @@
 extern crate context;
 
+use std::time::Duration;
+use std::time::Instant;
+
 use context::input;
 use context::input::Input;
 use context::keyboard::Action;
@@ -182,4 +185,95 @@ mod test {
         // Key should not be released
         assert!(!input.is_key_released(Key::A));
     }
+
+    #[test]
+    fn input_hold_duration() {
+        let mut input = Input::new();
+        let start = Instant::now();
+
+        input::update_at(
+            &mut input,
+            Key::A,
+            Button::new(Key::A, Action::Press, Modifier::Unknown),
+            start,
+        );
+
+        assert_eq!(input.pressed_at(Key::A), Some(start));
+        assert_eq!(input.hold_duration(Key::A), Duration::from_millis(0));
+
+        input::advance(&mut input, start + Duration::from_millis(500));
+
+        assert_eq!(input.hold_duration(Key::A), Duration::from_millis(500));
+
+        input::update_at(
+            &mut input,
+            Key::A,
+            Button::new(Key::A, Action::Release, Modifier::Unknown),
+            start + Duration::from_millis(800),
+        );
+
+        assert_eq!(input.hold_duration(Key::A), Duration::from_millis(0));
+        assert_eq!(input.last_hold_duration(Key::A), Duration::from_millis(800));
+        assert_eq!(
+            input.released_at(Key::A),
+            Some(start + Duration::from_millis(800))
+        );
+    }
+
+    #[test]
+    fn input_is_key_double_tapped() {
+        let mut input = Input::new();
+        let start = Instant::now();
+
+        for (i, action) in [Action::Press, Action::Release, Action::Press]
+            .iter()
+            .enumerate()
+        {
+            input::clean_up(&mut input);
+            input::update_at(
+                &mut input,
+                Key::A,
+                Button::new(Key::A, *action, Modifier::Unknown),
+                start + Duration::from_millis(100 * i as u64),
+            );
+        }
+
+        assert_eq!(input.tap_count(Key::A), 2);
+        assert!(input.is_key_double_tapped(Key::A));
+        assert!(!input.is_key_triple_tapped(Key::A));
+
+        input::clean_up(&mut input);
+
+        // the second tap is only reported in the frame it happened
+        assert!(!input.is_key_double_tapped(Key::A));
+    }
+
+    #[test]
+    fn input_tap_outside_of_window() {
+        let mut input = Input::new();
+        let start = Instant::now();
+        input.set_tap_window(Duration::from_millis(200));
+
+        input::update_at(
+            &mut input,
+            Key::A,
+            Button::new(Key::A, Action::Press, Modifier::Unknown),
+            start,
+        );
+        input::update_at(
+            &mut input,
+            Key::A,
+            Button::new(Key::A, Action::Release, Modifier::Unknown),
+            start + Duration::from_millis(100),
+        );
+        input::update_at(
+            &mut input,
+            Key::A,
+            Button::new(Key::A, Action::Press, Modifier::Unknown),
+            start + Duration::from_millis(300),
+        );
+
+        assert_eq!(input.tap_count(Key::A), 1);
+        assert!(input.is_key_tapped(Key::A, 1));
+    }
 }
@@ -0,0 +1,126 @@
+extern crate context;
+
+use std::time::Duration;
+use std::time::Instant;
+
+use context::input;
+use context::input::Input;
+use context::keyboard::Action;
+use context::keyboard::Button;
+use context::keyboard::Key;
+use context::keyboard::Modifier;
+use context::sequence::InputSequence;
+
+fn press(input: &mut Input, key: Key, time: Instant) {
+    input::update_at(
+        input,
+        key,
+        Button::new(key, Action::Press, Modifier::Unknown),
+        time,
+    );
+}
+
+fn release(input: &mut Input, key: Key, time: Instant) {
+    input::update_at(
+        input,
+        key,
+        Button::new(key, Action::Release, Modifier::Unknown),
+        time,
+    );
+}
+
+fn fireball() -> InputSequence {
+    InputSequence::new(Duration::from_millis(200))
+        .then(&[Key::Down])
+        .then(&[Key::Down, Key::Right])
+        .then(&[Key::Right, Key::P])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sequence_matched() {
+        let mut input = Input::new();
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        press(&mut input, Key::Down, ms(0));
+        press(&mut input, Key::Right, ms(50));
+        release(&mut input, Key::Down, ms(100));
+
+        assert!(!input.is_sequence_matched(&fireball()));
+
+        press(&mut input, Key::P, ms(150));
+
+        assert!(input.is_sequence_matched(&fireball()));
+
+        input::clean_up(&mut input);
+
+        // only reported in the frame the last step happened
+        assert!(!input.is_sequence_matched(&fireball()));
+    }
+
+    #[test]
+    fn sequence_ignores_unrelated_keys() {
+        let mut input = Input::new();
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        press(&mut input, Key::Down, ms(0));
+        press(&mut input, Key::A, ms(20));
+        press(&mut input, Key::Right, ms(50));
+        release(&mut input, Key::Down, ms(100));
+        press(&mut input, Key::P, ms(150));
+
+        assert!(input.is_sequence_matched(&fireball()));
+    }
+
+    #[test]
+    fn sequence_too_slow() {
+        let mut input = Input::new();
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        press(&mut input, Key::Down, ms(0));
+        press(&mut input, Key::Right, ms(300));
+        release(&mut input, Key::Down, ms(350));
+        press(&mut input, Key::P, ms(400));
+
+        assert!(!input.is_sequence_matched(&fireball()));
+    }
+
+    #[test]
+    fn sequence_wrong_order() {
+        let mut input = Input::new();
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+
+        press(&mut input, Key::Right, ms(0));
+        press(&mut input, Key::Down, ms(50));
+        release(&mut input, Key::Right, ms(100));
+        press(&mut input, Key::P, ms(150));
+
+        assert!(!input.is_sequence_matched(&fireball()));
+    }
+
+    #[test]
+    fn sequence_ignores_releases() {
+        let mut input = Input::new();
+        let start = Instant::now();
+        let ms = |ms| start + Duration::from_millis(ms);
+        let roll = InputSequence::new(Duration::from_millis(200))
+            .then(&[Key::Down, Key::Right])
+            .then(&[Key::Right]);
+
+        press(&mut input, Key::Down, ms(0));
+        press(&mut input, Key::Right, ms(20));
+        input::clean_up(&mut input);
+        release(&mut input, Key::Down, ms(60));
+
+        // letting go of down leaves right held, but only a press completes a step
+        assert!(!input.is_sequence_matched(&roll));
+        assert_eq!(input.history().count(), 2);
+    }
+}
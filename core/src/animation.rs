@@ -0,0 +1,100 @@
+extern crate legion;
+
+use std::collections::HashMap;
+
+use legion::entity::Entity;
+use legion::query::IntoQuery;
+use legion::query::Write;
+use legion::systems::schedule::Schedule;
+use legion::systems::SystemBuilder;
+
+use crate::component::AnimationClip;
+use crate::component::AnimationStateMachine;
+use crate::component::SpriteAnimation;
+use crate::events::Events;
+use crate::tick::Tick;
+
+// sent whenever a sprite animation completes its clip, looping clips send it every cycle
+#[derive(Clone, PartialEq, Debug)]
+pub struct AnimationFinished {
+    entity: Entity,
+    clip: String,
+}
+
+impl AnimationFinished {
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    pub fn clip(&self) -> &str {
+        &self.clip
+    }
+}
+
+// the named clips sprite animations refer to
+pub struct AnimationClips {
+    clips: HashMap<String, AnimationClip>,
+}
+
+impl AnimationClips {
+    pub(crate) fn new() -> Self {
+        Self {
+            clips: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, name: &str, clip: AnimationClip) {
+        self.clips.insert(name.to_owned(), clip);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.clips.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.clips.keys().map(|name| name.as_str())
+    }
+}
+
+// advances the sprite animations of every updated scene, then lets the state machines switch clips
+pub(crate) fn create_system() -> Schedule {
+    let system = SystemBuilder::new("AnimationSystem")
+        .read_resource::<Tick>()
+        .read_resource::<AnimationClips>()
+        .write_resource::<Events<AnimationFinished>>()
+        .with_query(<Write<SpriteAnimation>>::query())
+        .with_query(<(Write<AnimationStateMachine>, Write<SpriteAnimation>)>::query())
+        .build(
+            |_, mut world, (tick, clips, events), (animations, machines)| {
+                for (entity, mut animation) in animations.iter_entities_mut(&mut world) {
+                    let clip = match clips.get(animation.clip()) {
+                        Some(clip) => clip,
+                        None => continue,
+                    };
+
+                    for _ in 0..animation.advance(clip, tick.delta_time()) {
+                        events.send(AnimationFinished {
+                            entity,
+                            clip: animation.clip().to_owned(),
+                        });
+                    }
+                }
+
+                for (mut machine, mut animation) in machines.iter_mut(&mut world) {
+                    machine.update(animation.is_finished());
+
+                    // also covers the initial state, which no transition leads to
+                    match machine.clip() {
+                        Some(clip) if clip != animation.clip() => animation.play(clip),
+                        _ => (),
+                    }
+                }
+            },
+        );
+
+    Schedule::builder().add_system(system).build()
+}
@@ -0,0 +1,118 @@
+extern crate legion;
+
+use std::mem;
+
+use legion::entity::Entity;
+use legion::filter::ChunksetFilterData;
+use legion::filter::Filter;
+use legion::storage::Component;
+use legion::world::IntoComponentSource;
+use legion::world::TagLayout;
+use legion::world::TagSet;
+use legion::world::World;
+
+use crate::scene_management::SceneKey;
+
+type Command = Box<dyn FnOnce(&mut World) + Send + Sync>;
+
+pub(crate) fn take<K: SceneKey>(commands: &mut Commands<K>) -> Vec<(Option<K>, Command)> {
+    mem::replace(&mut commands.commands, Vec::new())
+}
+
+// queued commands are applied by the engine after the global systems and after the systems
+// of every scene, without a target scene they go to the scene whose systems queued them
+pub struct Commands<K: SceneKey> {
+    commands: Vec<(Option<K>, Command)>,
+}
+
+impl<K: SceneKey> Commands<K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn scene(&mut self, scene: K) -> SceneCommands<K> {
+        SceneCommands {
+            commands: &mut self.commands,
+            scene: Some(scene),
+        }
+    }
+
+    pub fn spawn<T, C>(&mut self, tags: T, components: C)
+    where
+        T: TagSet + TagLayout + for<'a> Filter<ChunksetFilterData<'a>> + Send + Sync + 'static,
+        C: IntoComponentSource + Send + Sync + 'static,
+    {
+        self.current().spawn(tags, components);
+    }
+
+    pub fn despawn(&mut self, entity: Entity) {
+        self.current().despawn(entity);
+    }
+
+    pub fn add_component<T: Component>(&mut self, entity: Entity, component: T) {
+        self.current().add_component(entity, component);
+    }
+
+    pub fn remove_component<T: Component>(&mut self, entity: Entity) {
+        self.current().remove_component::<T>(entity);
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    fn current(&mut self) -> SceneCommands<K> {
+        SceneCommands {
+            commands: &mut self.commands,
+            scene: None,
+        }
+    }
+}
+
+pub struct SceneCommands<'a, K: SceneKey> {
+    commands: &'a mut Vec<(Option<K>, Command)>,
+    scene: Option<K>,
+}
+
+impl<'a, K: SceneKey> SceneCommands<'a, K> {
+    pub fn spawn<T, C>(&mut self, tags: T, components: C)
+    where
+        T: TagSet + TagLayout + for<'b> Filter<ChunksetFilterData<'b>> + Send + Sync + 'static,
+        C: IntoComponentSource + Send + Sync + 'static,
+    {
+        self.push(move |world| {
+            world.insert(tags, components);
+        });
+    }
+
+    pub fn despawn(&mut self, entity: Entity) {
+        self.push(move |world| {
+            world.delete(entity);
+        });
+    }
+
+    pub fn add_component<T: Component>(&mut self, entity: Entity, component: T) {
+        self.push(move |world| {
+            let _ = world.add_component(entity, component);
+        });
+    }
+
+    pub fn remove_component<T: Component>(&mut self, entity: Entity) {
+        self.push(move |world| {
+            let _ = world.remove_component::<T>(entity);
+        });
+    }
+
+    fn push<F>(&mut self, command: F)
+    where
+        F: FnOnce(&mut World) + Send + Sync + 'static,
+    {
+        self.commands.push((self.scene.clone(), Box::new(command)));
+    }
+}
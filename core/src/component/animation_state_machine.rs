@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Parameter {
+    Bool(bool),
+    Float(f32),
+    Trigger(bool),
+}
+
+// all conditions of a transition have to hold for it to be taken
+#[derive(Clone, PartialEq, Debug)]
+pub enum Condition {
+    Bool(String, bool),
+    Greater(String, f32),
+    Less(String, f32),
+    // consumed when the transition is taken
+    Trigger(String),
+    // the clip of the current state was played once to its end
+    Finished,
+}
+
+impl Condition {
+    pub fn is_true(name: &str) -> Self {
+        Condition::Bool(name.to_owned(), true)
+    }
+
+    pub fn is_false(name: &str) -> Self {
+        Condition::Bool(name.to_owned(), false)
+    }
+
+    pub fn greater(name: &str, value: f32) -> Self {
+        Condition::Greater(name.to_owned(), value)
+    }
+
+    pub fn less(name: &str, value: f32) -> Self {
+        Condition::Less(name.to_owned(), value)
+    }
+
+    pub fn trigger(name: &str) -> Self {
+        Condition::Trigger(name.to_owned())
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct StateTransition {
+    // none for transitions that can be taken from every state
+    from: Option<String>,
+    to: String,
+    conditions: Vec<Condition>,
+}
+
+// picks the clip of a sprite animation on the same entity, transitions are checked in order
+#[derive(Clone, PartialEq, Debug)]
+pub struct AnimationStateMachine {
+    states: HashMap<String, String>,
+    transitions: Vec<StateTransition>,
+    parameters: HashMap<String, Parameter>,
+    state: String,
+}
+
+impl AnimationStateMachine {
+    pub fn new(state: &str, clip: &str) -> Self {
+        let mut states = HashMap::new();
+        states.insert(state.to_owned(), clip.to_owned());
+
+        Self {
+            states,
+            transitions: Vec::new(),
+            parameters: HashMap::new(),
+            state: state.to_owned(),
+        }
+    }
+
+    pub fn with_state(mut self, state: &str, clip: &str) -> Self {
+        self.states.insert(state.to_owned(), clip.to_owned());
+        self
+    }
+
+    pub fn with_transition(mut self, from: &str, to: &str, conditions: Vec<Condition>) -> Self {
+        self.transitions.push(StateTransition {
+            from: Some(from.to_owned()),
+            to: to.to_owned(),
+            conditions,
+        });
+        self
+    }
+
+    pub fn with_any_transition(mut self, to: &str, conditions: Vec<Condition>) -> Self {
+        self.transitions.push(StateTransition {
+            from: None,
+            to: to.to_owned(),
+            conditions,
+        });
+        self
+    }
+
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn clip(&self) -> Option<&str> {
+        self.states.get(&self.state).map(|clip| clip.as_str())
+    }
+
+    pub fn set_bool(&mut self, name: &str, value: bool) {
+        self.parameters
+            .insert(name.to_owned(), Parameter::Bool(value));
+    }
+
+    pub fn set_float(&mut self, name: &str, value: f32) {
+        self.parameters
+            .insert(name.to_owned(), Parameter::Float(value));
+    }
+
+    // stays set until a transition consumes it
+    pub fn set_trigger(&mut self, name: &str) {
+        self.parameters
+            .insert(name.to_owned(), Parameter::Trigger(true));
+    }
+
+    pub fn reset_trigger(&mut self, name: &str) {
+        self.parameters
+            .insert(name.to_owned(), Parameter::Trigger(false));
+    }
+
+    pub fn bool(&self, name: &str) -> bool {
+        match self.parameters.get(name) {
+            Some(Parameter::Bool(value)) => *value,
+            _ => false,
+        }
+    }
+
+    pub fn float(&self, name: &str) -> f32 {
+        match self.parameters.get(name) {
+            Some(Parameter::Float(value)) => *value,
+            _ => 0.0,
+        }
+    }
+
+    pub fn is_triggered(&self, name: &str) -> bool {
+        match self.parameters.get(name) {
+            Some(Parameter::Trigger(value)) => *value,
+            _ => false,
+        }
+    }
+
+    // takes the first matching transition, returns whether the state changed
+    pub(crate) fn update(&mut self, finished: bool) -> bool {
+        let index = self.transitions.iter().position(|transition| {
+            let from = match &transition.from {
+                Some(from) => *from == self.state,
+                None => transition.to != self.state,
+            };
+
+            from && self.states.contains_key(&transition.to)
+                && transition
+                    .conditions
+                    .iter()
+                    .all(|condition| self.holds(condition, finished))
+        });
+
+        let index = match index {
+            Some(index) => index,
+            None => return false,
+        };
+
+        let transition = self.transitions[index].clone();
+        for condition in &transition.conditions {
+            if let Condition::Trigger(name) = condition {
+                self.reset_trigger(name);
+            }
+        }
+
+        self.state = transition.to;
+        true
+    }
+
+    fn holds(&self, condition: &Condition, finished: bool) -> bool {
+        match condition {
+            Condition::Bool(name, value) => self.bool(name) == *value,
+            Condition::Greater(name, value) => self.float(name) > *value,
+            Condition::Less(name, value) => self.float(name) < *value,
+            Condition::Trigger(name) => self.is_triggered(name),
+            Condition::Finished => finished,
+        }
+    }
+}
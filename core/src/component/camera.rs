@@ -0,0 +1,143 @@
+extern crate math;
+extern crate serde;
+
+use math::Mat4;
+use math::Vec3;
+use math::Vec4;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::component::Transform;
+use crate::post_processing;
+use crate::post_processing::PostEffect;
+use crate::screen;
+
+// renders the scene around the entity's position into the sprite named after the target, before
+// the scene itself is drawn, e.g. for minimaps, mirrors or previews
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Camera {
+    target: String,
+    // of the target in pixels
+    width: u32,
+    height: u32,
+    // multisampled when above zero
+    samples: u32,
+    clear_color: Vec4,
+    // above one the camera shows less of the world
+    zoom: f32,
+    // drawn over the target in their order
+    effects: Vec<PostEffect>,
+}
+
+impl Camera {
+    pub fn new(target: &str, width: u32, height: u32) -> Self {
+        Self {
+            target: target.to_owned(),
+            width,
+            height,
+            samples: 0,
+            clear_color: Vec4::new(0.0, 0.0, 0.0, 0.0),
+            zoom: 1.0,
+            effects: Vec::new(),
+        }
+    }
+
+    pub fn with_samples(mut self, samples: u32) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    pub fn with_clear_color(mut self, clear_color: Vec4) -> Self {
+        self.clear_color = clear_color;
+        self
+    }
+
+    pub fn with_zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    // replaces an effect with the same shader
+    pub fn with_effect(mut self, effect: PostEffect) -> Self {
+        self.add_effect(effect);
+        self
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    pub fn clear_color(&self) -> Vec4 {
+        self.clear_color
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    // like the projection of the screen, around the entity's position and rotation
+    pub fn projection(&self, transform: &Transform) -> Mat4 {
+        let half_width = self.width as f32 / 2.0 / self.zoom;
+        let half_height = self.height as f32 / 2.0 / self.zoom;
+        let position = transform.position();
+
+        Mat4::ortho(
+            -half_width,
+            half_width,
+            -half_height,
+            half_height,
+            -screen::DEPTH_RANGE,
+            screen::DEPTH_RANGE,
+        ) * Mat4::from_rotation_z(-transform.rotation().x)
+            * Mat4::from_translate(Vec3::new(-position.x, -position.y, 0.0))
+    }
+
+    pub fn effects(&self) -> &[PostEffect] {
+        &self.effects
+    }
+
+    pub fn effect_mut(&mut self, shader: &str) -> Option<&mut PostEffect> {
+        post_processing::effect_mut(&mut self.effects, shader)
+    }
+
+    pub fn add_effect(&mut self, effect: PostEffect) {
+        post_processing::add_effect(&mut self.effects, effect);
+    }
+
+    pub fn remove_effect(&mut self, shader: &str) {
+        self.effects.retain(|effect| effect.shader() != shader);
+    }
+
+    pub fn set_target(&mut self, target: &str) {
+        self.target = target.to_owned();
+    }
+
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+    }
+
+    pub fn set_samples(&mut self, samples: u32) {
+        self.samples = samples;
+    }
+
+    pub fn set_clear_color(&mut self, clear_color: Vec4) {
+        self.clear_color = clear_color;
+    }
+
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom;
+    }
+}
@@ -0,0 +1,109 @@
+extern crate math;
+extern crate serde;
+
+use std::collections::BTreeMap;
+
+use math::Vec2;
+use math::Vec4;
+use serde::Deserialize;
+use serde::Serialize;
+
+// a custom property set in a map editor
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Property {
+    Bool(bool),
+    Int(i64),
+    Float(f32),
+    String(String),
+    Color(Vec4),
+    File(String),
+    // the id of another object of the map
+    Object(u32),
+}
+
+impl Property {
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Property::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Property::Int(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    // ints are converted
+    pub fn as_float(&self) -> Option<f32> {
+        match self {
+            Property::Float(value) => Some(*value),
+            Property::Int(value) => Some(*value as f32),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Property::String(value) | Property::File(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+// an object placed in a map editor, e.g. a spawn point or the area of a trigger
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MapObject {
+    id: u32,
+    name: String,
+    class: String,
+    // in world units, zero for points
+    size: Vec2,
+    properties: BTreeMap<String, Property>,
+}
+
+impl MapObject {
+    pub fn new(id: u32, name: &str, class: &str, size: Vec2) -> Self {
+        Self {
+            id,
+            name: name.to_owned(),
+            class: class.to_owned(),
+            size,
+            properties: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_property(mut self, name: &str, property: Property) -> Self {
+        self.properties.insert(name.to_owned(), property);
+        self
+    }
+
+    // unique within its map
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn property(&self, name: &str) -> Option<&Property> {
+        self.properties.get(name)
+    }
+
+    pub fn properties(&self) -> impl Iterator<Item = (&str, &Property)> {
+        self.properties
+            .iter()
+            .map(|(name, property)| (name.as_str(), property))
+    }
+}
@@ -1,5 +1,31 @@
+mod animation_state_machine;
+mod camera;
+mod map_object;
+mod parent;
 mod sprite;
+mod sprite_animation;
+mod text;
+mod tilemap;
+mod timer;
 mod transform;
 
+pub use animation_state_machine::AnimationStateMachine;
+pub use animation_state_machine::Condition;
+pub use camera::Camera;
+pub use map_object::MapObject;
+pub use map_object::Property;
+pub use parent::Parent;
 pub use sprite::Sprite;
+pub use sprite_animation::AnimationClip;
+pub use sprite_animation::AnimationFrame;
+pub use sprite_animation::PlaybackMode;
+pub use sprite_animation::SpriteAnimation;
+pub use text::Text;
+pub use text::TextAlignment;
+pub use tilemap::Tile;
+pub use tilemap::TileLayer;
+pub use tilemap::Tilemap;
+pub use tilemap::Tileset;
+pub use timer::Timer;
+pub use timer::TimerMode;
 pub use transform::Transform;
@@ -0,0 +1,17 @@
+extern crate legion;
+
+use legion::entity::Entity;
+
+// added to the children of an instantiated prefab
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Parent(Entity);
+
+impl Parent {
+    pub(crate) fn new(entity: Entity) -> Self {
+        Self(entity)
+    }
+
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+}
@@ -1,7 +1,160 @@
-pub struct Sprite;
+extern crate graphic;
+extern crate math;
+extern crate serde;
+
+use graphic::region::Region;
+use math::Vec2;
+use math::Vec4;
+use serde::Deserialize;
+use serde::Serialize;
+
+// draws the sprite with the given name, sprites with a lower layer and order are drawn first
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Sprite {
+    name: String,
+    // the whole image when none, otherwise relative to it
+    region: Option<Region>,
+    tint: Vec4,
+    opacity: f32,
+    flip_x: bool,
+    flip_y: bool,
+    // relative to the size, (0, 0) is the bottom left corner
+    // the pivot of the sprite asset or the center when none
+    pivot: Option<Vec2>,
+    layer: i32,
+    order: i32,
+}
 
 impl Sprite {
     pub fn new(name: &str) -> Self {
-        Self
+        Self {
+            name: name.to_owned(),
+            region: None,
+            tint: Vec4::new(1.0, 1.0, 1.0, 1.0),
+            opacity: 1.0,
+            flip_x: false,
+            flip_y: false,
+            pivot: None,
+            layer: 0,
+            order: 0,
+        }
+    }
+
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    pub fn with_tint(mut self, tint: Vec4) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.set_opacity(opacity);
+        self
+    }
+
+    pub fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+        self
+    }
+
+    pub fn with_pivot(mut self, pivot: Vec2) -> Self {
+        self.pivot = Some(pivot);
+        self
+    }
+
+    pub fn with_layer(mut self, layer: i32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn region(&self) -> Option<Region> {
+        self.region
+    }
+
+    pub fn tint(&self) -> Vec4 {
+        self.tint
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    // the tint with the opacity applied to its alpha
+    pub fn color(&self) -> Vec4 {
+        Vec4::new(
+            self.tint.x,
+            self.tint.y,
+            self.tint.z,
+            self.tint.w * self.opacity,
+        )
+    }
+
+    pub fn flip_x(&self) -> bool {
+        self.flip_x
+    }
+
+    pub fn flip_y(&self) -> bool {
+        self.flip_y
+    }
+
+    pub fn pivot(&self) -> Option<Vec2> {
+        self.pivot
+    }
+
+    pub fn layer(&self) -> i32 {
+        self.layer
+    }
+
+    pub fn order(&self) -> i32 {
+        self.order
+    }
+
+    pub fn set_name(&mut self, name: &str) {
+        self.name = name.to_owned();
+    }
+
+    pub fn set_region(&mut self, region: Option<Region>) {
+        self.region = region;
+    }
+
+    pub fn set_tint(&mut self, tint: Vec4) {
+        self.tint = tint;
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    pub fn set_flip_x(&mut self, flip_x: bool) {
+        self.flip_x = flip_x;
+    }
+
+    pub fn set_flip_y(&mut self, flip_y: bool) {
+        self.flip_y = flip_y;
+    }
+
+    pub fn set_pivot(&mut self, pivot: Option<Vec2>) {
+        self.pivot = pivot;
+    }
+
+    pub fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
+
+    pub fn set_order(&mut self, order: i32) {
+        self.order = order;
     }
 }
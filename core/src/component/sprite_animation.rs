@@ -0,0 +1,256 @@
+extern crate graphic;
+
+use std::ops::Range;
+
+use graphic::region::Region;
+
+// frames shorter than this would make a large delta time step through them forever
+const MIN_FRAME_DURATION: f32 = 0.001;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PlaybackMode {
+    Once,
+    Loop,
+    PingPong,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AnimationFrame {
+    region: Region,
+    duration: f32,
+}
+
+impl AnimationFrame {
+    pub fn new(region: Region, duration: f32) -> Self {
+        Self {
+            region,
+            duration: duration.max(MIN_FRAME_DURATION),
+        }
+    }
+
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AnimationClip {
+    frames: Vec<AnimationFrame>,
+    mode: PlaybackMode,
+}
+
+impl AnimationClip {
+    pub fn new(mode: PlaybackMode) -> Self {
+        Self {
+            frames: Vec::new(),
+            mode,
+        }
+    }
+
+    // every region is shown for the same duration, e.g. the regions of a sliced sprite sheet
+    pub fn from_regions(regions: &[Region], frame_duration: f32, mode: PlaybackMode) -> Self {
+        Self {
+            frames: regions
+                .iter()
+                .map(|region| AnimationFrame::new(*region, frame_duration))
+                .collect(),
+            mode,
+        }
+    }
+
+    // slices a sheet of the given size into a grid and takes the frames in the range
+    pub fn from_grid(
+        sheet_width: u32,
+        sheet_height: u32,
+        frame_width: u32,
+        frame_height: u32,
+        frames: Range<usize>,
+        frame_duration: f32,
+        mode: PlaybackMode,
+    ) -> Self {
+        let regions = Region::grid(sheet_width, sheet_height, frame_width, frame_height);
+        let end = frames.end.min(regions.len());
+        let start = frames.start.min(end);
+
+        Self::from_regions(&regions[start..end], frame_duration, mode)
+    }
+
+    pub fn with_frame(mut self, region: Region, duration: f32) -> Self {
+        self.frames.push(AnimationFrame::new(region, duration));
+        self
+    }
+
+    pub fn frames(&self) -> &[AnimationFrame] {
+        &self.frames
+    }
+
+    pub fn mode(&self) -> PlaybackMode {
+        self.mode
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.duration).sum()
+    }
+}
+
+// plays a clip of the animation clips resource, the engine advances it with the scaled delta time
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpriteAnimation {
+    clip: String,
+    frame: usize,
+    elapsed: f32,
+    speed: f32,
+    forward: bool,
+    playing: bool,
+    finished: bool,
+    region: Option<Region>,
+}
+
+impl SpriteAnimation {
+    pub fn new(clip: &str) -> Self {
+        Self {
+            clip: clip.to_owned(),
+            frame: 0,
+            elapsed: 0.0,
+            speed: 1.0,
+            forward: true,
+            playing: true,
+            finished: false,
+            region: None,
+        }
+    }
+
+    // keeps the playback position if the clip is already playing
+    pub fn play(&mut self, clip: &str) {
+        if self.clip != clip {
+            self.clip = clip.to_owned();
+            self.restart();
+        }
+
+        self.playing = true;
+    }
+
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.elapsed = 0.0;
+        self.forward = true;
+        self.finished = false;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn resume(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing && !self.finished
+    }
+
+    // only clips played once finish
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn clip(&self) -> &str {
+        &self.clip
+    }
+
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    // the region of the current frame, none until the animation got advanced once
+    pub fn region(&self) -> Option<Region> {
+        self.region
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    // returns how often the clip got completed during this step
+    pub(crate) fn advance(&mut self, clip: &AnimationClip, delta_time: f32) -> u32 {
+        let frames = clip.frames();
+
+        if frames.is_empty() {
+            self.region = None;
+            return 0;
+        }
+
+        if self.frame >= frames.len() {
+            self.frame = frames.len() - 1;
+        }
+
+        let mut completed = 0;
+
+        if self.is_playing() {
+            self.elapsed += delta_time * self.speed;
+
+            while self.elapsed >= frames[self.frame].duration() {
+                self.elapsed -= frames[self.frame].duration();
+
+                if self.step(clip.mode(), frames.len()) {
+                    completed += 1;
+                }
+
+                if self.finished {
+                    self.elapsed = 0.0;
+                    break;
+                }
+            }
+        }
+
+        self.region = Some(frames[self.frame].region());
+        completed
+    }
+
+    fn step(&mut self, mode: PlaybackMode, len: usize) -> bool {
+        let last = len - 1;
+
+        match mode {
+            PlaybackMode::Once => {
+                if self.frame == last {
+                    self.finished = true;
+                    return true;
+                }
+                self.frame += 1;
+            }
+            PlaybackMode::Loop => {
+                if self.frame == last {
+                    self.frame = 0;
+                    return true;
+                }
+                self.frame += 1;
+            }
+            PlaybackMode::PingPong => {
+                if self.forward {
+                    if self.frame == last {
+                        self.forward = false;
+                        self.frame = last.saturating_sub(1);
+                    } else {
+                        self.frame += 1;
+                    }
+                } else if self.frame == 0 {
+                    self.forward = true;
+                    self.frame = last.min(1);
+                    return true;
+                } else {
+                    self.frame -= 1;
+                }
+            }
+        }
+
+        false
+    }
+}
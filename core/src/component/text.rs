@@ -0,0 +1,170 @@
+extern crate math;
+extern crate serde;
+
+use math::Vec2;
+use math::Vec4;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum TextAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+// draws a string with the font of the given name, sorted with sprites by layer and order
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Text {
+    content: String,
+    font: String,
+    // in pixels, bitmap fonts are scaled from the size they got exported with
+    size: f32,
+    color: Vec4,
+    alignment: TextAlignment,
+    // lines are wrapped at the width, lines below the height are cut off
+    bounds: Option<Vec2>,
+    // a multiple of the font's line height
+    line_spacing: f32,
+    // relative to the bounds or the size of the text, (0, 0) is the bottom left corner
+    pivot: Vec2,
+    layer: i32,
+    order: i32,
+}
+
+impl Text {
+    pub fn new(content: &str, font: &str) -> Self {
+        Self {
+            content: content.to_owned(),
+            font: font.to_owned(),
+            size: 16.0,
+            color: Vec4::new(1.0, 1.0, 1.0, 1.0),
+            alignment: TextAlignment::Left,
+            bounds: None,
+            line_spacing: 1.0,
+            pivot: Vec2::new(0.5, 0.5),
+            layer: 0,
+            order: 0,
+        }
+    }
+
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_color(mut self, color: Vec4) -> Self {
+        self.color = color;
+        self
+    }
+
+    pub fn with_alignment(mut self, alignment: TextAlignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub fn with_bounds(mut self, bounds: Vec2) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    pub fn with_line_spacing(mut self, line_spacing: f32) -> Self {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    pub fn with_pivot(mut self, pivot: Vec2) -> Self {
+        self.pivot = pivot;
+        self
+    }
+
+    pub fn with_layer(mut self, layer: i32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn font(&self) -> &str {
+        &self.font
+    }
+
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    pub fn color(&self) -> Vec4 {
+        self.color
+    }
+
+    pub fn alignment(&self) -> TextAlignment {
+        self.alignment
+    }
+
+    pub fn bounds(&self) -> Option<Vec2> {
+        self.bounds
+    }
+
+    pub fn line_spacing(&self) -> f32 {
+        self.line_spacing
+    }
+
+    pub fn pivot(&self) -> Vec2 {
+        self.pivot
+    }
+
+    pub fn layer(&self) -> i32 {
+        self.layer
+    }
+
+    pub fn order(&self) -> i32 {
+        self.order
+    }
+
+    pub fn set_content(&mut self, content: &str) {
+        self.content = content.to_owned();
+    }
+
+    pub fn set_font(&mut self, font: &str) {
+        self.font = font.to_owned();
+    }
+
+    pub fn set_size(&mut self, size: f32) {
+        self.size = size;
+    }
+
+    pub fn set_color(&mut self, color: Vec4) {
+        self.color = color;
+    }
+
+    pub fn set_alignment(&mut self, alignment: TextAlignment) {
+        self.alignment = alignment;
+    }
+
+    pub fn set_bounds(&mut self, bounds: Option<Vec2>) {
+        self.bounds = bounds;
+    }
+
+    pub fn set_line_spacing(&mut self, line_spacing: f32) {
+        self.line_spacing = line_spacing;
+    }
+
+    pub fn set_pivot(&mut self, pivot: Vec2) {
+        self.pivot = pivot;
+    }
+
+    pub fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
+
+    pub fn set_order(&mut self, order: i32) {
+        self.order = order;
+    }
+}
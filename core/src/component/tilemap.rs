@@ -0,0 +1,441 @@
+extern crate graphic;
+extern crate math;
+extern crate serde;
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use graphic::region::Region;
+use math::Vec2;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::component::Transform;
+
+// tiles are rendered in square chunks of this many tiles per side
+const CHUNK_SIZE: u32 = 16;
+
+// unique across all layers, a chunk with an unchanged revision does not need to be rebuilt
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(1);
+
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
+
+// the tile is flipped first and then rotated clockwise in quarter turns
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct Tile {
+    index: u32,
+    flip_x: bool,
+    flip_y: bool,
+    rotation: u8,
+}
+
+impl Tile {
+    pub fn new(index: u32) -> Self {
+        Self {
+            index,
+            flip_x: false,
+            flip_y: false,
+            rotation: 0,
+        }
+    }
+
+    pub fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+        self
+    }
+
+    pub fn with_rotation(mut self, quarter_turns: u8) -> Self {
+        self.rotation = quarter_turns % 4;
+        self
+    }
+
+    // counted row by row from the top left tile of the tileset
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn flip_x(&self) -> bool {
+        self.flip_x
+    }
+
+    pub fn flip_y(&self) -> bool {
+        self.flip_y
+    }
+
+    // quarter turns clockwise
+    pub fn rotation(&self) -> u8 {
+        self.rotation
+    }
+}
+
+// a sprite cut into tiles row by row, with a margin around and spacing between them
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Tileset {
+    sprite: String,
+    tile_width: u32,
+    tile_height: u32,
+    margin: u32,
+    spacing: u32,
+}
+
+impl Tileset {
+    pub fn new(sprite: &str, tile_width: u32, tile_height: u32) -> Self {
+        Self {
+            sprite: sprite.to_owned(),
+            tile_width,
+            tile_height,
+            margin: 0,
+            spacing: 0,
+        }
+    }
+
+    pub fn with_margin(mut self, margin: u32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    pub fn with_spacing(mut self, spacing: u32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    pub fn sprite(&self) -> &str {
+        &self.sprite
+    }
+
+    pub fn tile_width(&self) -> u32 {
+        self.tile_width
+    }
+
+    pub fn tile_height(&self) -> u32 {
+        self.tile_height
+    }
+
+    pub fn margin(&self) -> u32 {
+        self.margin
+    }
+
+    pub fn spacing(&self) -> u32 {
+        self.spacing
+    }
+
+    // the number of tiles fitting into the sprite's image
+    pub fn count(&self, image_width: u32, image_height: u32) -> u32 {
+        self.columns(image_width)
+            * fitting(image_height, self.tile_height, self.margin, self.spacing)
+    }
+
+    // relative to the sprite's image, none when the index is outside of it
+    pub fn region(&self, index: u32, image_width: u32, image_height: u32) -> Option<Region> {
+        if index >= self.count(image_width, image_height) {
+            return None;
+        }
+
+        let columns = self.columns(image_width);
+        let column = index % columns;
+        let row = index / columns;
+
+        Some(Region::new(
+            self.margin + column * (self.tile_width + self.spacing),
+            self.margin + row * (self.tile_height + self.spacing),
+            self.tile_width,
+            self.tile_height,
+        ))
+    }
+
+    fn columns(&self, image_width: u32) -> u32 {
+        fitting(image_width, self.tile_width, self.margin, self.spacing)
+    }
+}
+
+fn fitting(size: u32, tile_size: u32, margin: u32, spacing: u32) -> u32 {
+    if tile_size == 0 || size < margin * 2 + tile_size {
+        return 0;
+    }
+
+    (size - margin * 2 + spacing) / (tile_size + spacing)
+}
+
+// a grid of tiles, (0, 0) is the bottom left tile
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TileLayer {
+    name: String,
+    width: u32,
+    height: u32,
+    tiles: Vec<Option<Tile>>,
+    // sprites and tiles with a lower layer are drawn first, tiles below sprites of the same one
+    layer: i32,
+    opacity: f32,
+    visible: bool,
+    // the revision of chunks that were not changed since the layer got created
+    #[serde(skip, default = "next_revision")]
+    revision: u64,
+    #[serde(skip)]
+    revisions: Vec<u64>,
+}
+
+impl TileLayer {
+    pub fn new(name: &str, width: u32, height: u32) -> Self {
+        Self {
+            name: name.to_owned(),
+            width,
+            height,
+            tiles: vec![None; (width * height) as usize],
+            layer: 0,
+            opacity: 1.0,
+            visible: true,
+            revision: next_revision(),
+            revisions: Vec::new(),
+        }
+    }
+
+    pub fn with_layer(mut self, layer: i32) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.set_opacity(opacity);
+        self
+    }
+
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn layer(&self) -> i32 {
+        self.layer
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    // none for empty tiles and positions outside of the layer
+    pub fn tile(&self, x: i32, y: i32) -> Option<Tile> {
+        let index = self.index(x, y)?;
+        self.tiles[index]
+    }
+
+    // row by row starting at the bottom left tile
+    pub fn tiles(&self) -> &[Option<Tile>] {
+        &self.tiles
+    }
+
+    pub fn set_layer(&mut self, layer: i32) {
+        self.layer = layer;
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        // the opacity is part of the vertices of every chunk
+        self.revision = next_revision();
+        self.revisions.clear();
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    // returns false for positions outside of the layer
+    pub fn set_tile(&mut self, x: i32, y: i32, tile: Option<Tile>) -> bool {
+        let index = match self.index(x, y) {
+            Some(index) => index,
+            None => return false,
+        };
+
+        if self.tiles[index] != tile {
+            self.tiles[index] = tile;
+            self.touch(x as u32 / CHUNK_SIZE, y as u32 / CHUNK_SIZE);
+        }
+
+        true
+    }
+
+    pub fn fill(&mut self, tile: Option<Tile>) {
+        for entry in self.tiles.iter_mut() {
+            *entry = tile;
+        }
+
+        self.revision = next_revision();
+        self.revisions.clear();
+    }
+
+    pub(crate) fn chunks(&self) -> (u32, u32) {
+        (
+            self.width.div_ceil(CHUNK_SIZE),
+            self.height.div_ceil(CHUNK_SIZE),
+        )
+    }
+
+    pub(crate) fn revision(&self, chunk_x: u32, chunk_y: u32) -> u64 {
+        let (columns, _) = self.chunks();
+
+        self.revisions
+            .get((chunk_y * columns + chunk_x) as usize)
+            .copied()
+            .unwrap_or(self.revision)
+    }
+
+    // the tiles of the chunk with their position in the layer
+    pub(crate) fn chunk_tiles(
+        &self,
+        chunk_x: u32,
+        chunk_y: u32,
+    ) -> impl Iterator<Item = (u32, u32, Tile)> + '_ {
+        let left = chunk_x * CHUNK_SIZE;
+        let bottom = chunk_y * CHUNK_SIZE;
+        let right = (left + CHUNK_SIZE).min(self.width);
+        let top = (bottom + CHUNK_SIZE).min(self.height);
+
+        (bottom..top).flat_map(move |y| {
+            (left..right).filter_map(move |x| {
+                self.tiles
+                    .get((y * self.width + x) as usize)
+                    .copied()
+                    .flatten()
+                    .map(|tile| (x, y, tile))
+            })
+        })
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return None;
+        }
+
+        // deserialized layers may have fewer tiles than their size
+        let index = (y as u32 * self.width + x as u32) as usize;
+        if index < self.tiles.len() {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    fn touch(&mut self, chunk_x: u32, chunk_y: u32) {
+        let (columns, rows) = self.chunks();
+        if self.revisions.is_empty() {
+            self.revisions = vec![self.revision; (columns * rows) as usize];
+        }
+
+        self.revisions[(chunk_y * columns + chunk_x) as usize] = next_revision();
+    }
+}
+
+// revisions only decide when to rebuild chunks, they are not part of the layer's content
+impl PartialEq for TileLayer {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.width == other.width
+            && self.height == other.height
+            && self.tiles == other.tiles
+            && self.layer == other.layer
+            && self.opacity == other.opacity
+            && self.visible == other.visible
+    }
+}
+
+// the bottom left corner of tile (0, 0) sits at the position of the transform
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Tilemap {
+    tileset: Tileset,
+    // the size of a cell in world units, larger tiles of the tileset reach into the cells above
+    tile_width: u32,
+    tile_height: u32,
+    layers: Vec<TileLayer>,
+}
+
+impl Tilemap {
+    pub fn new(tileset: Tileset, tile_width: u32, tile_height: u32) -> Self {
+        Self {
+            tileset,
+            tile_width,
+            tile_height,
+            layers: Vec::new(),
+        }
+    }
+
+    pub fn with_layer(mut self, layer: TileLayer) -> Self {
+        self.add_layer(layer);
+        self
+    }
+
+    pub fn tileset(&self) -> &Tileset {
+        &self.tileset
+    }
+
+    pub fn tile_width(&self) -> u32 {
+        self.tile_width
+    }
+
+    pub fn tile_height(&self) -> u32 {
+        self.tile_height
+    }
+
+    // in drawing order
+    pub fn layers(&self) -> &[TileLayer] {
+        &self.layers
+    }
+
+    pub fn layer(&self, name: &str) -> Option<&TileLayer> {
+        self.layers.iter().find(|layer| layer.name == name)
+    }
+
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut TileLayer> {
+        self.layers.iter_mut().find(|layer| layer.name == name)
+    }
+
+    pub fn add_layer(&mut self, layer: TileLayer) {
+        self.layers.push(layer);
+    }
+
+    // the tile under the position, may lie outside of the layers
+    pub fn world_to_tile(&self, transform: &Transform, position: Vec2) -> (i32, i32) {
+        let origin = transform.position();
+        let scale = transform.scale();
+        let (sin, cos) = transform.rotation().x.sin_cos();
+
+        let x = position.x - origin.x;
+        let y = position.y - origin.y;
+        let local_x = (x * cos + y * sin) / (scale.x * self.tile_width.max(1) as f32);
+        let local_y = (y * cos - x * sin) / (scale.y * self.tile_height.max(1) as f32);
+
+        (local_x.floor() as i32, local_y.floor() as i32)
+    }
+
+    // the center of the tile
+    pub fn tile_to_world(&self, transform: &Transform, x: i32, y: i32) -> Vec2 {
+        let origin = transform.position();
+        let scale = transform.scale();
+        let (sin, cos) = transform.rotation().x.sin_cos();
+
+        let local_x = (x as f32 + 0.5) * self.tile_width as f32 * scale.x;
+        let local_y = (y as f32 + 0.5) * self.tile_height as f32 * scale.y;
+
+        Vec2::new(
+            origin.x + local_x * cos - local_y * sin,
+            origin.y + local_x * sin + local_y * cos,
+        )
+    }
+}
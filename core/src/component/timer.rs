@@ -0,0 +1,135 @@
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TimerMode {
+    Once,
+    Repeating,
+}
+
+// counts in scaled seconds, the engine ticks timer components and the timers resource
+#[derive(Clone, PartialEq, Debug)]
+pub struct Timer {
+    duration: f32,
+    elapsed: f32,
+    mode: TimerMode,
+    paused: bool,
+    finished: bool,
+    times_finished: u32,
+}
+
+impl Timer {
+    pub fn new(duration: f32, mode: TimerMode) -> Self {
+        Self {
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            mode,
+            paused: false,
+            finished: false,
+            times_finished: 0,
+        }
+    }
+
+    pub fn once(duration: f32) -> Self {
+        Self::new(duration, TimerMode::Once)
+    }
+
+    pub fn repeating(duration: f32) -> Self {
+        Self::new(duration, TimerMode::Repeating)
+    }
+
+    // returns how often the timer finished during this tick
+    pub fn tick(&mut self, delta_time: f32) -> u32 {
+        self.times_finished = 0;
+
+        if self.paused || (self.finished && self.mode == TimerMode::Once) {
+            return 0;
+        }
+
+        self.elapsed += delta_time;
+
+        match self.mode {
+            TimerMode::Once => {
+                if self.elapsed >= self.duration {
+                    self.elapsed = self.duration;
+                    self.finished = true;
+                    self.times_finished = 1;
+                }
+            }
+            TimerMode::Repeating => {
+                if self.duration <= 0.0 {
+                    self.times_finished = 1;
+                } else {
+                    while self.elapsed >= self.duration {
+                        self.elapsed -= self.duration;
+                        self.times_finished += 1;
+                    }
+                }
+                self.finished = self.times_finished > 0;
+            }
+        }
+
+        self.times_finished
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = 0.0;
+        self.finished = false;
+        self.times_finished = 0;
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    // a repeating timer is only finished in the ticks it wrapped around
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn just_finished(&self) -> bool {
+        self.times_finished > 0
+    }
+
+    pub fn times_finished(&self) -> u32 {
+        self.times_finished
+    }
+
+    pub fn mode(&self) -> TimerMode {
+        self.mode
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    pub fn set_duration(&mut self, duration: f32) {
+        self.duration = duration.max(0.0);
+    }
+
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    pub fn remaining(&self) -> f32 {
+        (self.duration - self.elapsed).max(0.0)
+    }
+
+    // 0.0 when started and 1.0 when finished, e.g. for progress bars
+    pub fn fraction(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        (self.elapsed / self.duration).min(1.0)
+    }
+
+    pub fn remaining_fraction(&self) -> f32 {
+        1.0 - self.fraction()
+    }
+}
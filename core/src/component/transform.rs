@@ -1,10 +1,58 @@
 extern crate math;
+extern crate serde;
 
 use math::Vec2;
 use math::Vec3;
+use serde::Deserialize;
+use serde::Serialize;
 
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Transform {
     position: Vec3,
     rotation: Vec2,
     scale: Vec2,
 }
+
+impl Transform {
+    pub fn new(position: Vec3, rotation: Vec2, scale: Vec2) -> Self {
+        Self {
+            position,
+            rotation,
+            scale,
+        }
+    }
+
+    pub fn from_position(position: Vec3) -> Self {
+        Self::new(position, Vec2::zero(), Vec2::one())
+    }
+
+    pub fn position(&self) -> Vec3 {
+        self.position
+    }
+
+    pub fn rotation(&self) -> Vec2 {
+        self.rotation
+    }
+
+    pub fn scale(&self) -> Vec2 {
+        self.scale
+    }
+
+    pub fn set_position(&mut self, position: Vec3) {
+        self.position = position;
+    }
+
+    pub fn set_rotation(&mut self, rotation: Vec2) {
+        self.rotation = rotation;
+    }
+
+    pub fn set_scale(&mut self, scale: Vec2) {
+        self.scale = scale;
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::from_position(Vec3::zero())
+    }
+}
@@ -0,0 +1,287 @@
+extern crate math;
+
+use std::f32::consts::PI;
+
+use math::Vec2;
+use math::Vec4;
+
+use crate::window::WindowKey;
+
+const CIRCLE_SEGMENTS: u32 = 32;
+// the length of the lines of an arrow's head, at most half of the arrow
+const ARROW_HEAD: f32 = 8.0;
+const ARROW_ANGLE: f32 = PI / 6.0;
+
+// world positions are in the space of sprites, screen positions are pixels from the bottom left
+// corner of the window
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum DebugSpace {
+    World,
+    Screen,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Primitive {
+    Lines(Vec<(Vec2, Vec2)>),
+    // the bottom left corner of the text
+    Label(Vec2, String),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DebugShape {
+    primitive: Primitive,
+    color: Vec4,
+    space: DebugSpace,
+    thickness: f32,
+    // in seconds of real time, shapes without a duration are drawn for one frame
+    remaining: f32,
+}
+
+impl DebugShape {
+    fn new(primitive: Primitive, color: Vec4) -> Self {
+        Self {
+            primitive,
+            color,
+            space: DebugSpace::World,
+            thickness: 1.0,
+            remaining: 0.0,
+        }
+    }
+
+    pub fn in_screen_space(&mut self) -> &mut Self {
+        self.space = DebugSpace::Screen;
+        self
+    }
+
+    // keeps the shape for the given seconds of real time instead of a single frame
+    pub fn for_seconds(&mut self, seconds: f32) -> &mut Self {
+        self.remaining = seconds.max(0.0);
+        self
+    }
+
+    // of lines in pixels, labels ignore it
+    pub fn with_thickness(&mut self, thickness: f32) -> &mut Self {
+        self.thickness = thickness.max(0.0);
+        self
+    }
+
+    pub fn color(&self) -> Vec4 {
+        self.color
+    }
+
+    pub fn space(&self) -> DebugSpace {
+        self.space
+    }
+
+    pub fn thickness(&self) -> f32 {
+        self.thickness
+    }
+
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    // the start and end of every line, empty for labels
+    pub fn segments(&self) -> &[(Vec2, Vec2)] {
+        match &self.primitive {
+            Primitive::Lines(segments) => segments,
+            Primitive::Label(..) => &[],
+        }
+    }
+
+    pub fn label(&self) -> Option<(Vec2, &str)> {
+        match &self.primitive {
+            Primitive::Label(position, text) => Some((*position, text)),
+            Primitive::Lines(_) => None,
+        }
+    }
+}
+
+// shapes drawn on top of the scene by any system during a frame, enabled in debug builds only
+// unless enabled explicitly, calls do nothing while disabled
+pub struct DebugDraw {
+    shapes: Vec<DebugShape>,
+    enabled: bool,
+    // labels use the font with this name and are skipped when it was not added
+    font: String,
+    text_size: f32,
+    // the shapes are drawn over the scenes of this window only
+    window: WindowKey,
+    // takes the shapes drawn while disabled
+    discarded: DebugShape,
+}
+
+impl DebugDraw {
+    pub(crate) fn new() -> Self {
+        Self {
+            shapes: Vec::new(),
+            enabled: cfg!(debug_assertions),
+            font: "debug".to_owned(),
+            text_size: 16.0,
+            window: WindowKey::main(),
+            discarded: DebugShape::new(Primitive::Lines(Vec::new()), Vec4::zero()),
+        }
+    }
+
+    pub fn line(&mut self, from: Vec2, to: Vec2, color: Vec4) -> &mut DebugShape {
+        self.push(Primitive::Lines(vec![(from, to)]), color)
+    }
+
+    pub fn arrow(&mut self, from: Vec2, to: Vec2, color: Vec4) -> &mut DebugShape {
+        let (x, y) = (from.x - to.x, from.y - to.y);
+        let length = (x * x + y * y).sqrt();
+        if length <= 0.0 {
+            return self.line(from, to, color);
+        }
+
+        let head = ARROW_HEAD.min(length / 2.0) / length;
+        let mut segments = vec![(from, to)];
+        for angle in [ARROW_ANGLE, -ARROW_ANGLE].iter() {
+            let (sin, cos) = angle.sin_cos();
+            let end = Vec2::new(
+                to.x + (x * cos - y * sin) * head,
+                to.y + (x * sin + y * cos) * head,
+            );
+            segments.push((to, end));
+        }
+
+        self.push(Primitive::Lines(segments), color)
+    }
+
+    pub fn rect(&mut self, center: Vec2, size: Vec2, color: Vec4) -> &mut DebugShape {
+        let left = center.x - size.x / 2.0;
+        let right = center.x + size.x / 2.0;
+        let bottom = center.y - size.y / 2.0;
+        let top = center.y + size.y / 2.0;
+
+        self.polygon(
+            &[
+                Vec2::new(left, bottom),
+                Vec2::new(right, bottom),
+                Vec2::new(right, top),
+                Vec2::new(left, top),
+            ],
+            color,
+        )
+    }
+
+    pub fn circle(&mut self, center: Vec2, radius: f32, color: Vec4) -> &mut DebugShape {
+        let points: Vec<Vec2> = (0..CIRCLE_SEGMENTS)
+            .map(|index| {
+                let (sin, cos) = (index as f32 * 2.0 * PI / CIRCLE_SEGMENTS as f32).sin_cos();
+                Vec2::new(center.x + cos * radius, center.y + sin * radius)
+            })
+            .collect();
+
+        self.polygon(&points, color)
+    }
+
+    // the last point is connected to the first one
+    pub fn polygon(&mut self, points: &[Vec2], color: Vec4) -> &mut DebugShape {
+        let segments = match points.len() {
+            0 | 1 => Vec::new(),
+            2 => vec![(points[0], points[1])],
+            count => (0..count)
+                .map(|index| (points[index], points[(index + 1) % count]))
+                .collect(),
+        };
+
+        self.push(Primitive::Lines(segments), color)
+    }
+
+    // the origin is the bottom left corner of the grid
+    pub fn grid(
+        &mut self,
+        origin: Vec2,
+        cell_size: Vec2,
+        columns: u32,
+        rows: u32,
+        color: Vec4,
+    ) -> &mut DebugShape {
+        let width = cell_size.x * columns as f32;
+        let height = cell_size.y * rows as f32;
+
+        let vertical = (0..=columns).map(|column| {
+            let x = origin.x + cell_size.x * column as f32;
+            (Vec2::new(x, origin.y), Vec2::new(x, origin.y + height))
+        });
+        let horizontal = (0..=rows).map(|row| {
+            let y = origin.y + cell_size.y * row as f32;
+            (Vec2::new(origin.x, y), Vec2::new(origin.x + width, y))
+        });
+
+        self.push(
+            Primitive::Lines(vertical.chain(horizontal).collect()),
+            color,
+        )
+    }
+
+    // the position is the bottom left corner of the text
+    pub fn text(&mut self, position: Vec2, text: &str, color: Vec4) -> &mut DebugShape {
+        self.push(Primitive::Label(position, text.to_owned()), color)
+    }
+
+    // removes the shapes kept for a duration as well
+    pub fn clear(&mut self) {
+        self.shapes.clear();
+    }
+
+    pub fn shapes(&self) -> &[DebugShape] {
+        &self.shapes
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn font(&self) -> &str {
+        &self.font
+    }
+
+    pub fn text_size(&self) -> f32 {
+        self.text_size
+    }
+
+    pub fn window(&self) -> WindowKey {
+        self.window
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.shapes.clear();
+        }
+    }
+
+    pub fn set_font(&mut self, font: &str) {
+        self.font = font.to_owned();
+    }
+
+    pub fn set_text_size(&mut self, text_size: f32) {
+        self.text_size = text_size;
+    }
+
+    pub fn set_window(&mut self, window: WindowKey) {
+        self.window = window;
+    }
+
+    fn push(&mut self, primitive: Primitive, color: Vec4) -> &mut DebugShape {
+        let shape = DebugShape::new(primitive, color);
+        if !self.enabled {
+            self.discarded = shape;
+            return &mut self.discarded;
+        }
+
+        self.shapes.push(shape);
+        let last = self.shapes.len() - 1;
+        &mut self.shapes[last]
+    }
+}
+
+// called at the end of every frame, after the shapes got drawn
+pub(crate) fn update(debug_draw: &mut DebugDraw, delta_time: f32) {
+    for shape in debug_draw.shapes.iter_mut() {
+        shape.remaining -= delta_time;
+    }
+    debug_draw.shapes.retain(|shape| shape.remaining > 0.0);
+}
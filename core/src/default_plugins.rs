@@ -0,0 +1,126 @@
+extern crate context;
+
+use context::input::Input;
+
+use crate::animation;
+use crate::animation::AnimationClips;
+use crate::animation::AnimationFinished;
+use crate::engine::Engine;
+use crate::plugin::Plugin;
+use crate::scene_management::SceneKey;
+use crate::screenshot::CameraCaptured;
+use crate::screenshot::ScreenshotTaken;
+use crate::stage::Stage;
+use crate::system;
+use crate::system_descriptor::SystemDescriptor;
+use crate::tick::Tick;
+use crate::timers;
+use crate::timers::TimerFinished;
+use crate::timers::Timers;
+
+pub struct InputPlugin;
+
+impl<K: SceneKey> Plugin<K> for InputPlugin {
+    fn name(&self) -> &str {
+        "input"
+    }
+
+    fn build(&self, engine: &mut Engine<K>) {
+        engine.add_resource(Input::new());
+    }
+}
+
+pub struct TimePlugin;
+
+impl<K: SceneKey> Plugin<K> for TimePlugin {
+    fn name(&self) -> &str {
+        "time"
+    }
+
+    fn build(&self, engine: &mut Engine<K>) {
+        engine.add_resource(Tick::new());
+        engine.add_resource(Timers::new());
+        engine.register_channel::<TimerFinished>(2);
+        engine.add_system(
+            SystemDescriptor::new("timers", timers::create_system())
+                .in_stage(Stage::PreUpdate)
+                .for_each_scene(),
+        );
+    }
+}
+
+// plays sprite animations with the scaled time of the time plugin
+pub struct AnimationPlugin;
+
+impl<K: SceneKey> Plugin<K> for AnimationPlugin {
+    fn name(&self) -> &str {
+        "animation"
+    }
+
+    fn dependencies(&self) -> Vec<&str> {
+        vec!["time"]
+    }
+
+    fn build(&self, engine: &mut Engine<K>) {
+        // a replaced time plugin may leave out the tick, the engine advances it either way
+        if engine.resources().get::<Tick>().is_none() {
+            engine.add_resource(Tick::new());
+        }
+        engine.add_resource(AnimationClips::new());
+        engine.register_channel::<AnimationFinished>(2);
+        engine.add_system(
+            SystemDescriptor::new("animation", animation::create_system())
+                .in_stage(Stage::Update)
+                .for_each_scene(),
+        );
+    }
+}
+
+pub struct RenderPlugin;
+
+impl<K: SceneKey> Plugin<K> for RenderPlugin {
+    fn name(&self) -> &str {
+        "render"
+    }
+
+    fn build(&self, engine: &mut Engine<K>) {
+        engine.register_channel::<ScreenshotTaken>(2);
+        engine.register_channel::<CameraCaptured>(2);
+        engine.add_system(
+            SystemDescriptor::new("render", system::renderer::create()).in_stage(Stage::Render),
+        );
+    }
+}
+
+// keeps the name and the events of the render plugin so that plugins depending on it can be
+// installed, requested screenshots stay pending as nothing is rendered
+pub struct NullRenderPlugin;
+
+impl<K: SceneKey> Plugin<K> for NullRenderPlugin {
+    fn name(&self) -> &str {
+        "render"
+    }
+
+    fn build(&self, engine: &mut Engine<K>) {
+        engine.register_channel::<ScreenshotTaken>(2);
+        engine.register_channel::<CameraCaptured>(2);
+    }
+}
+
+pub fn default_plugins<K: SceneKey>() -> Vec<Box<dyn Plugin<K>>> {
+    vec![
+        Box::new(InputPlugin),
+        Box::new(TimePlugin),
+        Box::new(AnimationPlugin),
+        Box::new(RenderPlugin),
+    ]
+}
+
+pub(crate) fn headless_plugins<K: SceneKey>() -> Vec<Box<dyn Plugin<K>>> {
+    vec![
+        Box::new(InputPlugin),
+        Box::new(TimePlugin),
+        Box::new(AnimationPlugin),
+        Box::new(NullRenderPlugin),
+    ]
+}
@@ -2,20 +2,35 @@ extern crate context;
 extern crate graphic;
 extern crate legion;
 extern crate math;
+extern crate serde;
 
+use std::any::Any;
+use std::any::TypeId;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::mem;
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+use std::time::Instant;
 
+use legion::entity::Entity;
 use legion::filter::ChunksetFilterData;
 use legion::filter::Filter;
+use legion::storage::Component;
+use legion::storage::Tag;
 use legion::systems::resource::Resource;
 use legion::systems::resource::Resources;
-use legion::systems::schedule::Schedule;
 use legion::world::IntoComponentSource;
 use legion::world::TagLayout;
 use legion::world::TagSet;
 use legion::world::Universe;
+use legion::world::World;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 use context::canvas::Canvas;
+use context::canvas::WindowConfig;
+use context::event::EventSender;
 use context::input;
 use context::input::Input;
 use context::keyboard::Action;
@@ -24,74 +39,278 @@ use context::keyboard::Key;
 use context::keyboard::Modifier;
 use context::Event;
 
+use graphic::api::Rect;
 use graphic::texture::Texture;
 
+use crate::animation::AnimationClips;
+use crate::commands;
+use crate::commands::Commands;
+use crate::component::AnimationClip;
+use crate::component::Camera;
+use crate::component::MapObject;
+use crate::component::Property;
+use crate::component::Sprite;
+use crate::component::Text;
+use crate::component::Tilemap;
+use crate::component::Transform;
+use crate::debug_draw;
+use crate::debug_draw::DebugDraw;
+use crate::default_plugins;
+use crate::default_plugins::default_plugins;
+use crate::engine_builder::EngineBuilder;
+use crate::error::EngineError;
+use crate::events;
+use crate::events::Events;
+use crate::font::Font;
+use crate::font::FontError;
+use crate::font_management::FontManagement;
+use crate::plugin::Plugin;
+use crate::post_processing::PostProcessing;
+use crate::prefab;
+use crate::prefab::Prefab;
+use crate::prefab::Prefabs;
 use crate::scene::Scene;
+use crate::scene::SceneHooks;
+use crate::scene_management;
+use crate::scene_management::SceneError;
+use crate::scene_management::SceneEvent;
+use crate::scene_management::SceneKey;
 use crate::scene_management::SceneManagement;
+use crate::screen;
+use crate::screen::Screen;
+use crate::screenshot;
+use crate::screenshot::ScreenshotTaken;
+use crate::screenshot::Screenshots;
+use crate::serialization;
+use crate::serialization::ComponentRegistry;
+use crate::serialization::SerializationError;
 use crate::sprite_management::SpriteManagement;
-use crate::sprite_management::Sprite;
-use crate::system;
+use crate::sprite_sheet::SpriteSheet;
+use crate::sprite_sheet::SpriteSheetError;
+use crate::stage::Stage;
+use crate::system_descriptor::Stages;
+use crate::system_descriptor::SystemDescriptor;
+use crate::text_layout::TextLayout;
 use crate::tick;
 use crate::tick::Tick;
+use crate::tiled::TiledError;
+use crate::tiled::TiledMap;
+use crate::timers;
+use crate::user_events;
+use crate::user_events::UserEventType;
+use crate::user_events::UserEvents;
+use crate::window::WindowKey;
 
-pub struct Engine {
+const DEFAULT_DELTA_TIME: f32 = 1.0 / 60.0;
+
+// a headless engine has no canvas and is driven frame by frame
+struct Headless {
+    time: Instant,
+    keys: Vec<(Key, Button)>,
+}
+
+pub struct Engine<K: SceneKey> {
     universe: Universe,
-    scenes: HashMap<i16, Scene>,
-    render_system: Schedule,
-    systems: Vec<Schedule>,
+    scenes: HashMap<K, Scene>,
+    systems: Stages,
     resources: Resources,
+    windows: Vec<WindowConfig>,
+    user_event_types: Vec<UserEventType>,
+    // pushed through the event sender resource until the canvas loop runs
+    events: Receiver<Box<dyn Any + Send>>,
+    channels: Vec<(TypeId, fn(&mut Resources))>,
+    plugins: Vec<String>,
+    inputs: Vec<Input>,
+    headless: Option<Headless>,
+    started: bool,
+    // ids of the scenes, they stay unique when a scene is created again
+    created_scenes: usize,
 }
 
-impl Engine {
-    pub fn new() -> Self {
+impl<K: SceneKey> Engine<K> {
+    // installs the default plugins
+    pub fn new() -> Result<Self, EngineError> {
+        let mut engine = Self::empty();
+
+        for plugin in default_plugins() {
+            engine.install(plugin.as_ref())?;
+        }
+
+        Ok(engine)
+    }
+
+    // runs without window and graphics context, rendering is replaced by the null renderer
+    pub fn headless() -> Result<Self, EngineError> {
+        let mut engine = Self::empty();
+        engine.set_headless();
+
+        for plugin in default_plugins::headless_plugins() {
+            engine.install(plugin.as_ref())?;
+        }
+
+        Ok(engine)
+    }
+
+    pub(crate) fn set_headless(&mut self) {
+        self.headless = Some(Headless {
+            time: Instant::now(),
+            keys: Vec::new(),
+        });
+    }
+
+    pub(crate) fn empty() -> Self {
         let mut resources = Resources::default();
-        resources.insert(SceneManagement::new());
+        resources.insert(SceneManagement::<K>::new());
         resources.insert(SpriteManagement::new());
-        resources.insert(Input::new());
-        resources.insert(Tick::new());
+        resources.insert(FontManagement::new());
+        resources.insert(DebugDraw::new());
+        resources.insert(Screenshots::new());
+        resources.insert(PostProcessing::new());
+        resources.insert(UserEvents::new());
+        let (sender, events) = EventSender::channel();
+        resources.insert(sender);
+        resources.insert(Commands::<K>::new());
+        resources.insert(Prefabs::new());
+
+        let window = WindowConfig::default();
+        resources.insert(Screen::new(window.width(), window.height()));
+
+        let mut registry = ComponentRegistry::new();
+        registry.register_component::<Transform>("transform");
+        registry.register_component::<Sprite>("sprite");
+        registry.register_component::<Tilemap>("tilemap");
+        registry.register_component::<MapObject>("map_object");
+        registry.register_component::<Text>("text");
+        registry.register_component::<Camera>("camera");
+        resources.insert(registry);
 
         Engine {
             universe: Universe::new(),
             scenes: HashMap::new(),
-            render_system: system::renderer::create(),
-            systems: Vec::new(),
+            systems: Stages::new(),
             resources,
+            windows: Vec::new(),
+            user_event_types: Vec::new(),
+            events,
+            channels: Vec::new(),
+            plugins: Vec::new(),
+            inputs: Vec::new(),
+            headless: None,
+            started: false,
+            created_scenes: 0,
         }
     }
 
-    pub fn create_scene(&mut self, key: u8) {
+    pub fn builder() -> EngineBuilder<K> {
+        EngineBuilder::new()
+    }
+
+    // the first created window is the main window
+    pub fn create_window(&mut self, config: WindowConfig) -> WindowKey {
+        self.windows.push(config);
+
+        WindowKey::new(self.windows.len() - 1)
+    }
+
+    pub fn set_scene_window(&mut self, scene: K, window: WindowKey) {
+        if let Some(scene) = self.scenes.get_mut(&scene) {
+            scene.set_window(window);
+        }
+    }
+
+    // the first created scene becomes the current one, an existing scene with the key is kept
+    pub fn create_scene(&mut self, key: K) {
+        if self.scenes.contains_key(&key) {
+            return;
+        }
+
         let world = self.universe.create_world();
-        let scene = Scene::new(world);
+        let scene = Scene::new(self.created_scenes, world);
+        self.created_scenes += 1;
 
-        self.scenes.insert(key as i16, scene);
+        self.scenes.insert(key.clone(), scene);
 
-        if let Some(ref mut scene_management) = self.resources.get_mut::<SceneManagement>() {
-            if scene_management.current() == -1 {
-                scene_management.change(0);
-            }
+        if let Some(ref mut scene_management) = self.resources.get_mut::<SceneManagement<K>>() {
+            scene_management.register(key);
         }
     }
 
-    pub fn add_system(&mut self, system: Schedule) {
-        self.systems.push(system);
+    pub(crate) fn change_scene(&mut self, key: K) -> Result<(), SceneError> {
+        match self.resources.get_mut::<SceneManagement<K>>() {
+            Some(ref mut scene_management) => scene_management.set_initial(key),
+            None => Err(SceneError::UnknownScene),
+        }
+    }
+
+    pub fn set_scene_hooks<H>(&mut self, scene: K, hooks: H)
+    where
+        H: SceneHooks + 'static,
+    {
+        if let Some(scene) = self.scenes.get_mut(&scene) {
+            scene.set_hooks(Box::new(hooks));
+        }
+    }
+
+    // a plain schedule runs in the update stage
+    pub fn add_system<S>(&mut self, system: S)
+    where
+        S: Into<SystemDescriptor>,
+    {
+        self.systems.add(system.into());
     }
 
-    pub fn add_scene_system(&mut self, scene: u8, system: Schedule) {
-        if let Some(scene) = self.scenes.get_mut(&(scene as i16)) {
-            scene.add_system(system);
+    pub fn add_scene_system<S>(&mut self, scene: K, system: S)
+    where
+        S: Into<SystemDescriptor>,
+    {
+        if let Some(scene) = self.scenes.get_mut(&scene) {
+            scene.add_system(system.into());
         }
     }
 
-    pub fn add_entities<T, C>(&mut self, scene: u8, tags: T, components: C)
+    pub fn add_entities<T, C>(&mut self, scene: K, tags: T, components: C)
     where
         T: TagSet + TagLayout + for<'a> Filter<ChunksetFilterData<'a>>,
         C: IntoComponentSource,
     {
-        if let Some(scene) = self.scenes.get_mut(&(scene as i16)) {
+        if let Some(scene) = self.scenes.get_mut(&scene) {
             scene.world_mut().insert(tags, components);
         }
     }
 
+    pub fn add_plugin<P>(&mut self, plugin: P) -> Result<(), EngineError>
+    where
+        P: Plugin<K>,
+    {
+        self.install(&plugin)
+    }
+
+    pub fn has_plugin(&self, name: &str) -> bool {
+        self.plugins.iter().any(|plugin| plugin == name)
+    }
+
+    pub(crate) fn install(&mut self, plugin: &dyn Plugin<K>) -> Result<(), EngineError> {
+        if self.has_plugin(plugin.name()) {
+            return Err(EngineError::DuplicatePlugin(plugin.name().to_owned()));
+        }
+
+        if let Some(dependency) = plugin
+            .dependencies()
+            .into_iter()
+            .find(|dependency| !self.has_plugin(dependency))
+        {
+            return Err(EngineError::MissingPluginDependency(
+                plugin.name().to_owned(),
+                dependency.to_owned(),
+            ));
+        }
+
+        self.plugins.push(plugin.name().to_owned());
+        plugin.build(self);
+
+        Ok(())
+    }
+
     pub fn add_resource<R>(&mut self, resource: R)
     where
         R: Resource,
@@ -99,37 +318,337 @@ impl Engine {
         self.resources.insert(resource);
     }
 
+    // registered events can be pushed from any thread with the EventSender resource
+    pub fn register_event<T>(&mut self)
+    where
+        T: Any + Send + Sync,
+    {
+        let user_event_type = UserEventType::new::<T>();
+        if let Some(sender) = self.resources.get::<EventSender>() {
+            sender.register::<T>();
+        }
+
+        if self
+            .user_event_types
+            .iter()
+            .all(|registered| registered.type_id() != user_event_type.type_id())
+        {
+            self.user_event_types.push(user_event_type);
+        }
+    }
+
+    // registered components and tags are written to and read from scene files by their name
+    pub fn register_component<T>(&mut self, name: &str)
+    where
+        T: Component + Serialize + DeserializeOwned,
+    {
+        if let Some(ref mut registry) = self.resources.get_mut::<ComponentRegistry>() {
+            registry.register_component::<T>(name);
+        }
+    }
+
+    pub fn register_tag<T>(&mut self, name: &str)
+    where
+        T: Tag + Serialize + DeserializeOwned,
+    {
+        if let Some(ref mut registry) = self.resources.get_mut::<ComponentRegistry>() {
+            registry.register_tag::<T>(name);
+        }
+    }
+
+    pub fn save_scene(&self, scene: K, path: &str) -> Result<(), SerializationError> {
+        let scene = self
+            .scenes
+            .get(&scene)
+            .ok_or(SerializationError::SceneNotFound)?;
+        let registry = self
+            .resources
+            .get::<ComponentRegistry>()
+            .ok_or(SerializationError::RegistryNotFound)?;
+
+        serialization::save_world(&registry, scene.world(), path)
+    }
+
+    // creates the scene if it does not exist yet, otherwise the entities are added to it
+    pub fn load_scene(&mut self, scene: K, path: &str) -> Result<(), SerializationError> {
+        if !self.scenes.contains_key(&scene) {
+            self.create_scene(scene.clone());
+        }
+
+        let scene = self
+            .scenes
+            .get_mut(&scene)
+            .ok_or(SerializationError::SceneNotFound)?;
+        let registry = self
+            .resources
+            .get::<ComponentRegistry>()
+            .ok_or(SerializationError::RegistryNotFound)?;
+
+        serialization::load_world(&registry, scene.world_mut(), path).map(|_| ())
+    }
+
+    pub fn add_prefab(&mut self, name: &str, prefab: Prefab) {
+        if let Some(ref mut prefabs) = self.resources.get_mut::<Prefabs>() {
+            prefabs.insert(name, prefab);
+        }
+    }
+
+    pub fn load_prefab(&mut self, name: &str, path: &str) -> Result<(), SerializationError> {
+        let prefab = Prefab::from_file(path)?;
+        self.add_prefab(name, prefab);
+
+        Ok(())
+    }
+
+    // the overrides are merged into the prefab, e.g. to place every instance at its own position
+    pub fn instantiate(
+        &mut self,
+        scene: K,
+        name: &str,
+        overrides: Prefab,
+    ) -> Result<Entity, SerializationError> {
+        let scene = self
+            .scenes
+            .get_mut(&scene)
+            .ok_or(SerializationError::SceneNotFound)?;
+        let prefabs = self
+            .resources
+            .get::<Prefabs>()
+            .ok_or(SerializationError::RegistryNotFound)?;
+        let prefab = prefabs
+            .get(name)
+            .ok_or_else(|| SerializationError::PrefabNotFound(name.to_owned()))?
+            .merged(overrides)?;
+        let registry = self
+            .resources
+            .get::<ComponentRegistry>()
+            .ok_or(SerializationError::RegistryNotFound)?;
+
+        prefab::instantiate(&registry, scene.world_mut(), &prefab)
+    }
+
+    // systems send with write_resource::<Events<T>>() and read with read_resource::<Events<T>>(),
+    // events are dropped after lifetime frames
+    pub fn register_channel<T>(&mut self, lifetime: u32)
+    where
+        T: Send + Sync + 'static,
+    {
+        let type_id = TypeId::of::<T>();
+
+        if self.channels.iter().any(|(channel, _)| *channel == type_id) {
+            if let Some(ref mut events) = self.resources.get_mut::<Events<T>>() {
+                events.set_lifetime(lifetime);
+            }
+            return;
+        }
+
+        self.resources.insert(Events::<T>::new(lifetime));
+        self.channels.push((type_id, update_channel::<T>));
+    }
+
+    // sprites are packed into atlas textures when the engine starts running
     pub fn add_sprite(&mut self, name: &str, path: &str) {
         if let Some(ref mut sprite_management) = self.resources.get_mut::<SpriteManagement>() {
             sprite_management.add(name, path);
         }
     }
 
-    // TODO: return Error
-    pub fn run(mut self) {
-        let (mut canvas, canvas_loop) = Canvas::new("pandix engine", 400, 400).unwrap();
+    // frames become sprites named "<name>/<frame>", tags become sprites showing their first frame
+    // and animation clips, both named "<name>/<tag>"
+    pub fn add_sprite_sheet(&mut self, name: &str, sheet: &SpriteSheet) {
+        if let Some(ref mut sprite_management) = self.resources.get_mut::<SpriteManagement>() {
+            sprite_management.add(name, sheet.image());
+
+            for frame in sheet.frames() {
+                let frame_name = format!("{}/{}", name, frame.name());
+                sprite_management.add_frame(&frame_name, name, frame.region(), frame.pivot());
+            }
+
+            for tag in sheet.tags() {
+                if let Some(frame) = tag.frames().first().map(|index| &sheet.frames()[*index]) {
+                    let tag_name = format!("{}/{}", name, tag.name());
+                    sprite_management.add_frame(&tag_name, name, frame.region(), frame.pivot());
+                }
+            }
+        }
+
+        for tag in sheet.tags() {
+            if let Some(clip) = sheet.clip(tag.name()) {
+                self.add_animation(&format!("{}/{}", name, tag.name()), clip);
+            }
+        }
+    }
+
+    pub fn load_sprite_sheet(&mut self, name: &str, path: &str) -> Result<(), SpriteSheetError> {
+        let sheet = SpriteSheet::from_file(path)?;
+        self.add_sprite_sheet(name, &sheet);
+
+        Ok(())
+    }
+
+    // the pages of bitmap fonts become sprites named after their image
+    pub fn add_font(&mut self, name: &str, font: Font) {
+        if let Some(bitmap) = font.bitmap() {
+            for page in bitmap.pages() {
+                self.add_sprite(page, page);
+            }
+        }
+
+        if let Some(ref mut font_management) = self.resources.get_mut::<FontManagement>() {
+            font_management.add(name, font);
+        }
+    }
+
+    // .fnt files are loaded as bitmap fonts, anything else as true type or open type font
+    pub fn load_font(&mut self, name: &str, path: &str) -> Result<(), FontError> {
+        let font = Font::from_file(path)?;
+        self.add_font(name, font);
+
+        Ok(())
+    }
+
+    // none when the font of the text was not added
+    pub fn layout_text(&self, text: &Text) -> Option<TextLayout> {
+        let font_management = self.resources.get::<FontManagement>()?;
+        let font = font_management.get(text.font())?;
+
+        Some(TextLayout::new(font, text))
+    }
+
+    // tilesets become sprites named after their image, tile layers tilemap entities and objects
+    // entities with a map object, objects whose class names a prefab are instantiated from it
+    pub fn add_tiled_map(&mut self, scene: K, map: &TiledMap) -> Result<(), TiledError> {
+        let tilemaps = map.tilemaps()?;
+
+        for tileset in map.tilesets() {
+            self.add_sprite(tileset.image(), tileset.image());
+        }
+
+        if !self.scenes.contains_key(&scene) {
+            self.create_scene(scene.clone());
+        }
+
+        let scene = self
+            .scenes
+            .get_mut(&scene)
+            .ok_or(SerializationError::SceneNotFound)?;
+        let registry = self
+            .resources
+            .get::<ComponentRegistry>()
+            .ok_or(SerializationError::RegistryNotFound)?;
+        let prefabs = self
+            .resources
+            .get::<Prefabs>()
+            .ok_or(SerializationError::RegistryNotFound)?;
+
+        for tilemap in tilemaps {
+            let prefab = Prefab::new()
+                .with_component("transform", &Transform::default())?
+                .with_component("tilemap", &tilemap)?;
+            prefab::instantiate(&registry, scene.world_mut(), &prefab)?;
+        }
+
+        for object in map.objects() {
+            let mut overrides = Prefab::new()
+                .with_component("transform", &map.object_transform(object))?
+                .with_component("map_object", &object.map_object())?;
+            if let Some(sprite) = map.object_sprite(object) {
+                overrides = overrides.with_component("sprite", &sprite)?;
+            }
+            for (component, field, property) in object.field_overrides() {
+                overrides = with_property(overrides, component, field, property)?;
+            }
+
+            let prefab = match prefabs.get(object.class()) {
+                Some(prefab) => prefab.merged(overrides)?,
+                None => Prefab::new().merged(overrides)?,
+            };
+            prefab::instantiate(&registry, scene.world_mut(), &prefab)?;
+        }
+
+        Ok(())
+    }
+
+    // creates the scene if it does not exist yet, otherwise the map is added to it
+    pub fn load_tiled_map(&mut self, scene: K, path: &str) -> Result<(), TiledError> {
+        let map = TiledMap::from_file(path)?;
+        self.add_tiled_map(scene, &map)
+    }
+
+    // does nothing when the animation plugin is not installed
+    pub fn add_animation(&mut self, name: &str, clip: AnimationClip) {
+        if let Some(ref mut clips) = self.resources.get_mut::<AnimationClips>() {
+            clips.insert(name, clip);
+        }
+    }
+
+    pub fn run(mut self) -> Result<(), EngineError> {
+        if self.headless.is_some() {
+            return Err(EngineError::IsHeadless);
+        }
+
+        if self.scenes.is_empty() {
+            return Err(EngineError::NoScene);
+        }
+
+        if self.windows.is_empty() {
+            self.create_window(WindowConfig::default());
+        }
+
+        let (mut canvas, canvas_loop) = Canvas::new(&self.windows[WindowKey::main().index()])?;
+
+        let mut window_ids = vec![canvas.window().id()];
+        for config in self.windows.iter().skip(1) {
+            window_ids.push(canvas.add_window(config)?);
+        }
+
+        for user_event_type in &self.user_event_types {
+            user_event_type.register(&canvas_loop)?;
+        }
+        if let Some(sender) = self.resources.get::<EventSender>() {
+            canvas_loop.connect(&sender)?;
+        }
 
         graphic::api::load_graphic_functions_from_context(|proc_address| {
             canvas.get_context_proc_address(proc_address)
-        });
+        })?;
 
         if let Some(ref mut sprite_manager) = self.resources.get_mut::<SpriteManagement>() {
-            for (_, value) in sprite_manager.sprites_mut() {
-                match value {
-                    Sprite::Image(texture) => {
+            sprite_manager.upload();
+        }
 
-                    },
-                    _ => {
-                        // TODO: use purple 1x1 pixel
+        self.start()?;
+
+        // every window owns its input state, the main window's one lives in the resources
+        self.inputs = window_ids.iter().map(|_| Input::new()).collect();
+
+        canvas_loop.run(&mut canvas, move |canvas, events| {
+            self.begin_frame();
+
+            let now = Instant::now();
+            for event in events {
+                if event.is_user_event() {
+                    let user_event = self
+                        .user_event_types
+                        .iter()
+                        .find_map(|user_event_type| user_event_type.convert(&event));
+
+                    if let (Some(user_event), Some(ref mut user_events)) =
+                        (user_event, self.resources.get_mut::<UserEvents>())
+                    {
+                        user_events::push(user_events, user_event);
                     }
+
+                    continue;
                 }
-            }
-        }
 
-        canvas_loop.run(&canvas, move |events| {
-            let resources = &mut self.resources;
+                let window = event
+                    .get_window_id()
+                    .and_then(|id| window_ids.iter().position(|window_id| *window_id == id))
+                    .unwrap_or_else(|| WindowKey::main().index());
+                // hold durations and taps are measured between the events, not the frames
+                let time = canvas.event_time(event.get_timestamp());
 
-            for event in events {
                 match event {
                     Event::KeyDown {
                         keycode,
@@ -137,67 +656,504 @@ impl Engine {
                         repeat,
                         ..
                     } => {
-                        if let Some(ref mut input) = resources.get_mut::<Input>() {
-                            let key: Key = if keycode.is_some() {
-                                keycode.unwrap().into()
-                            } else {
-                                Key::Unknown
-                            };
-                            let action = if repeat {
-                                Action::Repeat
-                            } else {
-                                Action::Press
-                            };
-                            let modifier: Modifier = keymod.into();
-                            let button = Button::new(key, action, modifier);
-
-                            input::update(input, key, button);
-                        }
+                        let key: Key = if keycode.is_some() {
+                            keycode.unwrap().into()
+                        } else {
+                            Key::Unknown
+                        };
+                        let action = if repeat {
+                            Action::Repeat
+                        } else {
+                            Action::Press
+                        };
+                        let modifier: Modifier = keymod.into();
+                        let button = Button::new(key, action, modifier);
+
+                        self.update_input(window, key, button, time);
                     }
                     context::Event::KeyUp {
                         keycode, keymod, ..
                     } => {
-                        if let Some(ref mut input) = resources.get_mut::<Input>() {
-                            let key: Key = if keycode.is_some() {
-                                keycode.unwrap().into()
-                            } else {
-                                Key::Unknown
-                            };
-                            let action = Action::Release;
-                            let modifier: Modifier = keymod.into();
-                            let button = Button::new(key, action, modifier);
-
-                            input::update(input, key, button);
-                        }
+                        let key: Key = if keycode.is_some() {
+                            keycode.unwrap().into()
+                        } else {
+                            Key::Unknown
+                        };
+                        let action = Action::Release;
+                        let modifier: Modifier = keymod.into();
+                        let button = Button::new(key, action, modifier);
+
+                        self.update_input(window, key, button, time);
                     }
                     _ => (),
                 }
             }
 
-            if let Some(tick) = &mut resources.get_mut::<Tick>() {
-                tick::update(tick);
+            let active_scenes = self.update(now, None)?;
+
+            for (index, window_id) in window_ids.iter().enumerate() {
+                // the scenes of closed windows are only updated and rendered on the scene stack
+                if canvas.window_by_id(*window_id).is_none() {
+                    self.detach_scenes(WindowKey::new(index));
+                    continue;
+                }
+                if canvas.make_current(*window_id).is_err() {
+                    continue;
+                }
+
+                // a scissor or the write masks left by the last window would limit the clear
+                graphic::api::reset_masks();
+                let [red, green, blue, alpha] = screen::CLEAR_COLOR;
+                graphic::api::clear_color(red, green, blue, alpha);
+
+                if let Some((width, height)) = canvas.drawable_size(*window_id) {
+                    graphic::api::viewport(0, 0, width as i32, height as i32);
+                    if let Some(ref mut screen) = self.resources.get_mut::<Screen>() {
+                        screen::resize(screen, width, height);
+                    }
+                }
+
+                self.render_window(&active_scenes, index);
+
+                self.take_screenshot(WindowKey::new(index), canvas.drawable_size(*window_id));
             }
 
-            graphic::api::clear_color(0.0, 0.0, 1.0, 1.0);
+            self.end_frame();
 
-            let current_scene = resources.get::<SceneManagement>().unwrap().current();
-            // TODO: remove unwrap
-            let scene = self.scenes.get_mut(&current_scene).unwrap();
+            Ok(())
+        })
+    }
 
-            // execute global systems
-            self.systems
-                .iter_mut()
-                .for_each(|schedule| schedule.execute(scene.world_mut(), resources));
+    // runs a single frame of a headless engine, the clock advances by the fixed delta time
+    pub fn step(&mut self) -> Result<(), EngineError> {
+        let (time, keys) = match &mut self.headless {
+            Some(headless) => (headless.time, mem::replace(&mut headless.keys, Vec::new())),
+            None => return Err(EngineError::NotHeadless),
+        };
 
-            // execute scene systems
-            scene.execute_systems(resources);
+        if !self.started {
+            self.start()?;
+        }
 
-            // execute engine render system
-            self.render_system.execute(scene.world_mut(), resources);
+        let windows = self.windows.len().max(1);
+        if self.inputs.len() < windows {
+            self.inputs.resize_with(windows, Input::new);
+        }
 
-            let mut input = resources.get_mut::<Input>().unwrap();
+        self.begin_frame();
 
-            input::clean_up(&mut input);
-        });
+        for (key, button) in keys {
+            self.update_input(WindowKey::main().index(), key, button, time);
+        }
+
+        let delta_time = match self.resources.get::<Tick>() {
+            Some(tick) => tick.fixed_delta_time(),
+            None => DEFAULT_DELTA_TIME,
+        };
+        let active_scenes = self.update(time, Some(delta_time))?;
+
+        for index in 0..windows {
+            self.render_window(&active_scenes, index);
+        }
+
+        self.end_frame();
+
+        if let Some(headless) = &mut self.headless {
+            headless.time += Duration::from_secs_f32(delta_time);
+        }
+
+        Ok(())
+    }
+
+    pub fn step_frames(&mut self, frames: u32) -> Result<(), EngineError> {
+        for _ in 0..frames {
+            self.step()?;
+        }
+
+        Ok(())
+    }
+
+    // synthetic input of a headless engine, applied to the main window at the next step
+    pub fn press_key(&mut self, key: Key) {
+        self.queue_key(key, Action::Press);
+    }
+
+    pub fn release_key(&mut self, key: Key) {
+        self.queue_key(key, Action::Release);
+    }
+
+    pub fn is_headless(&self) -> bool {
+        self.headless.is_some()
+    }
+
+    pub fn world(&self, scene: K) -> Option<&World> {
+        self.scenes.get(&scene).map(|scene| scene.world())
+    }
+
+    pub fn world_mut(&mut self, scene: K) -> Option<&mut World> {
+        self.scenes.get_mut(&scene).map(|scene| scene.world_mut())
+    }
+
+    pub fn resources(&self) -> &Resources {
+        &self.resources
+    }
+
+    pub fn resources_mut(&mut self) -> &mut Resources {
+        &mut self.resources
+    }
+
+    fn queue_key(&mut self, key: Key, action: Action) {
+        if let Some(headless) = &mut self.headless {
+            headless
+                .keys
+                .push((key, Button::new(key, action, Modifier::Unknown)));
+        }
+    }
+
+    fn start(&mut self) -> Result<(), EngineError> {
+        if self.scenes.is_empty() {
+            return Err(EngineError::NoScene);
+        }
+
+        // systems are only ordered against the ones of the same stage, global ones against global
+        // ones and the ones of a scene against those of the scene
+        let names: HashSet<&str> = self
+            .systems
+            .names()
+            .chain(
+                self.scenes
+                    .values()
+                    .flat_map(|scene| scene.systems().names()),
+            )
+            .collect();
+        self.systems.check_names(&names)?;
+        for scene in self.scenes.values() {
+            scene.systems().check_names(&names)?;
+        }
+
+        self.systems.sort()?;
+        for scene in self.scenes.values_mut() {
+            scene.sort_systems()?;
+        }
+
+        let events = match self.resources.get::<SceneManagement<K>>() {
+            Some(scene_management) => scene_management::start(&scene_management),
+            None => Vec::new(),
+        };
+        apply_scene_events(&mut self.scenes, &mut self.resources, events);
+
+        self.started = true;
+        Ok(())
+    }
+
+    // events pushed before the canvas loop runs arrive in its first frame
+    fn begin_frame(&mut self) {
+        if let Some(ref mut user_events) = self.resources.get_mut::<UserEvents>() {
+            user_events::clear(user_events);
+            for event in self.events.try_iter() {
+                user_events::receive(user_events, &self.user_event_types, event);
+            }
+        }
+
+        for (_, update) in &self.channels {
+            update(&mut self.resources);
+        }
+    }
+
+    // without a delta time the tick measures the time since the last frame
+    fn update(
+        &mut self,
+        time: Instant,
+        delta_time: Option<f32>,
+    ) -> Result<Vec<(K, bool)>, EngineError> {
+        let resources = &mut self.resources;
+        let inputs = &mut self.inputs;
+
+        if let Some(ref mut input) = resources.get_mut::<Input>() {
+            input::advance(input, time);
+        }
+        inputs
+            .iter_mut()
+            .for_each(|input| input::advance(input, time));
+
+        let mut fixed_steps = 0;
+        let (delta_time, unscaled_delta_time) = match resources.get_mut::<Tick>() {
+            Some(ref mut tick) => {
+                match delta_time {
+                    Some(delta_time) => tick::advance(tick, delta_time),
+                    None => tick::update(tick),
+                }
+                fixed_steps = tick::fixed_steps(tick);
+                (tick.delta_time(), tick.unscaled_delta_time())
+            }
+            None => (delta_time.unwrap_or(0.0), delta_time.unwrap_or(0.0)),
+        };
+
+        timers::update(resources, delta_time);
+
+        // transitions keep running while the time is paused
+        let (events, stack) = match resources.get_mut::<SceneManagement<K>>() {
+            Some(ref mut scene_management) => (
+                scene_management::update(scene_management, unscaled_delta_time),
+                scene_management.stack().to_vec(),
+            ),
+            None => return Err(EngineError::NoScene),
+        };
+        apply_scene_events(&mut self.scenes, resources, events);
+
+        let current_scene = stack.last().ok_or(EngineError::NoScene)?;
+        if !self.scenes.contains_key(current_scene) {
+            return Err(EngineError::SceneNotFound);
+        }
+
+        // the whole scene stack is rendered but only its top is updated,
+        // scenes bound to a secondary window are always active
+        let mut active_scenes: Vec<(K, bool)> = stack
+            .iter()
+            .map(|key| (key.clone(), key == current_scene))
+            .collect();
+        active_scenes.extend(
+            self.scenes
+                .iter()
+                .filter(|(key, scene)| scene.window() != WindowKey::main() && !stack.contains(*key))
+                .map(|(key, _)| (key.clone(), true)),
+        );
+
+        // global systems run once on the current scene, then the global systems for each scene and
+        // the systems of the scene run on every updated scene
+        for stage in Stage::update_stages().iter() {
+            let repeat = match stage {
+                Stage::FixedUpdate => fixed_steps,
+                _ => 1,
+            };
+
+            for _ in 0..repeat {
+                if let Some(scene) = self.scenes.get_mut(current_scene) {
+                    self.systems
+                        .execute_where(*stage, false, scene.world_mut(), resources);
+                }
+                apply_commands(&mut self.scenes, resources, current_scene);
+
+                for (key, _) in active_scenes.iter().filter(|(_, update)| *update) {
+                    let window = match self.scenes.get(key) {
+                        Some(scene) => scene.window().index(),
+                        None => continue,
+                    };
+
+                    swap_input(resources, inputs, window);
+                    if let Some(scene) = self.scenes.get_mut(key) {
+                        self.systems
+                            .execute_where(*stage, true, scene.world_mut(), resources);
+                    }
+                    apply_commands(&mut self.scenes, resources, key);
+                    if let Some(scene) = self.scenes.get_mut(key) {
+                        scene.execute_systems(*stage, resources);
+                    }
+                    swap_input(resources, inputs, window);
+                    apply_commands(&mut self.scenes, resources, key);
+                }
+            }
+        }
+
+        Ok(active_scenes)
+    }
+
+    fn render_window(&mut self, active_scenes: &[(K, bool)], window: usize) {
+        let keys: Vec<&K> = active_scenes
+            .iter()
+            .map(|(key, _)| key)
+            .filter(|key| self.scene_window(key) == Some(window))
+            .collect();
+        if let Some(ref mut screen) = self.resources.get_mut::<Screen>() {
+            screen::set_window(screen, WindowKey::new(window));
+        }
+
+        for (index, key) in keys.iter().enumerate() {
+            self.render(key, window, index == 0, index + 1 == keys.len());
+        }
+    }
+
+    // render stages run for every rendered scene, global systems included
+    fn render(&mut self, key: &K, window: usize, first: bool, last: bool) {
+        let resources = &mut self.resources;
+        let scene = match self.scenes.get_mut(key) {
+            Some(scene) => scene,
+            None => return,
+        };
+
+        // only the scene on top of the stack is covered by the transition
+        let transition = resources
+            .get::<SceneManagement<K>>()
+            .filter(|scene_management| scene_management.current() == Some(key))
+            .and_then(|scene_management| {
+                scene_management
+                    .transition()
+                    .map(|state| (state.transition().kind(), state.coverage()))
+            });
+        if let Some(ref mut screen) = resources.get_mut::<Screen>() {
+            screen::set_transition(screen, transition);
+            screen::set_scene(screen, scene.id(), first, last);
+        }
+
+        swap_input(resources, &mut self.inputs, window);
+
+        for stage in Stage::render_stages().iter() {
+            self.systems.execute(*stage, scene.world_mut(), resources);
+            scene.execute_systems(*stage, resources);
+        }
+
+        swap_input(resources, &mut self.inputs, window);
+        apply_commands(&mut self.scenes, resources, key);
+    }
+
+    // needs the context of the window to be current, before its buffers get swapped
+    fn take_screenshot(&mut self, window: WindowKey, size: Option<(u32, u32)>) {
+        // the request is kept for the next frame when the window can not be read
+        let (width, height) = match size {
+            Some(size) => size,
+            None => return,
+        };
+
+        let requested = match self.resources.get_mut::<Screenshots>() {
+            Some(ref mut screenshots) => screenshot::take(screenshots, window),
+            None => false,
+        };
+        if !requested {
+            return;
+        }
+
+        let image = graphic::api::read_pixels(Rect::new(0, 0, width as i32, height as i32));
+        if let Some(ref mut events) = self.resources.get_mut::<Events<ScreenshotTaken>>() {
+            events.send(ScreenshotTaken::new(window, image));
+        }
+    }
+
+    fn end_frame(&mut self) {
+        // debug shapes are kept for seconds of real time
+        let delta_time = self
+            .resources
+            .get::<Tick>()
+            .map_or(0.0, |tick| tick.unscaled_delta_time());
+        if let Some(ref mut debug_draw) = self.resources.get_mut::<DebugDraw>() {
+            debug_draw::update(debug_draw, delta_time);
+        }
+
+        if let Some(ref mut screen) = self.resources.get_mut::<Screen>() {
+            screen::next_frame(screen);
+        }
+
+        if let Some(ref mut input) = self.resources.get_mut::<Input>() {
+            input::clean_up(input);
+        }
+        self.inputs
+            .iter_mut()
+            .for_each(|input| input::clean_up(input));
+    }
+
+    fn detach_scenes(&mut self, window: WindowKey) {
+        self.scenes
+            .values_mut()
+            .filter(|scene| scene.window() == window)
+            .for_each(|scene| scene.set_window(WindowKey::main()));
+    }
+
+    fn scene_window(&self, key: &K) -> Option<usize> {
+        self.scenes.get(key).map(|scene| scene.window().index())
+    }
+
+    fn update_input(&mut self, window: usize, key: Key, button: Button, time: Instant) {
+        if window == WindowKey::main().index() {
+            if let Some(ref mut input) = self.resources.get_mut::<Input>() {
+                input::update_at(input, key, button, time);
+            }
+        } else if let Some(input) = self.inputs.get_mut(window) {
+            input::update_at(input, key, button, time);
+        }
+    }
+}
+
+fn apply_scene_events<K: SceneKey>(
+    scenes: &mut HashMap<K, Scene>,
+    resources: &mut Resources,
+    events: Vec<SceneEvent<K>>,
+) {
+    for event in events {
+        match event {
+            SceneEvent::Enter(key) => {
+                if let Some(scene) = scenes.get_mut(&key) {
+                    scene.enter(resources);
+                }
+            }
+            SceneEvent::Exit(key) => {
+                if let Some(scene) = scenes.get_mut(&key) {
+                    scene.exit(resources);
+                }
+            }
+            SceneEvent::Pause(key) => {
+                if let Some(scene) = scenes.get_mut(&key) {
+                    scene.pause(resources);
+                }
+            }
+            SceneEvent::Resume(key) => {
+                if let Some(scene) = scenes.get_mut(&key) {
+                    scene.resume(resources);
+                }
+            }
+        }
+    }
+}
+
+fn apply_commands<K: SceneKey>(
+    scenes: &mut HashMap<K, Scene>,
+    resources: &mut Resources,
+    current_scene: &K,
+) {
+    let queued = match resources.get_mut::<Commands<K>>() {
+        Some(ref mut commands) => commands::take(commands),
+        None => return,
+    };
+
+    for (target, command) in queued {
+        let key = target.as_ref().unwrap_or(current_scene);
+
+        if let Some(scene) = scenes.get_mut(key) {
+            command(scene.world_mut());
+        }
+    }
+}
+
+fn update_channel<T: Send + Sync + 'static>(resources: &mut Resources) {
+    if let Some(ref mut events) = resources.get_mut::<Events<T>>() {
+        events::update(events);
+    }
+}
+
+fn with_property(
+    prefab: Prefab,
+    component: &str,
+    field: &str,
+    property: &Property,
+) -> Result<Prefab, SerializationError> {
+    match property {
+        Property::Bool(value) => prefab.with_field(component, field, value),
+        Property::Int(value) => prefab.with_field(component, field, value),
+        Property::Float(value) => prefab.with_field(component, field, value),
+        Property::String(value) | Property::File(value) => {
+            prefab.with_field(component, field, value)
+        }
+        Property::Color(value) => prefab.with_field(component, field, value),
+        Property::Object(value) => prefab.with_field(component, field, value),
+    }
+}
+
+fn swap_input(resources: &mut Resources, inputs: &mut [Input], window: usize) {
+    if window == WindowKey::main().index() {
+        return;
+    }
+
+    if let (Some(ref mut input), Some(window_input)) =
+        (resources.get_mut::<Input>(), inputs.get_mut(window))
+    {
+        mem::swap(&mut **input, window_input);
     }
 }
@@ -0,0 +1,133 @@
+extern crate context;
+
+use context::canvas::WindowConfig;
+
+use crate::default_plugins;
+use crate::default_plugins::default_plugins;
+use crate::engine::Engine;
+use crate::error::EngineError;
+use crate::plugin::Plugin;
+use crate::scene_management::SceneKey;
+use crate::stage;
+use crate::stage::Ordering;
+
+pub struct EngineBuilder<K: SceneKey> {
+    windows: Vec<WindowConfig>,
+    scenes: Vec<K>,
+    initial_scene: Option<K>,
+    plugins: Vec<Box<dyn Plugin<K>>>,
+    default_plugins: bool,
+    headless: bool,
+}
+
+impl<K: SceneKey> EngineBuilder<K> {
+    pub fn new() -> Self {
+        Self {
+            windows: Vec::new(),
+            scenes: Vec::new(),
+            initial_scene: None,
+            plugins: Vec::new(),
+            default_plugins: true,
+            headless: false,
+        }
+    }
+
+    // the first window is the main window
+    pub fn window(mut self, config: WindowConfig) -> Self {
+        self.windows.push(config);
+        self
+    }
+
+    pub fn scene(mut self, key: K) -> Self {
+        self.scenes.push(key);
+        self
+    }
+
+    // defaults to the first scene
+    pub fn initial_scene(mut self, key: K) -> Self {
+        self.initial_scene = Some(key);
+        self
+    }
+
+    // a plugin with the name of a default plugin replaces it
+    pub fn plugin<P>(mut self, plugin: P) -> Self
+    where
+        P: Plugin<K> + 'static,
+    {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
+    pub fn without_default_plugins(mut self) -> Self {
+        self.default_plugins = false;
+        self
+    }
+
+    // no window is created, the engine is driven with step
+    pub fn headless(mut self) -> Self {
+        self.headless = true;
+        self
+    }
+
+    pub fn build(self) -> Result<Engine<K>, EngineError> {
+        let mut engine = Engine::empty();
+        if self.headless {
+            engine.set_headless();
+        }
+
+        for config in self.windows {
+            engine.create_window(config);
+        }
+
+        for key in self.scenes {
+            engine.create_scene(key);
+        }
+
+        if let Some(key) = self.initial_scene {
+            engine.change_scene(key)?;
+        }
+
+        let mut plugins = match (self.default_plugins, self.headless) {
+            (true, true) => default_plugins::headless_plugins(),
+            (true, false) => default_plugins(),
+            (false, _) => Vec::new(),
+        };
+
+        for plugin in self.plugins {
+            match plugins
+                .iter()
+                .position(|installed| installed.name() == plugin.name())
+            {
+                Some(index) => plugins[index] = plugin,
+                None => plugins.push(plugin),
+            }
+        }
+
+        // dependencies are installed first, otherwise the order of the plugins is kept
+        let orderings: Vec<Ordering> = plugins
+            .iter()
+            .map(|plugin| Ordering {
+                name: Some(plugin.name().to_owned()),
+                before: Vec::new(),
+                after: plugin
+                    .dependencies()
+                    .into_iter()
+                    .map(|dependency| dependency.to_owned())
+                    .collect(),
+            })
+            .collect();
+        let order = stage::sort(&orderings).map_err(EngineError::OrderingPluginsFailed)?;
+
+        for index in order {
+            engine.install(plugins[index].as_ref())?;
+        }
+
+        Ok(engine)
+    }
+}
+
+impl<K: SceneKey> Default for EngineBuilder<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,98 @@
+extern crate context;
+extern crate graphic;
+
+use std::error::Error;
+use std::fmt;
+
+use context::canvas::CanvasError;
+use context::event::EventError;
+use graphic::api::ApiError;
+
+use crate::scene_management::SceneError;
+use crate::stage::StageError;
+
+#[derive(Debug)]
+pub enum EngineError {
+    CreatingCanvasFailed(CanvasError),
+    CreatingContextFailed,
+    LoadingGraphicFunctionsFailed,
+    RegisteringEventFailed(EventError),
+    NoScene,
+    SceneNotFound,
+    OrderingSystemsFailed(StageError),
+    OrderingPluginsFailed(StageError),
+    MissingPluginDependency(String, String),
+    DuplicatePlugin(String),
+    NotHeadless,
+    IsHeadless,
+}
+
+impl From<CanvasError> for EngineError {
+    fn from(error: CanvasError) -> Self {
+        match error {
+            CanvasError::CreatingContextFailed => EngineError::CreatingContextFailed,
+            error => EngineError::CreatingCanvasFailed(error),
+        }
+    }
+}
+
+impl From<ApiError> for EngineError {
+    fn from(_: ApiError) -> Self {
+        EngineError::LoadingGraphicFunctionsFailed
+    }
+}
+
+impl From<EventError> for EngineError {
+    fn from(error: EventError) -> Self {
+        EngineError::RegisteringEventFailed(error)
+    }
+}
+
+impl From<SceneError> for EngineError {
+    fn from(_: SceneError) -> Self {
+        EngineError::SceneNotFound
+    }
+}
+
+impl From<StageError> for EngineError {
+    fn from(error: StageError) -> Self {
+        EngineError::OrderingSystemsFailed(error)
+    }
+}
+
+impl fmt::Display for EngineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EngineError::CreatingCanvasFailed(error) => {
+                write!(f, "could not create the window: {:?}", error)
+            }
+            EngineError::CreatingContextFailed => {
+                write!(f, "could not create the graphics context")
+            }
+            EngineError::LoadingGraphicFunctionsFailed => {
+                write!(f, "could not load the graphics functions")
+            }
+            EngineError::RegisteringEventFailed(error) => {
+                write!(f, "could not register an event type: {:?}", error)
+            }
+            EngineError::NoScene => write!(f, "no scene was created"),
+            EngineError::SceneNotFound => write!(f, "the current scene does not exist"),
+            EngineError::OrderingSystemsFailed(error) => write!(f, "{}", error),
+            EngineError::OrderingPluginsFailed(error) => {
+                write!(f, "the plugin dependencies are cyclic: {}", error)
+            }
+            EngineError::MissingPluginDependency(plugin, dependency) => write!(
+                f,
+                "the plugin {} depends on the plugin {} which is not installed",
+                plugin, dependency
+            ),
+            EngineError::DuplicatePlugin(plugin) => {
+                write!(f, "a plugin named {} is already installed", plugin)
+            }
+            EngineError::NotHeadless => write!(f, "only a headless engine can be stepped"),
+            EngineError::IsHeadless => write!(f, "a headless engine can not be run"),
+        }
+    }
+}
+
+impl Error for EngineError {}
@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+struct Entry<T> {
+    id: u64,
+    frame: u64,
+    event: T,
+}
+
+// every reader keeps its own position, so each reader receives every event once
+pub struct EventReader<T> {
+    next: u64,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> EventReader<T> {
+    // reads all events that are still kept by the channel
+    pub fn new() -> Self {
+        Self {
+            next: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for EventReader<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) fn update<T>(events: &mut Events<T>) {
+    events.frame += 1;
+
+    let frame = events.frame;
+    let lifetime = events.lifetime;
+    while let Some(entry) = events.events.front() {
+        if frame - entry.frame < lifetime {
+            break;
+        }
+
+        events.events.pop_front();
+    }
+}
+
+// events are dropped after the given number of frames
+pub struct Events<T> {
+    events: VecDeque<Entry<T>>,
+    next_id: u64,
+    frame: u64,
+    lifetime: u64,
+}
+
+impl<T> Events<T> {
+    pub(crate) fn new(lifetime: u32) -> Self {
+        Self {
+            events: VecDeque::new(),
+            next_id: 0,
+            frame: 0,
+            lifetime: u64::from(lifetime.max(1)),
+        }
+    }
+
+    pub fn send(&mut self, event: T) {
+        self.events.push_back(Entry {
+            id: self.next_id,
+            frame: self.frame,
+            event,
+        });
+        self.next_id += 1;
+    }
+
+    // a reader that only receives the events sent from now on
+    pub fn reader(&self) -> EventReader<T> {
+        EventReader {
+            next: self.next_id,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn read<'a>(&'a self, reader: &mut EventReader<T>) -> impl Iterator<Item = &'a T> {
+        let next = reader.next;
+        reader.next = self.next_id;
+
+        self.events
+            .iter()
+            .filter(move |entry| entry.id >= next)
+            .map(|entry| &entry.event)
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    pub fn lifetime(&self) -> u32 {
+        self.lifetime as u32
+    }
+
+    pub fn set_lifetime(&mut self, lifetime: u32) {
+        self.lifetime = u64::from(lifetime.max(1));
+    }
+}
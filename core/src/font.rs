@@ -0,0 +1,375 @@
+extern crate ab_glyph;
+extern crate graphic;
+extern crate image;
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use ab_glyph::Font as _;
+use ab_glyph::FontArc;
+use ab_glyph::PxScale;
+use ab_glyph::ScaleFont;
+use graphic::region::Region;
+use image::Rgba;
+use image::RgbaImage;
+
+#[derive(Debug)]
+pub enum FontError {
+    OpeningFileFailed,
+    ReadingFileFailed,
+    ParsingFailed(String),
+    // a glyph refers to a page the font does not have
+    UnknownPage(u32),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BitmapGlyph {
+    page: u32,
+    region: Region,
+    // from the left of the pen position and from the top of the line to the top of the glyph
+    x_offset: f32,
+    y_offset: f32,
+    advance: f32,
+}
+
+impl BitmapGlyph {
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    // relative to the page's image
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    pub fn x_offset(&self) -> f32 {
+        self.x_offset
+    }
+
+    pub fn y_offset(&self) -> f32 {
+        self.y_offset
+    }
+
+    pub fn advance(&self) -> f32 {
+        self.advance
+    }
+}
+
+// a font exported in the text format of angelcode's bmfont, metrics are in pixels of its pages
+#[derive(Clone, PartialEq, Debug)]
+pub struct BitmapFont {
+    face: String,
+    size: f32,
+    line_height: f32,
+    base: f32,
+    pages: Vec<String>,
+    glyphs: HashMap<char, BitmapGlyph>,
+    kernings: HashMap<(char, char), f32>,
+}
+
+impl BitmapFont {
+    // the page images are resolved relative to the file
+    pub fn from_file(path: &str) -> Result<Self, FontError> {
+        let mut file = File::open(path).map_err(|_| FontError::OpeningFileFailed)?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(|_| FontError::ReadingFileFailed)?;
+
+        let mut font = Self::parse(&content)?;
+        if let Some(directory) = Path::new(path).parent() {
+            for page in font.pages.iter_mut() {
+                *page = directory.join(&page).to_string_lossy().into_owned();
+            }
+        }
+
+        Ok(font)
+    }
+
+    pub fn parse(source: &str) -> Result<Self, FontError> {
+        let mut font = Self {
+            face: String::new(),
+            size: 0.0,
+            line_height: 0.0,
+            base: 0.0,
+            pages: Vec::new(),
+            glyphs: HashMap::new(),
+            kernings: HashMap::new(),
+        };
+        let mut pages: Vec<(u32, String)> = Vec::new();
+
+        for line in source.lines() {
+            let (tag, attributes) = attributes(line);
+
+            match tag {
+                "info" => {
+                    font.face = attributes.get("face").copied().unwrap_or("").to_owned();
+                    // negative sizes match the height of the characters instead of the cells
+                    font.size = number::<f32>(&attributes, "size")?.abs();
+                }
+                "common" => {
+                    font.line_height = number(&attributes, "lineHeight")?;
+                    font.base = number(&attributes, "base")?;
+                }
+                "page" => pages.push((
+                    number(&attributes, "id")?,
+                    attributes.get("file").copied().unwrap_or("").to_owned(),
+                )),
+                "char" => {
+                    // the replacement glyph some exporters add has the id -1 and is skipped
+                    let character = match character(number(&attributes, "id")?) {
+                        Some(character) => character,
+                        None => continue,
+                    };
+
+                    font.glyphs.insert(
+                        character,
+                        BitmapGlyph {
+                            page: number(&attributes, "page")?,
+                            region: Region::new(
+                                number(&attributes, "x")?,
+                                number(&attributes, "y")?,
+                                number(&attributes, "width")?,
+                                number(&attributes, "height")?,
+                            ),
+                            x_offset: number(&attributes, "xoffset")?,
+                            y_offset: number(&attributes, "yoffset")?,
+                            advance: number(&attributes, "xadvance")?,
+                        },
+                    );
+                }
+                "kerning" => {
+                    let first = character(number(&attributes, "first")?);
+                    let second = character(number(&attributes, "second")?);
+                    if let (Some(first), Some(second)) = (first, second) {
+                        font.kernings
+                            .insert((first, second), number(&attributes, "amount")?);
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if font.size <= 0.0 {
+            font.size = font.line_height;
+        }
+        if font.size <= 0.0 {
+            return Err(FontError::ParsingFailed("the font has no size".to_owned()));
+        }
+
+        pages.sort_by_key(|(id, _)| *id);
+        for (index, (id, file)) in pages.into_iter().enumerate() {
+            if id as usize != index {
+                return Err(FontError::UnknownPage(index as u32));
+            }
+            font.pages.push(file);
+        }
+        if let Some(glyph) = font
+            .glyphs
+            .values()
+            .find(|glyph| glyph.page as usize >= font.pages.len())
+        {
+            return Err(FontError::UnknownPage(glyph.page));
+        }
+
+        Ok(font)
+    }
+
+    pub fn face(&self) -> &str {
+        &self.face
+    }
+
+    // the size the font got exported with
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    // from the top of the line to the baseline
+    pub fn base(&self) -> f32 {
+        self.base
+    }
+
+    // the image files by page id
+    pub fn pages(&self) -> &[String] {
+        &self.pages
+    }
+
+    pub fn glyph(&self, character: char) -> Option<&BitmapGlyph> {
+        self.glyphs.get(&character)
+    }
+
+    pub fn kerning(&self, first: char, second: char) -> f32 {
+        self.kernings.get(&(first, second)).copied().unwrap_or(0.0)
+    }
+}
+
+#[derive(Clone, Debug)]
+enum FontKind {
+    TrueType(FontArc),
+    Bitmap(BitmapFont),
+}
+
+// a true type or bitmap font, metrics are in pixels for the given size
+#[derive(Clone, Debug)]
+pub struct Font {
+    kind: FontKind,
+}
+
+impl Font {
+    // .fnt files are loaded as bitmap fonts, anything else as true type or open type font
+    pub fn from_file(path: &str) -> Result<Self, FontError> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str());
+        if extension == Some("fnt") {
+            return Ok(Self::from_bitmap(BitmapFont::from_file(path)?));
+        }
+
+        let mut file = File::open(path).map_err(|_| FontError::OpeningFileFailed)?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)
+            .map_err(|_| FontError::ReadingFileFailed)?;
+
+        Self::from_bytes(data)
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, FontError> {
+        let font = FontArc::try_from_vec(data)
+            .map_err(|error| FontError::ParsingFailed(error.to_string()))?;
+
+        Ok(Self {
+            kind: FontKind::TrueType(font),
+        })
+    }
+
+    pub fn from_bitmap(font: BitmapFont) -> Self {
+        Self {
+            kind: FontKind::Bitmap(font),
+        }
+    }
+
+    pub fn bitmap(&self) -> Option<&BitmapFont> {
+        match &self.kind {
+            FontKind::Bitmap(font) => Some(font),
+            FontKind::TrueType(_) => None,
+        }
+    }
+
+    // from the top of a line to its baseline
+    pub fn ascent(&self, size: f32) -> f32 {
+        match &self.kind {
+            FontKind::TrueType(font) => font.as_scaled(PxScale::from(size)).ascent(),
+            FontKind::Bitmap(font) => font.base * size / font.size,
+        }
+    }
+
+    // the distance between the baselines of two lines
+    pub fn line_height(&self, size: f32) -> f32 {
+        match &self.kind {
+            FontKind::TrueType(font) => {
+                let font = font.as_scaled(PxScale::from(size));
+                font.height() + font.line_gap()
+            }
+            FontKind::Bitmap(font) => font.line_height * size / font.size,
+        }
+    }
+
+    pub fn advance(&self, character: char, size: f32) -> f32 {
+        match &self.kind {
+            FontKind::TrueType(font) => {
+                let font = font.as_scaled(PxScale::from(size));
+                font.h_advance(font.glyph_id(character))
+            }
+            FontKind::Bitmap(font) => font
+                .glyph(character)
+                .map_or(0.0, |glyph| glyph.advance * size / font.size),
+        }
+    }
+
+    pub fn kerning(&self, first: char, second: char, size: f32) -> f32 {
+        match &self.kind {
+            FontKind::TrueType(font) => {
+                let font = font.as_scaled(PxScale::from(size));
+                font.kern(font.glyph_id(first), font.glyph_id(second))
+            }
+            FontKind::Bitmap(font) => font.kerning(first, second) * size / font.size,
+        }
+    }
+
+    // white with the coverage as alpha and the offset of its top left corner from the pen
+    // position, y pointing up, none for bitmap fonts and glyphs without outline
+    pub(crate) fn rasterize(&self, character: char, size: f32) -> Option<(RgbaImage, f32, f32)> {
+        let font = match &self.kind {
+            FontKind::TrueType(font) => font,
+            FontKind::Bitmap(_) => return None,
+        };
+
+        let glyph = font.as_scaled(PxScale::from(size)).scaled_glyph(character);
+        let outline = font.outline_glyph(glyph)?;
+        let bounds = outline.px_bounds();
+
+        let mut image = RgbaImage::new(bounds.width() as u32, bounds.height() as u32);
+        if image.width() == 0 || image.height() == 0 {
+            return None;
+        }
+        outline.draw(|x, y, coverage| {
+            let alpha = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+            image.put_pixel(x, y, Rgba([255, 255, 255, alpha]));
+        });
+
+        Some((image, bounds.min.x, -bounds.min.y))
+    }
+}
+
+// splits a line like `char id=65 x="0"` into its tag and attributes
+fn attributes(line: &str) -> (&str, HashMap<&str, &str>) {
+    let line = line.trim();
+    let (tag, mut rest) = match line.find(char::is_whitespace) {
+        Some(index) => (&line[..index], line[index..].trim_start()),
+        None => (line, ""),
+    };
+
+    let mut attributes = HashMap::new();
+    while let Some(equals) = rest.find('=') {
+        let key = rest[..equals].trim();
+        let value = &rest[equals + 1..];
+
+        let (value, remaining) = if let Some(quoted) = value.strip_prefix('"') {
+            match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            }
+        } else {
+            match value.find(char::is_whitespace) {
+                Some(end) => (&value[..end], &value[end..]),
+                None => (value, ""),
+            }
+        };
+
+        attributes.insert(key, value);
+        rest = remaining.trim_start();
+    }
+
+    (tag, attributes)
+}
+
+fn character(id: i64) -> Option<char> {
+    u32::try_from(id).ok().and_then(std::char::from_u32)
+}
+
+fn number<T: std::str::FromStr>(
+    attributes: &HashMap<&str, &str>,
+    name: &str,
+) -> Result<T, FontError> {
+    let value = attributes.get(name).copied().unwrap_or("0");
+
+    value
+        .parse()
+        .map_err(|_| FontError::ParsingFailed(format!("{} is not a number: {}", name, value)))
+}
@@ -0,0 +1,85 @@
+extern crate graphic;
+
+use std::collections::HashMap;
+
+use graphic::texture::Texture;
+
+use crate::component::Text;
+use crate::font::Font;
+use crate::glyph_cache;
+use crate::glyph_cache::GlyphCache;
+use crate::glyph_cache::GlyphQuad;
+use crate::sprite_management::SpriteManagement;
+use crate::text_layout::TextLayout;
+
+pub(crate) struct FontManagement {
+    fonts: HashMap<String, Font>,
+    glyphs: GlyphCache,
+    // the sprite texture of every page of the glyph cache
+    textures: Vec<usize>,
+}
+
+impl FontManagement {
+    pub(crate) fn new() -> Self {
+        Self {
+            fonts: HashMap::new(),
+            glyphs: GlyphCache::new(),
+            textures: Vec::new(),
+        }
+    }
+}
+
+impl FontManagement {
+    pub(crate) fn add(&mut self, name: &str, font: Font) {
+        self.glyphs.remove(name);
+        self.fonts.insert(name.to_owned(), font);
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&Font> {
+        self.fonts.get(name)
+    }
+
+    // none when the font of the text is unknown
+    pub(crate) fn layout(&mut self, text: &Text) -> Option<(TextLayout, Vec<GlyphQuad>)> {
+        let font = self.fonts.get(text.font())?;
+        let layout = TextLayout::new(font, text);
+        let quads =
+            glyph_cache::glyph_quads(&mut self.glyphs, text.font(), font, text.size(), &layout);
+
+        Some((layout, quads))
+    }
+
+    // the name of the sprite showing the page of a bitmap font
+    pub(crate) fn page(&self, font: &str, page: usize) -> Option<&str> {
+        let pages = self.fonts.get(font)?.bitmap()?.pages();
+        pages.get(page).map(String::as_str)
+    }
+
+    // needs a current graphics context, creates the textures of new pages of the glyph cache
+    pub(crate) fn texture(
+        &mut self,
+        page: usize,
+        sprite_management: &mut SpriteManagement,
+    ) -> Option<usize> {
+        while self.textures.len() <= page {
+            let image = self.glyphs.page(self.textures.len())?;
+            let texture = Texture::from_image(image).ok()?;
+            self.textures.push(sprite_management.add_texture(texture));
+        }
+
+        self.textures.get(page).copied()
+    }
+
+    // needs a current graphics context, uploads the glyphs rasterized since the last call
+    pub(crate) fn upload(&mut self, sprite_management: &SpriteManagement) {
+        for page in self.glyphs.take_dirty() {
+            let texture = self
+                .textures
+                .get(page)
+                .and_then(|texture| sprite_management.texture(*texture));
+            if let (Some(texture), Some(image)) = (texture, self.glyphs.page(page)) {
+                texture.update(image);
+            }
+        }
+    }
+}
@@ -0,0 +1,300 @@
+extern crate graphic;
+extern crate image;
+extern crate math;
+
+use std::collections::HashMap;
+
+use graphic::atlas::SkylinePacker;
+use graphic::region::Region;
+use image::imageops;
+use image::RgbaImage;
+use math::Vec2;
+
+use crate::font::Font;
+use crate::text_layout::TextLayout;
+
+const PAGE_SIZE: u32 = 1024;
+// the least recently used page is cleared for new glyphs once all pages are full
+const MAX_PAGES: usize = 4;
+// keeps linear filtering from bleeding neighbouring glyphs in
+const PADDING: u32 = 1;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct CachedGlyph {
+    pub(crate) page: usize,
+    pub(crate) region: Region,
+    // the top left corner relative to the pen position, y pointing up
+    pub(crate) left: f32,
+    pub(crate) top: f32,
+}
+
+struct GlyphPage {
+    image: RgbaImage,
+    packer: SkylinePacker,
+    dirty: bool,
+    // the use of the cache a glyph of the page was drawn in last
+    used: u64,
+}
+
+// glyphs are rasterized at whole pixel sizes, the quads are scaled to the size of the text
+pub(crate) fn pixel_size(size: f32) -> f32 {
+    size.round().max(1.0)
+}
+
+// glyphs of true type fonts are rasterized into pages the first time they get drawn at a size
+pub(crate) struct GlyphCache {
+    // none for glyphs without outline, they are not rasterized again
+    glyphs: HashMap<String, HashMap<(char, u32), Option<CachedGlyph>>>,
+    pages: Vec<GlyphPage>,
+    uses: u64,
+}
+
+impl GlyphCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            glyphs: HashMap::new(),
+            pages: Vec::new(),
+            uses: 0,
+        }
+    }
+
+    pub(crate) fn get(
+        &mut self,
+        name: &str,
+        font: &Font,
+        character: char,
+        size: f32,
+    ) -> Option<CachedGlyph> {
+        let size = pixel_size(size);
+        let key = (character, size as u32);
+        self.uses += 1;
+        if let Some(glyph) = self.glyphs.get(name).and_then(|glyphs| glyphs.get(&key)) {
+            if let Some(glyph) = glyph {
+                self.pages[glyph.page].used = self.uses;
+            }
+            return *glyph;
+        }
+
+        let glyph = font
+            .rasterize(character, size)
+            .and_then(|(image, left, top)| self.insert(&image, left, top));
+        self.glyphs
+            .entry(name.to_owned())
+            .or_default()
+            .insert(key, glyph);
+
+        glyph
+    }
+
+    // the space of the removed glyphs is reused once their page gets cleared
+    pub(crate) fn remove(&mut self, name: &str) {
+        self.glyphs.remove(name);
+    }
+
+    pub(crate) fn page(&self, index: usize) -> Option<&RgbaImage> {
+        self.pages.get(index).map(|page| &page.image)
+    }
+
+    // the pages that got new glyphs since the last call
+    pub(crate) fn take_dirty(&mut self) -> Vec<usize> {
+        self.pages
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, page)| page.dirty)
+            .map(|(index, page)| {
+                page.dirty = false;
+                index
+            })
+            .collect()
+    }
+
+    fn insert(&mut self, image: &RgbaImage, left: f32, top: f32) -> Option<CachedGlyph> {
+        let width = image.width() + PADDING;
+        let height = image.height() + PADDING;
+        if width > PAGE_SIZE || height > PAGE_SIZE {
+            return None;
+        }
+
+        let mut position = None;
+        for (index, page) in self.pages.iter_mut().enumerate() {
+            if let Some((x, y)) = page.packer.insert(width, height) {
+                position = Some((index, x, y));
+                break;
+            }
+        }
+
+        let (index, x, y) = match position {
+            Some(position) => position,
+            None => {
+                let index = self.clear_page();
+                let (x, y) = self.pages[index].packer.insert(width, height)?;
+                (index, x, y)
+            }
+        };
+
+        let page = &mut self.pages[index];
+        imageops::replace(&mut page.image, image, x, y);
+        page.dirty = true;
+        page.used = self.uses;
+
+        Some(CachedGlyph {
+            page: index,
+            region: Region::new(x, y, image.width(), image.height()),
+            left,
+            top,
+        })
+    }
+
+    // a new page while there are less than the maximum, otherwise the least recently used one
+    // without its glyphs
+    fn clear_page(&mut self) -> usize {
+        let index = match self
+            .pages
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, page)| page.used)
+        {
+            Some((index, _)) if self.pages.len() >= MAX_PAGES => index,
+            _ => {
+                self.pages.push(GlyphPage {
+                    image: RgbaImage::new(PAGE_SIZE, PAGE_SIZE),
+                    packer: SkylinePacker::new(PAGE_SIZE, PAGE_SIZE),
+                    dirty: true,
+                    used: self.uses,
+                });
+                return self.pages.len() - 1;
+            }
+        };
+
+        for glyphs in self.glyphs.values_mut() {
+            glyphs.retain(|_, glyph| !matches!(glyph, Some(glyph) if glyph.page == index));
+        }
+
+        let page = &mut self.pages[index];
+        page.image = RgbaImage::new(PAGE_SIZE, PAGE_SIZE);
+        page.packer = SkylinePacker::new(PAGE_SIZE, PAGE_SIZE);
+        page.dirty = true;
+        index
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) enum GlyphSource {
+    // a page of a bitmap font, the region is relative to its image
+    Page(usize),
+    Cache(usize),
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct GlyphQuad {
+    pub(crate) source: GlyphSource,
+    pub(crate) region: Region,
+    // the bottom left corner relative to the top left corner of the text
+    pub(crate) position: Vec2,
+    pub(crate) size: Vec2,
+}
+
+// the quads of the laid out glyphs, glyphs without image are skipped
+pub(crate) fn glyph_quads(
+    cache: &mut GlyphCache,
+    name: &str,
+    font: &Font,
+    size: f32,
+    layout: &TextLayout,
+) -> Vec<GlyphQuad> {
+    let mut quads = Vec::with_capacity(layout.glyphs().len());
+
+    for glyph in layout.glyphs() {
+        let pen = glyph.position();
+
+        if let Some(bitmap) = font.bitmap() {
+            let bitmap_glyph = match bitmap.glyph(glyph.character()) {
+                Some(bitmap_glyph) => bitmap_glyph,
+                None => continue,
+            };
+            let scale = size / bitmap.size();
+            let region = bitmap_glyph.region();
+            let width = region.width() as f32 * scale;
+            let height = region.height() as f32 * scale;
+            let top = (bitmap.base() - bitmap_glyph.y_offset()) * scale;
+
+            quads.push(GlyphQuad {
+                source: GlyphSource::Page(bitmap_glyph.page() as usize),
+                region,
+                position: Vec2::new(
+                    pen.x + bitmap_glyph.x_offset() * scale,
+                    pen.y + top - height,
+                ),
+                size: Vec2::new(width, height),
+            });
+        } else if let Some(cached) = cache.get(name, font, glyph.character(), size) {
+            let scale = size / pixel_size(size);
+            let width = cached.region.width() as f32 * scale;
+            let height = cached.region.height() as f32 * scale;
+
+            quads.push(GlyphQuad {
+                source: GlyphSource::Cache(cached.page),
+                region: cached.region,
+                position: Vec2::new(
+                    pen.x + cached.left * scale,
+                    pen.y + cached.top * scale - height,
+                ),
+                size: Vec2::new(width, height),
+            });
+        }
+    }
+
+    quads
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // fills a page on its own
+    fn insert_page(cache: &mut GlyphCache) -> usize {
+        cache.uses += 1;
+        let image = RgbaImage::new(PAGE_SIZE - PADDING, PAGE_SIZE - PADDING);
+        cache.insert(&image, 0.0, 0.0).unwrap().page
+    }
+
+    #[test]
+    fn full_caches_reuse_the_least_recently_used_page() {
+        let mut cache = GlyphCache::new();
+        let pages: Vec<usize> = (0..MAX_PAGES).map(|_| insert_page(&mut cache)).collect();
+        assert_eq!(pages, (0..MAX_PAGES).collect::<Vec<_>>());
+
+        let glyph = |page| {
+            Some(CachedGlyph {
+                page,
+                region: Region::new(0, 0, 1, 1),
+                left: 0.0,
+                top: 0.0,
+            })
+        };
+        let glyphs = cache.glyphs.entry("font".to_owned()).or_default();
+        glyphs.insert(('a', 12), glyph(0));
+        glyphs.insert(('b', 12), glyph(1));
+        glyphs.insert((' ', 12), None);
+
+        cache.uses += 1;
+        cache.pages[0].used = cache.uses;
+        cache.take_dirty();
+
+        assert_eq!(insert_page(&mut cache), 1);
+        assert_eq!(cache.pages.len(), MAX_PAGES);
+        assert_eq!(cache.take_dirty(), vec![1]);
+
+        let glyphs = &cache.glyphs["font"];
+        assert_eq!(glyphs.get(&('a', 12)), Some(&glyph(0)));
+        assert_eq!(glyphs.get(&('b', 12)), None);
+        assert_eq!(glyphs.get(&(' ', 12)), Some(&None));
+    }
+
+    #[test]
+    fn sizes_are_rounded_to_whole_pixels() {
+        assert_eq!(pixel_size(12.4), 12.0);
+        assert_eq!(pixel_size(12.5), 13.0);
+        assert_eq!(pixel_size(0.2), 1.0);
+    }
+}
@@ -1,14 +1,99 @@
+mod animation;
+mod commands;
+mod debug_draw;
+mod default_plugins;
 mod engine;
+mod engine_builder;
+mod error;
+mod events;
+mod font;
+mod font_management;
+mod glyph_cache;
+mod plugin;
+mod post_processing;
+mod prefab;
+mod render_cache;
 mod scene;
 mod scene_management;
+mod screen;
+mod screenshot;
+mod serialization;
+mod sprite_batch;
 mod sprite_management;
+mod sprite_sheet;
+mod stage;
 mod system;
+mod system_descriptor;
+mod text_layout;
 mod tick;
+mod tiled;
+mod timers;
+mod user_events;
+mod window;
 
+pub use crate::animation::AnimationFinished;
+pub use crate::commands::SceneCommands;
+pub use crate::debug_draw::DebugShape;
+pub use crate::debug_draw::DebugSpace;
+pub use crate::default_plugins::default_plugins;
+pub use crate::default_plugins::AnimationPlugin;
+pub use crate::default_plugins::InputPlugin;
+pub use crate::default_plugins::NullRenderPlugin;
+pub use crate::default_plugins::RenderPlugin;
+pub use crate::default_plugins::TimePlugin;
 pub use crate::engine::Engine;
+pub use crate::engine_builder::EngineBuilder;
+pub use crate::error::EngineError;
+pub use crate::events::EventReader;
+pub use crate::font::BitmapFont;
+pub use crate::font::BitmapGlyph;
+pub use crate::font::Font;
+pub use crate::font::FontError;
+pub use crate::plugin::Plugin;
+pub use crate::post_processing::PostEffect;
+pub use crate::prefab::Prefab;
+pub use crate::scene::SceneHooks;
+pub use crate::scene_management::SceneError;
+pub use crate::scene_management::SceneKey;
+pub use crate::scene_management::Transition;
+pub use crate::scene_management::TransitionKind;
+pub use crate::screenshot::CameraCaptured;
+pub use crate::screenshot::ScreenshotTaken;
+pub use crate::serialization::SerializationError;
+pub use crate::sprite_sheet::SheetFrame;
+pub use crate::sprite_sheet::SheetTag;
+pub use crate::sprite_sheet::SpriteSheet;
+pub use crate::sprite_sheet::SpriteSheetError;
+pub use crate::stage::Stage;
+pub use crate::stage::StageError;
+pub use crate::system_descriptor::SystemDescriptor;
+pub use crate::text_layout::PlacedGlyph;
+pub use crate::text_layout::TextLayout;
+pub use crate::tiled::TiledError;
+pub use crate::tiled::TiledMap;
+pub use crate::tiled::TiledObject;
+pub use crate::tiled::TiledTileset;
+pub use crate::timers::TimerFinished;
+pub use crate::window::WindowKey;
+pub use context::canvas::Fullscreen;
+pub use context::canvas::WindowConfig;
+pub use graphic::program::Uniform;
+pub use graphic::region::Region;
 pub mod component;
 pub mod resource {
+    pub use crate::animation::AnimationClips;
+    pub use crate::commands::Commands;
+    pub use crate::debug_draw::DebugDraw;
+    pub use crate::events::Events;
+    pub use crate::post_processing::PostProcessing;
+    pub use crate::prefab::Prefabs;
     pub use crate::scene_management::SceneManagement as Scene;
+    pub use crate::screen::Screen;
+    pub use crate::screenshot::Screenshots;
+    pub use crate::serialization::ComponentRegistry;
     pub use crate::tick::Tick;
+    pub use crate::timers::Timers;
+    pub use crate::user_events::UserEvents;
+    pub use context::event::EventSender;
     pub use context::input::Input;
 }
@@ -0,0 +1,14 @@
+use crate::engine::Engine;
+use crate::scene_management::SceneKey;
+
+pub trait Plugin<K: SceneKey> {
+    // plugins are identified by their name, a plugin with the same name is only installed once
+    fn name(&self) -> &str;
+
+    // names of the plugins that have to be installed before this one
+    fn dependencies(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    fn build(&self, engine: &mut Engine<K>);
+}
@@ -0,0 +1,263 @@
+extern crate graphic;
+extern crate serde;
+
+use std::collections::HashMap;
+
+use graphic::program::Uniform;
+use serde::Deserialize;
+use serde::Serialize;
+
+// darkens the corners, starting at the radius from the center where 1 is a corner
+const VIGNETTE_SHADER: &str = r#"#version 330 core
+in vec2 uv;
+
+uniform sampler2D source;
+uniform float radius = 0.75;
+uniform float softness = 0.45;
+uniform float intensity = 0.5;
+
+out vec4 color;
+
+void main() {
+    vec4 pixel = texture(source, uv);
+    float center_distance = length(uv - vec2(0.5)) * 1.41421356;
+    float vignette = smoothstep(radius, radius - softness, center_distance);
+    color = vec4(pixel.rgb * mix(1.0, vignette, intensity), pixel.a);
+}
+"#;
+
+// a curved screen with scanlines, the colors are split apart by the aberration in pixels
+const CRT_SHADER: &str = r#"#version 330 core
+in vec2 uv;
+
+uniform sampler2D source;
+uniform vec2 resolution;
+uniform float curvature = 4.0;
+uniform float scanlines = 0.25;
+uniform float aberration = 1.0;
+
+out vec4 color;
+
+void main() {
+    vec2 centered = uv * 2.0 - 1.0;
+    centered *= 1.0 + pow(abs(centered.yx) / curvature, vec2(2.0));
+    vec2 curved = centered * 0.5 + 0.5;
+    if (curved.x < 0.0 || curved.x > 1.0 || curved.y < 0.0 || curved.y > 1.0) {
+        color = vec4(0.0, 0.0, 0.0, 1.0);
+        return;
+    }
+
+    vec2 offset = vec2(aberration / resolution.x, 0.0);
+    vec3 pixel = vec3(
+        texture(source, curved + offset).r,
+        texture(source, curved).g,
+        texture(source, curved - offset).b
+    );
+    float line = sin(curved.y * resolution.y * 3.14159265);
+    color = vec4(pixel * (1.0 - scanlines * line * line), 1.0);
+}
+"#;
+
+// the tint multiplies the colors after brightness, contrast and saturation got applied
+const COLOR_GRADING_SHADER: &str = r#"#version 330 core
+in vec2 uv;
+
+uniform sampler2D source;
+uniform float brightness = 0.0;
+uniform float contrast = 1.0;
+uniform float saturation = 1.0;
+uniform vec4 tint = vec4(1.0);
+
+out vec4 color;
+
+void main() {
+    vec4 pixel = texture(source, uv);
+    vec3 graded = (pixel.rgb + brightness - 0.5) * contrast + 0.5;
+    float luma = dot(graded, vec3(0.2126, 0.7152, 0.0722));
+    graded = mix(vec3(luma), graded, saturation) * tint.rgb;
+    color = vec4(clamp(graded, 0.0, 1.0), pixel.a);
+}
+"#;
+
+// moves the picture by the offset in pixels
+const SCREEN_SHAKE_SHADER: &str = r#"#version 330 core
+in vec2 uv;
+
+uniform sampler2D source;
+uniform vec2 resolution;
+uniform vec2 offset = vec2(0.0);
+
+out vec4 color;
+
+void main() {
+    color = texture(source, uv + offset / resolution);
+}
+"#;
+
+// adds a blur of the pixels brighter than the threshold, spread over the radius in pixels
+const BLOOM_SHADER: &str = r#"#version 330 core
+in vec2 uv;
+
+uniform sampler2D source;
+uniform vec2 resolution;
+uniform float threshold = 0.7;
+uniform float intensity = 1.0;
+uniform float radius = 4.0;
+
+out vec4 color;
+
+vec3 bright(vec2 position) {
+    vec3 pixel = texture(source, position).rgb;
+    float brightness = max(pixel.r, max(pixel.g, pixel.b));
+    return pixel * smoothstep(threshold, 1.0, brightness);
+}
+
+void main() {
+    vec4 pixel = texture(source, uv);
+    vec3 glow = vec3(0.0);
+    float total = 0.0;
+    for (int x = -3; x <= 3; x++) {
+        for (int y = -3; y <= 3; y++) {
+            float weight = exp(-float(x * x + y * y) / 8.0);
+            glow += bright(uv + vec2(x, y) * radius / 3.0 / resolution) * weight;
+            total += weight;
+        }
+    }
+    color = vec4(pixel.rgb + glow / total * intensity, pixel.a);
+}
+"#;
+
+// a pass of post processing drawn with the shader of the given name, the uniforms override the
+// defaults of the shader
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct PostEffect {
+    shader: String,
+    enabled: bool,
+    uniforms: Vec<(String, Uniform)>,
+}
+
+impl PostEffect {
+    pub fn new(shader: &str) -> Self {
+        Self {
+            shader: shader.to_owned(),
+            enabled: true,
+            uniforms: Vec::new(),
+        }
+    }
+
+    pub fn with_uniform(mut self, name: &str, value: Uniform) -> Self {
+        self.set_uniform(name, value);
+        self
+    }
+
+    pub fn shader(&self) -> &str {
+        &self.shader
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn uniforms(&self) -> &[(String, Uniform)] {
+        &self.uniforms
+    }
+
+    pub fn uniform(&self, name: &str) -> Option<Uniform> {
+        self.uniforms
+            .iter()
+            .find(|(uniform, _)| uniform == name)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn set_uniform(&mut self, name: &str, value: Uniform) {
+        match self
+            .uniforms
+            .iter_mut()
+            .find(|(uniform, _)| uniform == name)
+        {
+            Some((_, uniform)) => *uniform = value,
+            None => self.uniforms.push((name.to_owned(), value)),
+        }
+    }
+}
+
+// the effects are drawn in their order, a shader is used by one effect of a list at most
+pub(crate) fn add_effect(effects: &mut Vec<PostEffect>, effect: PostEffect) {
+    match effects
+        .iter_mut()
+        .find(|other| other.shader == effect.shader)
+    {
+        Some(other) => *other = effect,
+        None => effects.push(effect),
+    }
+}
+
+pub(crate) fn effect_mut<'a>(
+    effects: &'a mut [PostEffect],
+    shader: &str,
+) -> Option<&'a mut PostEffect> {
+    effects.iter_mut().find(|effect| effect.shader == shader)
+}
+
+// the fragment shaders of post effects and the effects drawn over the whole window, cameras have
+// effects of their own, the shaders vignette, crt, color_grading, screen_shake and bloom are
+// built in
+pub struct PostProcessing {
+    shaders: HashMap<String, String>,
+    effects: Vec<PostEffect>,
+    // changes with every added shader so that passes get compiled again
+    revision: u64,
+}
+
+impl PostProcessing {
+    pub(crate) fn new() -> Self {
+        let mut post_processing = Self {
+            shaders: HashMap::new(),
+            effects: Vec::new(),
+            revision: 0,
+        };
+        post_processing.add_shader("vignette", VIGNETTE_SHADER);
+        post_processing.add_shader("crt", CRT_SHADER);
+        post_processing.add_shader("color_grading", COLOR_GRADING_SHADER);
+        post_processing.add_shader("screen_shake", SCREEN_SHAKE_SHADER);
+        post_processing.add_shader("bloom", BLOOM_SHADER);
+
+        post_processing
+    }
+
+    // the fragment shader gets the uv of its pixel, the sampler source with the output of the
+    // previous effect, the sampler scene with the unprocessed scene and the resolution in pixels
+    pub fn add_shader(&mut self, name: &str, source: &str) {
+        self.shaders.insert(name.to_owned(), source.to_owned());
+        self.revision += 1;
+    }
+
+    pub fn shader(&self, name: &str) -> Option<&str> {
+        self.shaders.get(name).map(String::as_str)
+    }
+
+    // replaces an effect with the same shader
+    pub fn add_effect(&mut self, effect: PostEffect) {
+        add_effect(&mut self.effects, effect);
+    }
+
+    pub fn remove_effect(&mut self, shader: &str) {
+        self.effects.retain(|effect| effect.shader() != shader);
+    }
+
+    pub fn effects(&self) -> &[PostEffect] {
+        &self.effects
+    }
+
+    pub fn effect_mut(&mut self, shader: &str) -> Option<&mut PostEffect> {
+        effect_mut(&mut self.effects, shader)
+    }
+
+    pub(crate) fn revision(&self) -> u64 {
+        self.revision
+    }
+}
@@ -0,0 +1,208 @@
+extern crate legion;
+extern crate serde;
+extern crate serde_json;
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use legion::entity::Entity;
+use legion::world::World;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::component::Parent;
+use crate::serialization;
+use crate::serialization::ComponentRegistry;
+use crate::serialization::EntityData;
+use crate::serialization::Format;
+use crate::serialization::SerializationError;
+
+// an entity template, the same prefab is also used to describe the overrides of an instance
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub struct Prefab {
+    #[serde(default)]
+    tags: BTreeMap<String, Value>,
+    #[serde(default)]
+    components: BTreeMap<String, Value>,
+    #[serde(default)]
+    children: Vec<Prefab>,
+    // components that only have single fields set, the template has to provide the rest
+    #[serde(skip)]
+    fields: BTreeSet<String>,
+}
+
+impl Prefab {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, SerializationError> {
+        let text = serialization::read_file(path)?;
+
+        serialization::from_str(&text, Format::from_path(path))
+    }
+
+    pub fn with_component<T: Serialize>(
+        mut self,
+        name: &str,
+        component: &T,
+    ) -> Result<Self, SerializationError> {
+        self.components
+            .insert(name.to_owned(), serialization::to_value(component)?);
+        self.fields.remove(name);
+        Ok(self)
+    }
+
+    pub fn with_tag<T: Serialize>(
+        mut self,
+        name: &str,
+        tag: &T,
+    ) -> Result<Self, SerializationError> {
+        self.tags
+            .insert(name.to_owned(), serialization::to_value(tag)?);
+        Ok(self)
+    }
+
+    // sets a single field of a component, the other fields keep the prefab's values
+    pub fn with_field<T: Serialize>(
+        mut self,
+        component: &str,
+        field: &str,
+        value: &T,
+    ) -> Result<Self, SerializationError> {
+        let mut fields = serde_json::Map::new();
+        fields.insert(field.to_owned(), serialization::to_value(value)?);
+        if !self.components.contains_key(component) {
+            self.fields.insert(component.to_owned());
+        }
+        merge_value(
+            self.components
+                .entry(component.to_owned())
+                .or_insert(Value::Null),
+            Value::Object(fields),
+        );
+        Ok(self)
+    }
+
+    pub fn with_child(mut self, child: Prefab) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn children(&self) -> &[Prefab] {
+        &self.children
+    }
+
+    pub fn has_component(&self, name: &str) -> bool {
+        self.components.contains_key(name)
+    }
+
+    pub fn has_tag(&self, name: &str) -> bool {
+        self.tags.contains_key(name)
+    }
+
+    // fields of the overrides replace the ones of the template, children are appended
+    pub(crate) fn merged(&self, overrides: Prefab) -> Result<Prefab, SerializationError> {
+        if let Some(name) = overrides
+            .fields
+            .iter()
+            .find(|name| !self.components.contains_key(*name))
+        {
+            return Err(SerializationError::OverriddenComponentNotFound(
+                name.clone(),
+            ));
+        }
+
+        let mut prefab = self.clone();
+
+        for (name, value) in overrides.components {
+            merge_value(prefab.components.entry(name).or_insert(Value::Null), value);
+        }
+
+        for (name, value) in overrides.tags {
+            prefab.tags.insert(name, value);
+        }
+
+        prefab.children.extend(overrides.children);
+        Ok(prefab)
+    }
+
+    fn entity_data(&self) -> EntityData {
+        EntityData {
+            tags: self.tags.clone(),
+            components: self.components.clone(),
+            parent: None,
+        }
+    }
+}
+
+fn merge_value(target: &mut Value, value: Value) {
+    match (target, value) {
+        (Value::Object(target), Value::Object(value)) => {
+            for (key, value) in value {
+                merge_value(target.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (target, value) => *target = value,
+    }
+}
+
+pub(crate) fn instantiate(
+    registry: &ComponentRegistry,
+    world: &mut World,
+    prefab: &Prefab,
+) -> Result<Entity, SerializationError> {
+    let entity = registry.spawn_entity(world, prefab.entity_data())?;
+
+    for child in &prefab.children {
+        instantiate_child(registry, world, child, entity)?;
+    }
+
+    Ok(entity)
+}
+
+fn instantiate_child(
+    registry: &ComponentRegistry,
+    world: &mut World,
+    prefab: &Prefab,
+    parent: Entity,
+) -> Result<Entity, SerializationError> {
+    let entity = world.insert((), vec![(Parent::new(parent),)])[0];
+    registry.add_to_entity(world, entity, prefab.entity_data())?;
+
+    for child in &prefab.children {
+        instantiate_child(registry, world, child, entity)?;
+    }
+
+    Ok(entity)
+}
+
+pub struct Prefabs {
+    prefabs: HashMap<String, Prefab>,
+}
+
+impl Prefabs {
+    pub(crate) fn new() -> Self {
+        Self {
+            prefabs: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, name: &str, prefab: Prefab) {
+        self.prefabs.insert(name.to_owned(), prefab);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Prefab> {
+        self.prefabs.get(name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.prefabs.contains_key(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.prefabs.keys().map(|name| name.as_str())
+    }
+}
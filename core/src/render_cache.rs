@@ -0,0 +1,117 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// with the frame the value got used last
+type Entries<K, V> = HashMap<K, (u64, V)>;
+
+// gpu data that is built once and reused, the renderer is called for every rendered scene so
+// the keys have to tell the scenes apart
+pub(crate) struct RenderCache<K, V> {
+    entries: Entries<K, V>,
+    frame: u64,
+}
+
+impl<K: Eq + Hash, V> RenderCache<K, V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            frame: 0,
+        }
+    }
+
+    // the frame of the engine, not of a single scene
+    pub(crate) fn set_frame(&mut self, frame: u64) {
+        self.frame = frame;
+    }
+
+    pub(crate) fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.entries.get(key).map(|(_, value)| value)
+    }
+
+    // the value is kept for this frame
+    pub(crate) fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        let frame = self.frame;
+
+        self.entries.get_mut(key).map(|(used, value)| {
+            *used = frame;
+            value
+        })
+    }
+
+    pub(crate) fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.entries
+            .insert(key, (self.frame, value))
+            .map(|(_, value)| value)
+    }
+
+    pub(crate) fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.entries.remove(key).map(|(_, value)| value)
+    }
+
+    // removes the values that were not used for a whole frame, so that the scenes rendered after
+    // this one still find theirs
+    pub(crate) fn evict(&mut self) -> Vec<V> {
+        let frame = self.frame;
+        let (unused, kept): (Entries<K, V>, Entries<K, V>) = self
+            .entries
+            .drain()
+            .partition(|(_, (used, _))| used + 1 < frame);
+        self.entries = kept;
+
+        unused.into_iter().map(|(_, (_, value))| value).collect()
+    }
+}
+
+impl<K: Eq + Hash, V> Default for RenderCache<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn values_are_evicted_after_a_frame_without_use() {
+        let mut cache = RenderCache::new();
+        cache.set_frame(1);
+        cache.insert("used", 1);
+        cache.insert("unused", 2);
+
+        // the values of the last frame are kept for scenes rendered later in this one
+        cache.set_frame(2);
+        assert!(cache.get_mut("used").is_some());
+        assert!(cache.evict().is_empty());
+
+        cache.set_frame(3);
+        assert!(cache.get_mut("used").is_some());
+        assert_eq!(cache.evict(), vec![2]);
+        assert_eq!(cache.get("used"), Some(&1));
+        assert_eq!(cache.get("unused"), None);
+    }
+
+    #[test]
+    fn looking_up_without_use_does_not_keep_values() {
+        let mut cache = RenderCache::new();
+        cache.set_frame(1);
+        cache.insert(0, "chunk");
+
+        cache.set_frame(3);
+        assert_eq!(cache.get(&0), Some(&"chunk"));
+        assert_eq!(cache.evict(), vec!["chunk"]);
+    }
+}
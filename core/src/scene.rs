@@ -1,34 +1,106 @@
 extern crate legion;
 
 use legion::systems::resource::Resources;
-use legion::systems::schedule::Schedule;
 use legion::world::World;
 
+use crate::stage::Stage;
+use crate::stage::StageError;
+use crate::system_descriptor::Stages;
+use crate::system_descriptor::SystemDescriptor;
+use crate::window::WindowKey;
+
+pub trait SceneHooks: Send + Sync {
+    fn on_enter(&mut self, _world: &mut World, _resources: &mut Resources) {}
+
+    fn on_exit(&mut self, _world: &mut World, _resources: &mut Resources) {}
+
+    // another scene got pushed on top of this one
+    fn on_pause(&mut self, _world: &mut World, _resources: &mut Resources) {}
+
+    // the scene on top of this one got popped
+    fn on_resume(&mut self, _world: &mut World, _resources: &mut Resources) {}
+}
+
 pub(crate) struct Scene {
+    id: usize,
     world: World,
-    systems: Vec<Schedule>,
+    systems: Stages,
+    window: WindowKey,
+    hooks: Option<Box<dyn SceneHooks>>,
 }
 
 impl Scene {
-    pub(crate) fn new(world: World) -> Self {
+    pub(crate) fn new(id: usize, world: World) -> Self {
         Scene {
+            id,
             world,
-            systems: Vec::new(),
+            systems: Stages::new(),
+            window: WindowKey::main(),
+            hooks: None,
         }
     }
 
+    pub(crate) fn id(&self) -> usize {
+        self.id
+    }
+
+    pub(crate) fn window(&self) -> WindowKey {
+        self.window
+    }
+
+    pub(crate) fn set_window(&mut self, window: WindowKey) {
+        self.window = window;
+    }
+
+    pub(crate) fn set_hooks(&mut self, hooks: Box<dyn SceneHooks>) {
+        self.hooks = Some(hooks);
+    }
+
+    pub(crate) fn world(&self) -> &World {
+        &self.world
+    }
+
     pub(crate) fn world_mut(&mut self) -> &mut World {
         &mut self.world
     }
 
-    pub(crate) fn add_system(&mut self, system: Schedule) {
-        self.systems.push(system);
+    pub(crate) fn add_system(&mut self, system: SystemDescriptor) {
+        self.systems.add(system);
     }
 
-    pub(crate) fn execute_systems(&mut self, resources: &mut Resources) {
-        let world = &mut self.world;
-        self.systems
-            .iter_mut()
-            .for_each(|schedule| schedule.execute(world, resources));
+    pub(crate) fn systems(&self) -> &Stages {
+        &self.systems
+    }
+
+    pub(crate) fn sort_systems(&mut self) -> Result<(), StageError> {
+        self.systems.sort()
+    }
+
+    pub(crate) fn execute_systems(&mut self, stage: Stage, resources: &mut Resources) {
+        self.systems.execute(stage, &mut self.world, resources);
+    }
+
+    pub(crate) fn enter(&mut self, resources: &mut Resources) {
+        if let Some(hooks) = &mut self.hooks {
+            hooks.on_enter(&mut self.world, resources);
+        }
+    }
+
+    pub(crate) fn exit(&mut self, resources: &mut Resources) {
+        if let Some(hooks) = &mut self.hooks {
+            hooks.on_exit(&mut self.world, resources);
+        }
+    }
+
+    pub(crate) fn pause(&mut self, resources: &mut Resources) {
+        if let Some(hooks) = &mut self.hooks {
+            hooks.on_pause(&mut self.world, resources);
+        }
+    }
+
+    pub(crate) fn resume(&mut self, resources: &mut Resources) {
+        if let Some(hooks) = &mut self.hooks {
+            hooks.on_resume(&mut self.world, resources);
+        }
     }
 }
@@ -1,20 +1,268 @@
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+pub trait SceneKey: Clone + Eq + Hash + Debug + Send + Sync + 'static {}
+
+impl<T> SceneKey for T where T: Clone + Eq + Hash + Debug + Send + Sync + 'static {}
+
+#[derive(Debug)]
+pub enum SceneError {
+    UnknownScene,
+    LastScene,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TransitionKind {
+    Fade,
+    Wipe,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Transition {
+    kind: TransitionKind,
+    duration: f32,
+}
+
+impl Transition {
+    pub fn fade(duration: f32) -> Self {
+        Self {
+            kind: TransitionKind::Fade,
+            duration,
+        }
+    }
+
+    pub fn wipe(duration: f32) -> Self {
+        Self {
+            kind: TransitionKind::Wipe,
+            duration,
+        }
+    }
+
+    pub fn kind(&self) -> TransitionKind {
+        self.kind
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+}
+
+// the scene is switched halfway through, when the screen is fully covered
+#[derive(Debug)]
+pub struct TransitionState<K: SceneKey> {
+    transition: Transition,
+    target: K,
+    elapsed: f32,
+    switched: bool,
+}
+
+impl<K: SceneKey> TransitionState<K> {
+    pub fn transition(&self) -> Transition {
+        self.transition
+    }
+
+    pub fn target(&self) -> &K {
+        &self.target
+    }
+
+    pub fn progress(&self) -> f32 {
+        if self.transition.duration <= 0.0 {
+            return 1.0;
+        }
+
+        (self.elapsed / self.transition.duration).min(1.0)
+    }
+
+    // 0.0 nothing is covered, 1.0 the whole screen is covered
+    pub fn coverage(&self) -> f32 {
+        1.0 - (2.0 * self.progress() - 1.0).abs()
+    }
+}
+
+#[derive(Debug)]
+enum SceneCommand<K: SceneKey> {
+    Change(K, Option<Transition>),
+    Push(K),
+    Pop,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) enum SceneEvent<K: SceneKey> {
+    Enter(K),
+    Exit(K),
+    Pause(K),
+    Resume(K),
+}
+
+// requested changes are applied by the engine at the beginning of the next frame
+pub(crate) fn update<K: SceneKey>(
+    scene_management: &mut SceneManagement<K>,
+    delta_time: f32,
+) -> Vec<SceneEvent<K>> {
+    let mut events = Vec::new();
+
+    if let Some(transition) = &mut scene_management.transition {
+        transition.elapsed += delta_time;
+
+        if !transition.switched && transition.progress() >= 0.5 {
+            transition.switched = true;
+            let target = transition.target.clone();
+            replace_top(&mut scene_management.stack, target, &mut events);
+        }
+
+        if transition.progress() < 1.0 {
+            return events;
+        }
+
+        scene_management.transition = None;
+    }
+
+    while let Some(command) = scene_management.commands.pop_front() {
+        match command {
+            SceneCommand::Change(scene, Some(transition)) if transition.duration() > 0.0 => {
+                scene_management.transition = Some(TransitionState {
+                    transition,
+                    target: scene,
+                    elapsed: 0.0,
+                    switched: false,
+                });
+                break;
+            }
+            SceneCommand::Change(scene, _) => {
+                replace_top(&mut scene_management.stack, scene, &mut events);
+            }
+            SceneCommand::Push(scene) => {
+                if let Some(top) = scene_management.stack.last() {
+                    events.push(SceneEvent::Pause(top.clone()));
+                }
+                events.push(SceneEvent::Enter(scene.clone()));
+                scene_management.stack.push(scene);
+            }
+            SceneCommand::Pop => {
+                if scene_management.stack.len() > 1 {
+                    if let Some(top) = scene_management.stack.pop() {
+                        events.push(SceneEvent::Exit(top));
+                    }
+                    if let Some(top) = scene_management.stack.last() {
+                        events.push(SceneEvent::Resume(top.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    events
+}
+
+pub(crate) fn start<K: SceneKey>(scene_management: &SceneManagement<K>) -> Vec<SceneEvent<K>> {
+    scene_management
+        .stack
+        .iter()
+        .map(|scene| SceneEvent::Enter(scene.clone()))
+        .collect()
+}
+
+fn replace_top<K: SceneKey>(stack: &mut Vec<K>, scene: K, events: &mut Vec<SceneEvent<K>>) {
+    if let Some(top) = stack.pop() {
+        events.push(SceneEvent::Exit(top));
+    }
+
+    events.push(SceneEvent::Enter(scene.clone()));
+    stack.push(scene);
+}
+
 #[derive(Debug)]
-pub struct SceneManagement {
-    current_scene: i16,
+pub struct SceneManagement<K: SceneKey> {
+    scenes: HashSet<K>,
+    stack: Vec<K>,
+    depth: usize,
+    commands: VecDeque<SceneCommand<K>>,
+    transition: Option<TransitionState<K>>,
 }
 
-impl SceneManagement {
+impl<K: SceneKey> SceneManagement<K> {
     pub(crate) fn new() -> Self {
-        Self { current_scene: -1 }
+        Self {
+            scenes: HashSet::new(),
+            stack: Vec::new(),
+            depth: 0,
+            commands: VecDeque::new(),
+            transition: None,
+        }
+    }
+
+    pub(crate) fn register(&mut self, scene: K) {
+        if self.stack.is_empty() {
+            self.stack.push(scene.clone());
+            self.depth = 1;
+        }
+
+        self.scenes.insert(scene);
+    }
+
+    // replaces the initial scene before the engine runs, no hooks are called
+    pub(crate) fn set_initial(&mut self, scene: K) -> Result<(), SceneError> {
+        if !self.scenes.contains(&scene) {
+            return Err(SceneError::UnknownScene);
+        }
+
+        self.stack = vec![scene];
+        self.depth = 1;
+        Ok(())
     }
 }
 
-impl SceneManagement {
-    pub fn change(&mut self, scene: u8) {
-        self.current_scene = scene as i16;
+impl<K: SceneKey> SceneManagement<K> {
+    pub fn change(&mut self, scene: K) -> Result<(), SceneError> {
+        self.request(SceneCommand::Change(scene, None))
+    }
+
+    pub fn change_with(&mut self, scene: K, transition: Transition) -> Result<(), SceneError> {
+        self.request(SceneCommand::Change(scene, Some(transition)))
+    }
+
+    // the pushed scene gets updated, the scenes below are paused but still rendered
+    pub fn push(&mut self, scene: K) -> Result<(), SceneError> {
+        self.request(SceneCommand::Push(scene))
+    }
+
+    pub fn pop(&mut self) -> Result<(), SceneError> {
+        self.request(SceneCommand::Pop)
     }
 
-    pub fn current(&self) -> i16 {
-        self.current_scene
+    pub fn current(&self) -> Option<&K> {
+        self.stack.last()
+    }
+
+    // from the bottom to the top
+    pub fn stack(&self) -> &[K] {
+        &self.stack
+    }
+
+    pub fn transition(&self) -> Option<&TransitionState<K>> {
+        self.transition.as_ref()
+    }
+
+    pub fn contains(&self, scene: &K) -> bool {
+        self.scenes.contains(scene)
+    }
+
+    fn request(&mut self, command: SceneCommand<K>) -> Result<(), SceneError> {
+        match &command {
+            SceneCommand::Change(scene, _) | SceneCommand::Push(scene)
+                if !self.scenes.contains(scene) =>
+            {
+                return Err(SceneError::UnknownScene);
+            }
+            SceneCommand::Push(_) => self.depth += 1,
+            SceneCommand::Pop if self.depth <= 1 => return Err(SceneError::LastScene),
+            SceneCommand::Pop => self.depth -= 1,
+            SceneCommand::Change(_, _) => (),
+        }
+
+        self.commands.push_back(command);
+        Ok(())
     }
 }
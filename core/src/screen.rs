@@ -0,0 +1,94 @@
+use crate::scene_management::TransitionKind;
+use crate::window::WindowKey;
+
+// windows are cleared with it before every frame
+pub(crate) const CLEAR_COLOR: [f32; 4] = [0.0, 0.0, 1.0, 1.0];
+// of the orthographic projections, in world units in front of and behind the camera
+pub(crate) const DEPTH_RANGE: f32 = 1000.0;
+
+// the size in pixels of the window that is rendered at the moment
+pub struct Screen {
+    width: u32,
+    height: u32,
+    transition: Option<(TransitionKind, f32)>,
+    frame: u64,
+    scene: usize,
+    window: WindowKey,
+    // of the scenes rendered into the window this frame
+    first_scene: bool,
+    last_scene: bool,
+}
+
+impl Screen {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            transition: None,
+            frame: 0,
+            scene: 0,
+            window: WindowKey::main(),
+            first_scene: true,
+            last_scene: true,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // the transition drawn over the scene that is rendered, with how much of the screen it covers
+    pub fn transition(&self) -> Option<(TransitionKind, f32)> {
+        self.transition
+    }
+
+    // counted up once every window got rendered
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    // tells the rendered scenes apart, e.g. to cache data of each of them
+    pub fn scene(&self) -> usize {
+        self.scene
+    }
+
+    pub fn window(&self) -> WindowKey {
+        self.window
+    }
+
+    // e.g. to draw what covers the whole window only once
+    pub fn is_first_scene(&self) -> bool {
+        self.first_scene
+    }
+
+    pub fn is_last_scene(&self) -> bool {
+        self.last_scene
+    }
+}
+
+pub(crate) fn resize(screen: &mut Screen, width: u32, height: u32) {
+    screen.width = width;
+    screen.height = height;
+}
+
+pub(crate) fn set_transition(screen: &mut Screen, transition: Option<(TransitionKind, f32)>) {
+    screen.transition = transition;
+}
+
+pub(crate) fn set_scene(screen: &mut Screen, scene: usize, first: bool, last: bool) {
+    screen.scene = scene;
+    screen.first_scene = first;
+    screen.last_scene = last;
+}
+
+pub(crate) fn set_window(screen: &mut Screen, window: WindowKey) {
+    screen.window = window;
+}
+
+pub(crate) fn next_frame(screen: &mut Screen) {
+    screen.frame += 1;
+}
@@ -0,0 +1,106 @@
+extern crate image;
+
+use image::RgbaImage;
+
+use crate::window::WindowKey;
+
+// sent once the window got rendered after a screenshot of it was requested
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScreenshotTaken {
+    window: WindowKey,
+    image: RgbaImage,
+}
+
+impl ScreenshotTaken {
+    pub(crate) fn new(window: WindowKey, image: RgbaImage) -> Self {
+        Self { window, image }
+    }
+
+    pub fn window(&self) -> WindowKey {
+        self.window
+    }
+
+    // the first row is the top of the window
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+}
+
+// sent once a camera rendered into its target after a capture of it was requested
+#[derive(Clone, PartialEq, Debug)]
+pub struct CameraCaptured {
+    target: String,
+    image: RgbaImage,
+}
+
+impl CameraCaptured {
+    pub(crate) fn new(target: &str, image: RgbaImage) -> Self {
+        Self {
+            target: target.to_owned(),
+            image,
+        }
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    // the first row is the top of what the camera sees
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+}
+
+// windows and camera targets are read back after the next frame got rendered into them, headless
+// engines render nothing so their requests stay pending
+pub struct Screenshots {
+    requested: Vec<WindowKey>,
+    // by the name of the target
+    cameras: Vec<String>,
+}
+
+impl Screenshots {
+    pub(crate) fn new() -> Self {
+        Self {
+            requested: Vec::new(),
+            cameras: Vec::new(),
+        }
+    }
+
+    pub fn request(&mut self, window: WindowKey) {
+        if !self.requested.contains(&window) {
+            self.requested.push(window);
+        }
+    }
+
+    pub fn is_requested(&self, window: WindowKey) -> bool {
+        self.requested.contains(&window)
+    }
+
+    pub fn request_camera(&mut self, target: &str) {
+        if !self.is_camera_requested(target) {
+            self.cameras.push(target.to_owned());
+        }
+    }
+
+    pub fn is_camera_requested(&self, target: &str) -> bool {
+        self.cameras.iter().any(|requested| requested == target)
+    }
+}
+
+// whether the window was requested, the request is removed
+pub(crate) fn take(screenshots: &mut Screenshots, window: WindowKey) -> bool {
+    let count = screenshots.requested.len();
+    screenshots
+        .requested
+        .retain(|requested| *requested != window);
+
+    screenshots.requested.len() != count
+}
+
+pub(crate) fn take_camera(screenshots: &mut Screenshots, target: &str) -> bool {
+    let count = screenshots.cameras.len();
+    screenshots.cameras.retain(|requested| requested != target);
+
+    screenshots.cameras.len() != count
+}
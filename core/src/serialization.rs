@@ -0,0 +1,431 @@
+extern crate legion;
+extern crate ron;
+extern crate serde;
+extern crate serde_json;
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+
+use legion::entity::Entity;
+use legion::storage::Component;
+use legion::storage::Tag;
+use legion::world::World;
+use ron::ser::PrettyConfig;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::component::Parent;
+
+#[derive(Debug)]
+pub enum SerializationError {
+    OpeningFileFailed,
+    ReadingFileFailed,
+    WritingFileFailed,
+    ParsingFailed(String),
+    SerializingFailed(String),
+    UnknownComponent(String),
+    MissingComponent,
+    AddingComponentFailed(String),
+    SceneNotFound,
+    RegistryNotFound,
+    PrefabNotFound(String),
+    // a field is overridden on a component the prefab does not have
+    OverriddenComponentNotFound(String),
+}
+
+type SerializeFn = fn(&World, Entity) -> Option<Result<Value, SerializationError>>;
+type SpawnFn = fn(&mut World, Value) -> Result<Entity, SerializationError>;
+type AddFn = fn(&mut World, Entity, Value) -> Result<(), SerializationError>;
+
+struct ComponentEntry {
+    name: String,
+    serialize: SerializeFn,
+    spawn: SpawnFn,
+    add: AddFn,
+}
+
+// keeps an entity without components alive until its tags are added
+struct Placeholder;
+
+struct TagEntry {
+    name: String,
+    serialize: SerializeFn,
+    add: AddFn,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Debug)]
+pub(crate) struct EntityData {
+    #[serde(default)]
+    pub(crate) tags: BTreeMap<String, Value>,
+    #[serde(default)]
+    pub(crate) components: BTreeMap<String, Value>,
+    // the index of the parent entity in the scene file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) parent: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+struct SceneData {
+    entities: Vec<EntityData>,
+}
+
+// component values are kept as json values, they map one to one to both file formats
+pub(crate) fn to_value<T: Serialize>(value: &T) -> Result<Value, SerializationError> {
+    serde_json::to_value(value)
+        .map_err(|error| SerializationError::SerializingFailed(error.to_string()))
+}
+
+pub(crate) fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, SerializationError> {
+    serde_json::from_value(value)
+        .map_err(|error| SerializationError::ParsingFailed(error.to_string()))
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) enum Format {
+    Ron,
+    Json,
+}
+
+impl Format {
+    // everything but .json is read and written as ron
+    pub(crate) fn from_path(path: &str) -> Self {
+        match Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some("json") => Format::Json,
+            _ => Format::Ron,
+        }
+    }
+}
+
+pub(crate) fn to_string<T: Serialize>(
+    value: &T,
+    format: Format,
+) -> Result<String, SerializationError> {
+    match format {
+        Format::Ron => ron::ser::to_string_pretty(value, PrettyConfig::new())
+            .map_err(|error| SerializationError::SerializingFailed(error.to_string())),
+        Format::Json => serde_json::to_string_pretty(value)
+            .map_err(|error| SerializationError::SerializingFailed(error.to_string())),
+    }
+}
+
+pub(crate) fn from_str<T: DeserializeOwned>(
+    text: &str,
+    format: Format,
+) -> Result<T, SerializationError> {
+    match format {
+        Format::Ron => ron::from_str(text)
+            .map_err(|error| SerializationError::ParsingFailed(error.to_string())),
+        Format::Json => serde_json::from_str(text)
+            .map_err(|error| SerializationError::ParsingFailed(error.to_string())),
+    }
+}
+
+pub(crate) fn read_file(path: &str) -> Result<String, SerializationError> {
+    let mut file = File::open(path).map_err(|_| SerializationError::OpeningFileFailed)?;
+    let mut buffer = String::new();
+    file.read_to_string(&mut buffer)
+        .map_err(|_| SerializationError::ReadingFileFailed)?;
+
+    Ok(buffer)
+}
+
+fn serialize_component<T>(
+    world: &World,
+    entity: Entity,
+) -> Option<Result<Value, SerializationError>>
+where
+    T: Component + Serialize,
+{
+    world
+        .get_component::<T>(entity)
+        .map(|component| to_value(&*component))
+}
+
+fn spawn_component<T>(world: &mut World, value: Value) -> Result<Entity, SerializationError>
+where
+    T: Component + DeserializeOwned,
+{
+    let component: T = from_value(value)?;
+
+    Ok(world.insert((), vec![(component,)])[0])
+}
+
+fn add_component<T>(
+    world: &mut World,
+    entity: Entity,
+    value: Value,
+) -> Result<(), SerializationError>
+where
+    T: Component + DeserializeOwned,
+{
+    let component: T = from_value(value)?;
+
+    world
+        .add_component(entity, component)
+        .map_err(|error| SerializationError::AddingComponentFailed(format!("{:?}", error)))
+}
+
+fn serialize_tag<T>(world: &World, entity: Entity) -> Option<Result<Value, SerializationError>>
+where
+    T: Tag + Serialize,
+{
+    world.get_tag::<T>(entity).map(|tag| to_value(tag))
+}
+
+fn add_tag<T>(world: &mut World, entity: Entity, value: Value) -> Result<(), SerializationError>
+where
+    T: Tag + DeserializeOwned,
+{
+    let tag: T = from_value(value)?;
+
+    world
+        .add_tag(entity, tag)
+        .map_err(|error| SerializationError::AddingComponentFailed(format!("{:?}", error)))
+}
+
+// maps the names used in scene files to the registered component and tag types
+pub struct ComponentRegistry {
+    components: Vec<ComponentEntry>,
+    tags: Vec<TagEntry>,
+}
+
+impl ComponentRegistry {
+    pub(crate) fn new() -> Self {
+        Self {
+            components: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    pub(crate) fn register_component<T>(&mut self, name: &str)
+    where
+        T: Component + Serialize + DeserializeOwned,
+    {
+        self.components.retain(|entry| entry.name != name);
+        self.components.push(ComponentEntry {
+            name: name.to_owned(),
+            serialize: serialize_component::<T>,
+            spawn: spawn_component::<T>,
+            add: add_component::<T>,
+        });
+    }
+
+    pub(crate) fn register_tag<T>(&mut self, name: &str)
+    where
+        T: Tag + Serialize + DeserializeOwned,
+    {
+        self.tags.retain(|entry| entry.name != name);
+        self.tags.push(TagEntry {
+            name: name.to_owned(),
+            serialize: serialize_tag::<T>,
+            add: add_tag::<T>,
+        });
+    }
+
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.components.iter().any(|entry| entry.name == name)
+            || self.tags.iter().any(|entry| entry.name == name)
+    }
+
+    pub(crate) fn serialize_entity(
+        &self,
+        world: &World,
+        entity: Entity,
+    ) -> Result<EntityData, SerializationError> {
+        let mut data = EntityData::default();
+
+        for entry in &self.components {
+            if let Some(value) = (entry.serialize)(world, entity) {
+                data.components.insert(entry.name.clone(), value?);
+            }
+        }
+
+        for entry in &self.tags {
+            if let Some(value) = (entry.serialize)(world, entity) {
+                data.tags.insert(entry.name.clone(), value?);
+            }
+        }
+
+        Ok(data)
+    }
+
+    // tag only entities are spawned with a placeholder component that is removed right away,
+    // nothing is left in the world when a component or tag fails to be added
+    pub(crate) fn spawn_entity(
+        &self,
+        world: &mut World,
+        mut data: EntityData,
+    ) -> Result<Entity, SerializationError> {
+        let entity = match data.components.keys().next().cloned() {
+            Some(name) => {
+                let value = data
+                    .components
+                    .remove(&name)
+                    .ok_or(SerializationError::MissingComponent)?;
+                (self.component_entry(&name)?.spawn)(world, value)?
+            }
+            None if !data.tags.is_empty() => {
+                let entity = world.insert((), vec![(Placeholder,)])[0];
+                world
+                    .remove_component::<Placeholder>(entity)
+                    .map_err(|error| {
+                        SerializationError::AddingComponentFailed(format!("{:?}", error))
+                    })?;
+                entity
+            }
+            None => return Err(SerializationError::MissingComponent),
+        };
+
+        if let Err(error) = self.add_to_entity(world, entity, data) {
+            world.delete(entity);
+            return Err(error);
+        }
+
+        Ok(entity)
+    }
+
+    pub(crate) fn add_to_entity(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        data: EntityData,
+    ) -> Result<(), SerializationError> {
+        for (name, value) in data.components {
+            (self.component_entry(&name)?.add)(world, entity, value)?;
+        }
+
+        for (name, value) in data.tags {
+            (self.tag_entry(&name)?.add)(world, entity, value)?;
+        }
+
+        Ok(())
+    }
+
+    fn component_entry(&self, name: &str) -> Result<&ComponentEntry, SerializationError> {
+        self.components
+            .iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| SerializationError::UnknownComponent(name.to_owned()))
+    }
+
+    fn tag_entry(&self, name: &str) -> Result<&TagEntry, SerializationError> {
+        self.tags
+            .iter()
+            .find(|entry| entry.name == name)
+            .ok_or_else(|| SerializationError::UnknownComponent(name.to_owned()))
+    }
+
+    pub(crate) fn serialize_world(
+        &self,
+        world: &World,
+        format: Format,
+    ) -> Result<String, SerializationError> {
+        let mut scene = SceneData::default();
+        let mut saved = Vec::new();
+
+        for entity in world.iter_entities() {
+            let data = self.serialize_entity(world, entity)?;
+
+            if !data.components.is_empty() || !data.tags.is_empty() {
+                scene.entities.push(data);
+                saved.push(entity);
+            }
+        }
+
+        // parents are written as their index, the link is lost when the parent is not saved
+        let indices: HashMap<Entity, usize> = saved
+            .iter()
+            .enumerate()
+            .map(|(index, entity)| (*entity, index))
+            .collect();
+        for (data, entity) in scene.entities.iter_mut().zip(&saved) {
+            data.parent = world
+                .get_component::<Parent>(*entity)
+                .and_then(|parent| indices.get(&parent.entity()).copied());
+        }
+
+        to_string(&scene, format)
+    }
+
+    // a scene loads completely or not at all
+    pub(crate) fn deserialize_world(
+        &self,
+        world: &mut World,
+        text: &str,
+        format: Format,
+    ) -> Result<Vec<Entity>, SerializationError> {
+        let scene: SceneData = from_str(text, format)?;
+        let mut entities = Vec::new();
+
+        match self.spawn_scene(world, scene, &mut entities) {
+            Ok(()) => Ok(entities),
+            Err(error) => {
+                for entity in entities {
+                    world.delete(entity);
+                }
+                Err(error)
+            }
+        }
+    }
+
+    fn spawn_scene(
+        &self,
+        world: &mut World,
+        scene: SceneData,
+        entities: &mut Vec<Entity>,
+    ) -> Result<(), SerializationError> {
+        let parents: Vec<Option<usize>> = scene.entities.iter().map(|data| data.parent).collect();
+
+        for data in scene.entities {
+            entities.push(self.spawn_entity(world, data)?);
+        }
+
+        for (entity, parent) in entities.iter().zip(parents) {
+            let parent = match parent {
+                Some(index) => *entities.get(index).ok_or_else(|| {
+                    SerializationError::ParsingFailed(format!("no entity {} to parent", index))
+                })?,
+                None => continue,
+            };
+
+            world
+                .add_component(*entity, Parent::new(parent))
+                .map_err(|error| {
+                    SerializationError::AddingComponentFailed(format!("{:?}", error))
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn save_world(
+    registry: &ComponentRegistry,
+    world: &World,
+    path: &str,
+) -> Result<(), SerializationError> {
+    let text = registry.serialize_world(world, Format::from_path(path))?;
+    let mut file = File::create(path).map_err(|_| SerializationError::OpeningFileFailed)?;
+
+    file.write_all(text.as_bytes())
+        .map_err(|_| SerializationError::WritingFileFailed)
+}
+
+pub(crate) fn load_world(
+    registry: &ComponentRegistry,
+    world: &mut World,
+    path: &str,
+) -> Result<Vec<Entity>, SerializationError> {
+    let text = read_file(path)?;
+
+    registry.deserialize_world(world, &text, Format::from_path(path))
+}
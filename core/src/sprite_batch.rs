@@ -0,0 +1,368 @@
+extern crate graphic;
+extern crate math;
+
+use std::cmp::Ordering;
+
+use graphic::region::Region;
+use math::Vec2;
+use math::Vec4;
+
+use crate::component::Sprite;
+use crate::component::TileLayer;
+use crate::component::Tilemap;
+use crate::component::Transform;
+use crate::scene_management::TransitionKind;
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub(crate) struct Vertex {
+    position: [f32; 3],
+    uv: [f32; 2],
+    color: [f32; 4],
+}
+
+impl Vertex {
+    pub(crate) fn new(position: [f32; 3], uv: [f32; 2], color: [f32; 4]) -> Self {
+        Self {
+            position,
+            uv,
+            color,
+        }
+    }
+}
+
+// corners in the order bottom left, bottom right, top right, top left
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct Quad {
+    texture: usize,
+    layer: i32,
+    order: i32,
+    vertices: [Vertex; 4],
+}
+
+impl Quad {
+    pub(crate) fn new(texture: usize, layer: i32, order: i32, vertices: [Vertex; 4]) -> Self {
+        Self {
+            texture,
+            layer,
+            order,
+            vertices,
+        }
+    }
+
+    // sprites further away, i.e. with a lower z, are drawn first within the same order
+    fn depth(&self) -> f32 {
+        self.vertices[0].position[2]
+    }
+}
+
+// a range of quads sharing the same texture and layer
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub(crate) struct Batch {
+    texture: usize,
+    layer: i32,
+    first: usize,
+    count: usize,
+}
+
+impl Batch {
+    pub(crate) fn texture(&self) -> usize {
+        self.texture
+    }
+
+    pub(crate) fn layer(&self) -> i32 {
+        self.layer
+    }
+
+    pub(crate) fn first(&self) -> usize {
+        self.first
+    }
+
+    pub(crate) fn count(&self) -> usize {
+        self.count
+    }
+}
+
+pub(crate) struct SpriteBatch {
+    quads: Vec<Quad>,
+}
+
+impl SpriteBatch {
+    pub(crate) fn new() -> Self {
+        Self { quads: Vec::new() }
+    }
+
+    pub(crate) fn push(&mut self, quad: Quad) {
+        self.quads.push(quad);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.quads.is_empty()
+    }
+
+    // sorts into drawing order and merges neighbours with the same texture, empties the batch
+    pub(crate) fn build(&mut self) -> (Vec<Vertex>, Vec<Batch>) {
+        self.quads.sort_by(|lhs, rhs| {
+            lhs.layer
+                .cmp(&rhs.layer)
+                .then(lhs.order.cmp(&rhs.order))
+                .then(
+                    lhs.depth()
+                        .partial_cmp(&rhs.depth())
+                        .unwrap_or(Ordering::Equal),
+                )
+        });
+
+        let mut vertices = Vec::with_capacity(self.quads.len() * 4);
+        let mut batches: Vec<Batch> = Vec::new();
+
+        for (index, quad) in self.quads.drain(..).enumerate() {
+            vertices.extend_from_slice(&quad.vertices);
+
+            match batches.last_mut() {
+                Some(batch) if batch.texture == quad.texture && batch.layer == quad.layer => {
+                    batch.count += 1
+                }
+                _ => batches.push(Batch {
+                    texture: quad.texture,
+                    layer: quad.layer,
+                    first: index,
+                    count: 1,
+                }),
+            }
+        }
+
+        (vertices, batches)
+    }
+}
+
+// two triangles per quad
+pub(crate) fn quad_indices(quads: usize) -> Vec<u32> {
+    (0..quads as u32)
+        .flat_map(|quad| {
+            let first = quad * 4;
+            vec![first, first + 1, first + 2, first + 2, first + 3, first]
+        })
+        .collect()
+}
+
+// the region is scaled by the transform, its rotation x is the angle around the z axis in radians,
+// regions of flipped textures are counted from the top like in images
+pub(crate) fn sprite_vertices(
+    transform: &Transform,
+    sprite: &Sprite,
+    region: Region,
+    pivot: Vec2,
+    flipped: bool,
+    texture_width: u32,
+    texture_height: u32,
+) -> [Vertex; 4] {
+    let width = region.width() as f32;
+    let height = region.height() as f32;
+
+    let uvs = if flipped {
+        let bottom = texture_height.saturating_sub(region.y() + region.height());
+        corner_uvs(
+            Region::new(region.x(), bottom, region.width(), region.height()),
+            sprite.flip_x(),
+            !sprite.flip_y(),
+            texture_width,
+            texture_height,
+        )
+    } else {
+        corner_uvs(
+            region,
+            sprite.flip_x(),
+            sprite.flip_y(),
+            texture_width,
+            texture_height,
+        )
+    };
+
+    quad_vertices(
+        transform,
+        Vec2::new(-pivot.x * width, -pivot.y * height),
+        Vec2::new(width, height),
+        uvs,
+        sprite.color(),
+    )
+}
+
+// the corner and size of the glyph are relative to the transform's position before scaling
+pub(crate) fn glyph_vertices(
+    transform: &Transform,
+    corner: Vec2,
+    size: Vec2,
+    region: Region,
+    color: Vec4,
+    texture_width: u32,
+    texture_height: u32,
+) -> [Vertex; 4] {
+    let uvs = corner_uvs(region, false, false, texture_width, texture_height);
+
+    quad_vertices(transform, corner, size, uvs, color)
+}
+
+// a line as a rectangle of the thickness around it, drawn with a white texture
+pub(crate) fn line_vertices(from: Vec2, to: Vec2, thickness: f32, color: Vec4) -> [Vertex; 4] {
+    let (x, y) = (to.x - from.x, to.y - from.y);
+    let length = (x * x + y * y).sqrt().max(f32::EPSILON);
+    let normal = Vec2::new(-y / length * thickness / 2.0, x / length * thickness / 2.0);
+
+    let color = color_array(color);
+    let corners = [
+        [from.x - normal.x, from.y - normal.y, 0.0],
+        [to.x - normal.x, to.y - normal.y, 0.0],
+        [to.x + normal.x, to.y + normal.y, 0.0],
+        [from.x + normal.x, from.y + normal.y, 0.0],
+    ];
+
+    let mut vertices = [Vertex::default(); 4];
+    for (index, corner) in corners.iter().enumerate() {
+        vertices[index] = Vertex::new(*corner, [0.5, 0.5], color);
+    }
+
+    vertices
+}
+
+// a fade darkens the whole screen, a wipe covers it from the left without transparency
+pub(crate) fn transition_vertices(
+    kind: TransitionKind,
+    coverage: f32,
+    width: u32,
+    height: u32,
+) -> [Vertex; 4] {
+    let (width, height) = (width as f32, height as f32);
+    let (right, alpha) = match kind {
+        TransitionKind::Fade => (width, coverage),
+        TransitionKind::Wipe => (width * coverage, 1.0),
+    };
+
+    let color = [0.0, 0.0, 0.0, alpha];
+    let corners = [
+        [0.0, 0.0, 0.0],
+        [right, 0.0, 0.0],
+        [right, height, 0.0],
+        [0.0, height, 0.0],
+    ];
+
+    let mut vertices = [Vertex::default(); 4];
+    for (index, corner) in corners.iter().enumerate() {
+        vertices[index] = Vertex::new(*corner, [0.5, 0.5], color);
+    }
+
+    vertices
+}
+
+// the tiles of a chunk relative to the tilemap, the image is the tileset's area of the texture
+pub(crate) fn tile_vertices(
+    tilemap: &Tilemap,
+    layer: &TileLayer,
+    chunk_x: u32,
+    chunk_y: u32,
+    image: Region,
+    texture_width: u32,
+    texture_height: u32,
+) -> Vec<Vertex> {
+    let tileset = tilemap.tileset();
+    let width = tileset.tile_width() as f32;
+    let height = tileset.tile_height() as f32;
+    let color = [1.0, 1.0, 1.0, layer.opacity()];
+
+    let mut vertices = Vec::new();
+    for (x, y, tile) in layer.chunk_tiles(chunk_x, chunk_y) {
+        let region = match tileset.region(tile.index(), image.width(), image.height()) {
+            Some(region) => region.offset(image.x(), image.y()),
+            None => continue,
+        };
+
+        let left = (x * tilemap.tile_width()) as f32;
+        let bottom = (y * tilemap.tile_height()) as f32;
+        let corners = [
+            [left, bottom, 0.0],
+            [left + width, bottom, 0.0],
+            [left + width, bottom + height, 0.0],
+            [left, bottom + height, 0.0],
+        ];
+
+        let uvs = corner_uvs(
+            region,
+            tile.flip_x(),
+            tile.flip_y(),
+            texture_width,
+            texture_height,
+        );
+        // the corners are counter clockwise, taking the uv of the next one turns the tile clockwise
+        for (index, corner) in corners.iter().enumerate() {
+            let uv = uvs[(index + tile.rotation() as usize) % 4];
+            vertices.push(Vertex::new(*corner, uv, color));
+        }
+    }
+
+    vertices
+}
+
+// scales, rotates and moves the rectangle with the bottom left corner
+fn quad_vertices(
+    transform: &Transform,
+    corner: Vec2,
+    size: Vec2,
+    uvs: [[f32; 2]; 4],
+    color: Vec4,
+) -> [Vertex; 4] {
+    let scale = transform.scale();
+    let left = corner.x * scale.x;
+    let bottom = corner.y * scale.y;
+    let right = left + size.x * scale.x;
+    let top = bottom + size.y * scale.y;
+    let corners = [
+        Vec2::new(left, bottom),
+        Vec2::new(right, bottom),
+        Vec2::new(right, top),
+        Vec2::new(left, top),
+    ];
+
+    let angle = transform.rotation().x;
+    let (sin, cos) = angle.sin_cos();
+    let position = transform.position();
+    let color = color_array(color);
+
+    let mut vertices = [Vertex::default(); 4];
+    for (index, corner) in corners.iter().enumerate() {
+        vertices[index] = Vertex::new(
+            [
+                position.x + corner.x * cos - corner.y * sin,
+                position.y + corner.x * sin + corner.y * cos,
+                position.z,
+            ],
+            uvs[index],
+            color,
+        );
+    }
+
+    vertices
+}
+
+// in the order of the corners of a quad
+fn corner_uvs(
+    region: Region,
+    flip_x: bool,
+    flip_y: bool,
+    texture_width: u32,
+    texture_height: u32,
+) -> [[f32; 2]; 4] {
+    let [mut u0, mut v0, mut u1, mut v1] = region.uv(texture_width, texture_height);
+    if flip_x {
+        std::mem::swap(&mut u0, &mut u1);
+    }
+    if flip_y {
+        std::mem::swap(&mut v0, &mut v1);
+    }
+
+    // images are uploaded top row first, so the top of the region has the lower v
+    [[u0, v1], [u1, v1], [u1, v0], [u0, v0]]
+}
+
+fn color_array(color: Vec4) -> [f32; 4] {
+    [color.x, color.y, color.z, color.w]
+}
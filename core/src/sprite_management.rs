@@ -1,23 +1,70 @@
+extern crate graphic;
 extern crate image;
+extern crate math;
 
 use std::collections::HashMap;
 
+use graphic::api;
+use graphic::atlas::AtlasBuilder;
+use graphic::region::Region;
+use graphic::texture::Texture;
 use image::DynamicImage;
+use image::Rgba;
+use image::RgbaImage;
+use math::Vec2;
+
+// shown for sprites whose image could not be loaded
+const MISSING_COLOR: [u8; 4] = [255, 0, 255, 255];
+// images are packed into atlas pages up to this size, larger ones get a texture of their own
+const ATLAS_SIZE: u32 = 2048;
+const ATLAS_PADDING: u32 = 2;
+const ATLAS_EXTRUSION: u32 = 1;
 
 pub(crate) enum Sprite {
     None,
     Image(DynamicImage),
-    Texture2d(u32)
+    // index into the textures and the area of the sprite inside of it
+    Texture2d(usize, Region),
+}
+
+// a part of another sprite's image, e.g. a frame of a sprite sheet
+struct Frame {
+    image: String,
+    region: Region,
+    pivot: Option<Vec2>,
+}
+
+// where to find a sprite, the regions are in texture coordinates
+pub(crate) struct SpriteAsset {
+    pub(crate) texture: usize,
+    // the whole image the sprite is part of
+    pub(crate) image: Region,
+    pub(crate) region: Region,
+    pub(crate) pivot: Option<Vec2>,
+    // render targets have their bottom row first, unlike uploaded images
+    pub(crate) flipped: bool,
 }
 
 pub(crate) struct SpriteManagement {
     sprites: HashMap<String, Sprite>,
+    frames: HashMap<String, Frame>,
+    textures: Vec<Texture>,
+    // the textures cameras render into, by the name of their sprite
+    render_targets: HashMap<String, usize>,
+    // of removed render targets, reused by the next ones
+    free_textures: Vec<usize>,
+    missing: Option<usize>,
 }
 
 impl SpriteManagement {
     pub(crate) fn new() -> Self {
         Self {
             sprites: HashMap::new(),
+            frames: HashMap::new(),
+            textures: Vec::new(),
+            render_targets: HashMap::new(),
+            free_textures: Vec::new(),
+            missing: None,
         }
     }
 }
@@ -26,11 +73,164 @@ impl SpriteManagement {
     pub(crate) fn add(&mut self, name: &str, path: &str) {
         match image::open(path) {
             Ok(image) => self.sprites.insert(name.to_owned(), Sprite::Image(image)),
-            Err(_) => self.sprites.insert(name.to_owned(), Sprite::None)
+            Err(_) => self.sprites.insert(name.to_owned(), Sprite::None),
+        };
+    }
+
+    // the region is relative to the image of the other sprite
+    pub(crate) fn add_frame(
+        &mut self,
+        name: &str,
+        image: &str,
+        region: Region,
+        pivot: Option<Vec2>,
+    ) {
+        self.frames.insert(
+            name.to_owned(),
+            Frame {
+                image: image.to_owned(),
+                region,
+                pivot,
+            },
+        );
+    }
+
+    // needs a current graphics context, sprites added afterwards are uploaded by the next call
+    pub(crate) fn upload(&mut self) {
+        let mut builder = AtlasBuilder::new(api::max_texture_size().min(ATLAS_SIZE))
+            .with_padding(ATLAS_PADDING)
+            .with_extrusion(ATLAS_EXTRUSION);
+        let mut missing = Vec::new();
+
+        for (name, sprite) in &self.sprites {
+            match sprite {
+                Sprite::Image(image) => builder.add(name, image.to_rgba8()),
+                Sprite::None => missing.push(name.clone()),
+                Sprite::Texture2d(..) => (),
+            }
+        }
+
+        let atlas = builder.build();
+
+        let pages: Vec<Option<usize>> = atlas
+            .pages()
+            .iter()
+            .map(|page| self.upload_texture(page))
+            .collect();
+
+        for (name, region) in atlas.regions() {
+            match pages[region.page()] {
+                Some(texture) => {
+                    self.sprites
+                        .insert(name.to_owned(), Sprite::Texture2d(texture, region.region()));
+                }
+                None => missing.push(name.to_owned()),
+            }
+        }
+
+        for (name, image) in atlas.oversized() {
+            match self.upload_texture(image) {
+                Some(texture) => {
+                    let region = Region::new(0, 0, image.width(), image.height());
+                    self.sprites
+                        .insert(name.to_owned(), Sprite::Texture2d(texture, region));
+                }
+                None => missing.push(name.to_owned()),
+            }
+        }
+
+        for name in missing {
+            if let Some((texture, region)) = self.missing() {
+                self.sprites
+                    .insert(name, Sprite::Texture2d(texture, region));
+            }
+        }
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<SpriteAsset> {
+        if let Some(Sprite::Texture2d(texture, region)) = self.sprites.get(name) {
+            return Some(SpriteAsset {
+                texture: *texture,
+                image: *region,
+                region: *region,
+                pivot: None,
+                flipped: self.render_targets.contains_key(name),
+            });
+        }
+
+        let frame = self.frames.get(name)?;
+        match self.sprites.get(&frame.image) {
+            Some(Sprite::Texture2d(texture, image)) => Some(SpriteAsset {
+                texture: *texture,
+                image: *image,
+                region: frame.region.offset(image.x(), image.y()),
+                pivot: frame.pivot,
+                flipped: self.render_targets.contains_key(&frame.image),
+            }),
+            _ => None,
+        }
+    }
+
+    // for textures created after the sprites got uploaded, e.g. pages of glyphs
+    pub(crate) fn add_texture(&mut self, texture: Texture) -> usize {
+        self.textures.push(texture);
+        self.textures.len() - 1
+    }
+
+    // shows the texture as the sprite with the given name, the texture of an earlier call with the
+    // same name is replaced and deleted
+    pub(crate) fn set_render_target(&mut self, name: &str, texture: Texture) -> usize {
+        let region = Region::new(0, 0, texture.width(), texture.height());
+        let index = match self.render_targets.get(name) {
+            Some(index) => {
+                self.textures[*index].delete();
+                self.textures[*index] = texture;
+                *index
+            }
+            None => match self.free_textures.pop() {
+                Some(index) => {
+                    self.textures[index] = texture;
+                    index
+                }
+                None => self.add_texture(texture),
+            },
         };
+
+        self.render_targets.insert(name.to_owned(), index);
+        self.sprites
+            .insert(name.to_owned(), Sprite::Texture2d(index, region));
+
+        index
     }
 
-    pub(crate) fn sprites_mut(&mut self) -> &mut HashMap<String, Sprite> {
-        &mut self.sprites
+    // the sprite of the target is removed with its texture
+    pub(crate) fn remove_render_target(&mut self, name: &str) {
+        if let Some(index) = self.render_targets.remove(name) {
+            self.textures[index].delete();
+            self.free_textures.push(index);
+            self.sprites.remove(name);
+        }
+    }
+
+    pub(crate) fn texture(&self, index: usize) -> Option<&Texture> {
+        self.textures.get(index)
+    }
+
+    fn upload_texture(&mut self, image: &RgbaImage) -> Option<usize> {
+        let texture = Texture::from_image(image).ok()?;
+        self.textures.push(texture);
+
+        Some(self.textures.len() - 1)
+    }
+
+    fn missing(&mut self) -> Option<(usize, Region)> {
+        if let Some(texture) = self.missing {
+            return Some((texture, Region::new(0, 0, 1, 1)));
+        }
+
+        let texture = self.upload_texture(&RgbaImage::from_pixel(1, 1, Rgba(MISSING_COLOR)))?;
+        self.missing = Some(texture);
+
+        Some((texture, Region::new(0, 0, 1, 1)))
     }
 }
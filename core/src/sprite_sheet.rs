@@ -0,0 +1,404 @@
+extern crate graphic;
+extern crate math;
+extern crate serde;
+extern crate serde_json;
+
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use graphic::region::Region;
+use math::Vec2;
+use serde::de::MapAccess;
+use serde::de::Visitor;
+use serde::Deserialize;
+use serde::Deserializer;
+
+use crate::component::AnimationClip;
+use crate::component::PlaybackMode;
+
+// used when a frame has no duration of its own, texture packer sheets have none
+const DEFAULT_FRAME_DURATION: f32 = 0.1;
+
+#[derive(Debug)]
+pub enum SpriteSheetError {
+    OpeningFileFailed,
+    ReadingFileFailed,
+    ParsingFailed(String),
+    RotatedFrame(String),
+    UnknownTag(String),
+}
+
+#[derive(Deserialize)]
+struct Rect {
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+struct Size {
+    w: u32,
+    h: u32,
+}
+
+#[derive(Deserialize)]
+struct Point {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Deserialize)]
+struct FrameData {
+    #[serde(default)]
+    filename: String,
+    frame: Rect,
+    #[serde(default)]
+    rotated: bool,
+    #[serde(default)]
+    trimmed: bool,
+    // where the trimmed frame lies in the untrimmed sprite
+    #[serde(rename = "spriteSourceSize")]
+    sprite_source_size: Option<Rect>,
+    // the size of the untrimmed sprite
+    #[serde(rename = "sourceSize")]
+    source_size: Option<Size>,
+    // in milliseconds
+    duration: Option<f32>,
+    // relative to the untrimmed sprite with the origin at the top left
+    pivot: Option<Point>,
+}
+
+// the offset of a frame in its untrimmed sprite and the size of the sprite, in pixels
+#[derive(Copy, Clone)]
+struct Trim {
+    trimmed: bool,
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl Trim {
+    fn new(frame: &FrameData) -> Self {
+        let (x, y) = frame
+            .sprite_source_size
+            .as_ref()
+            .map_or((0, 0), |rect| (rect.x, rect.y));
+        let (width, height) = frame
+            .source_size
+            .as_ref()
+            .map_or((frame.frame.w, frame.frame.h), |size| (size.w, size.h));
+
+        Self {
+            trimmed: frame.trimmed,
+            x: x as f32,
+            y: y as f32,
+            width: width as f32,
+            height: height as f32,
+        }
+    }
+
+    // turns a position in the untrimmed sprite into a pivot of the trimmed frame
+    fn pivot(&self, region: Region, x: f32, y: f32) -> Vec2 {
+        let width = region.width().max(1) as f32;
+        let height = region.height().max(1) as f32;
+
+        Vec2::new((x - self.x) / width, 1.0 - (y - self.y) / height)
+    }
+}
+
+// keeps the order of the file, tags refer to frames by index
+struct FrameHash(Vec<(String, FrameData)>);
+
+impl<'de> Deserialize<'de> for FrameHash {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FrameHashVisitor;
+
+        impl<'de> Visitor<'de> for FrameHashVisitor {
+            type Value = FrameHash;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of frames")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<FrameHash, A::Error> {
+                let mut frames = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    frames.push(entry);
+                }
+
+                Ok(FrameHash(frames))
+            }
+        }
+
+        deserializer.deserialize_map(FrameHashVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Frames {
+    Hash(FrameHash),
+    Array(Vec<FrameData>),
+}
+
+#[derive(Deserialize)]
+struct TagData {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: String,
+}
+
+#[derive(Deserialize)]
+struct SliceKey {
+    frame: usize,
+    bounds: Rect,
+    // in pixels relative to the bounds
+    pivot: Option<Point>,
+}
+
+#[derive(Deserialize)]
+struct SliceData {
+    keys: Vec<SliceKey>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetaData {
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    frame_tags: Vec<TagData>,
+    #[serde(default)]
+    slices: Vec<SliceData>,
+}
+
+#[derive(Deserialize)]
+struct SheetData {
+    frames: Frames,
+    meta: MetaData,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SheetFrame {
+    name: String,
+    region: Region,
+    duration: f32,
+    pivot: Option<Vec2>,
+}
+
+impl SheetFrame {
+    // the file name without its extension
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn region(&self) -> Region {
+        self.region
+    }
+
+    // in seconds
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    // relative to the frame with the origin at the bottom left, like the pivot of sprites
+    pub fn pivot(&self) -> Option<Vec2> {
+        self.pivot
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SheetTag {
+    name: String,
+    frames: Vec<usize>,
+    mode: PlaybackMode,
+}
+
+impl SheetTag {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // indices into the frames of the sheet in playing order
+    pub fn frames(&self) -> &[usize] {
+        &self.frames
+    }
+
+    pub fn mode(&self) -> PlaybackMode {
+        self.mode
+    }
+}
+
+// frames and animation tags of a sprite sheet exported by aseprite or texture packer
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpriteSheet {
+    image: String,
+    frames: Vec<SheetFrame>,
+    tags: Vec<SheetTag>,
+}
+
+impl SpriteSheet {
+    // the image path of the sheet is resolved relative to the file
+    pub fn from_file(path: &str) -> Result<Self, SpriteSheetError> {
+        let mut file = File::open(path).map_err(|_| SpriteSheetError::OpeningFileFailed)?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(|_| SpriteSheetError::ReadingFileFailed)?;
+
+        let mut sheet = Self::from_json(&content)?;
+        if let Some(directory) = Path::new(path).parent() {
+            sheet.image = directory.join(&sheet.image).to_string_lossy().into_owned();
+        }
+
+        Ok(sheet)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, SpriteSheetError> {
+        let data: SheetData = serde_json::from_str(json)
+            .map_err(|error| SpriteSheetError::ParsingFailed(error.to_string()))?;
+
+        let frames = match data.frames {
+            Frames::Hash(FrameHash(frames)) => frames
+                .into_iter()
+                .map(|(filename, frame)| FrameData { filename, ..frame })
+                .collect(),
+            Frames::Array(frames) => frames,
+        };
+
+        let mut sheet_frames = Vec::with_capacity(frames.len());
+        let mut trims = Vec::with_capacity(frames.len());
+        for frame in frames {
+            if frame.rotated {
+                return Err(SpriteSheetError::RotatedFrame(frame.filename));
+            }
+
+            let region = Region::new(frame.frame.x, frame.frame.y, frame.frame.w, frame.frame.h);
+            let trim = Trim::new(&frame);
+            sheet_frames.push(SheetFrame {
+                name: strip_extension(&frame.filename),
+                region,
+                duration: frame
+                    .duration
+                    .map_or(DEFAULT_FRAME_DURATION, |duration| duration / 1000.0),
+                pivot: frame
+                    .pivot
+                    .map(|pivot| trim.pivot(region, pivot.x * trim.width, pivot.y * trim.height)),
+            });
+            trims.push(trim);
+        }
+
+        apply_slice_pivots(&mut sheet_frames, &trims, &data.meta.slices);
+
+        // trimmed frames keep the center of the untrimmed sprite in place
+        for (frame, trim) in sheet_frames.iter_mut().zip(&trims) {
+            if frame.pivot.is_none() && trim.trimmed {
+                frame.pivot = Some(trim.pivot(frame.region, trim.width / 2.0, trim.height / 2.0));
+            }
+        }
+
+        let mut tags = Vec::with_capacity(data.meta.frame_tags.len());
+        for tag in data.meta.frame_tags {
+            if tag.from > tag.to || tag.to >= sheet_frames.len() {
+                return Err(SpriteSheetError::UnknownTag(tag.name));
+            }
+
+            let (frames, mode) = match tag.direction.as_str() {
+                "reverse" => ((tag.from..=tag.to).rev().collect(), PlaybackMode::Loop),
+                "pingpong" => ((tag.from..=tag.to).collect(), PlaybackMode::PingPong),
+                "pingpong_reverse" => ((tag.from..=tag.to).rev().collect(), PlaybackMode::PingPong),
+                _ => ((tag.from..=tag.to).collect(), PlaybackMode::Loop),
+            };
+
+            tags.push(SheetTag {
+                name: tag.name,
+                frames,
+                mode,
+            });
+        }
+
+        Ok(Self {
+            image: data.meta.image,
+            frames: sheet_frames,
+            tags,
+        })
+    }
+
+    pub fn image(&self) -> &str {
+        &self.image
+    }
+
+    pub fn frames(&self) -> &[SheetFrame] {
+        &self.frames
+    }
+
+    pub fn tags(&self) -> &[SheetTag] {
+        &self.tags
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&SheetTag> {
+        self.tags.iter().find(|tag| tag.name == name)
+    }
+
+    // the frames of the tag with their own durations, regions are relative to the sheet
+    pub fn clip(&self, tag: &str) -> Option<AnimationClip> {
+        let tag = self.tag(tag)?;
+
+        Some(
+            tag.frames
+                .iter()
+                .map(|index| &self.frames[*index])
+                .fold(AnimationClip::new(tag.mode), |clip, frame| {
+                    clip.with_frame(frame.region, frame.duration)
+                }),
+        )
+    }
+}
+
+fn strip_extension(filename: &str) -> String {
+    Path::new(filename)
+        .with_extension("")
+        .to_string_lossy()
+        .into_owned()
+}
+
+// a slice key's pivot holds from its frame on until the next key, the bounds are given in the
+// untrimmed sprite
+fn apply_slice_pivots(frames: &mut [SheetFrame], trims: &[Trim], slices: &[SliceData]) {
+    let slice = match slices
+        .iter()
+        .find(|slice| slice.keys.iter().any(|key| key.pivot.is_some()))
+    {
+        Some(slice) => slice,
+        None => return,
+    };
+
+    for (index, (frame, trim)) in frames.iter_mut().zip(trims).enumerate() {
+        if frame.pivot.is_some() {
+            continue;
+        }
+
+        let key = slice
+            .keys
+            .iter()
+            .filter(|key| key.frame <= index)
+            .max_by_key(|key| key.frame);
+        if let Some(SliceKey {
+            bounds,
+            pivot: Some(pivot),
+            ..
+        }) = key
+        {
+            frame.pivot = Some(trim.pivot(
+                frame.region,
+                bounds.x as f32 + pivot.x,
+                bounds.y as f32 + pivot.y,
+            ));
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Stage {
+    PreUpdate,
+    FixedUpdate,
+    Update,
+    PostUpdate,
+    PreRender,
+    Render,
+    PostRender,
+}
+
+impl Stage {
+    pub fn all() -> [Stage; 7] {
+        [
+            Stage::PreUpdate,
+            Stage::FixedUpdate,
+            Stage::Update,
+            Stage::PostUpdate,
+            Stage::PreRender,
+            Stage::Render,
+            Stage::PostRender,
+        ]
+    }
+
+    pub(crate) fn update_stages() -> [Stage; 4] {
+        [
+            Stage::PreUpdate,
+            Stage::FixedUpdate,
+            Stage::Update,
+            Stage::PostUpdate,
+        ]
+    }
+
+    pub(crate) fn render_stages() -> [Stage; 3] {
+        [Stage::PreRender, Stage::Render, Stage::PostRender]
+    }
+}
+
+#[derive(Debug)]
+pub enum StageError {
+    CyclicOrdering(Vec<String>),
+    // the system and the name it is ordered against
+    UnknownSystem(String, String),
+    // the other system runs in another stage, or is global while this one belongs to a scene or
+    // the other way around, so the order can not be kept
+    OtherStage(String, String),
+}
+
+impl fmt::Display for StageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StageError::CyclicOrdering(systems) => write!(
+                f,
+                "the ordering of the systems {} is cyclic",
+                systems.join(", ")
+            ),
+            StageError::UnknownSystem(system, name) => write!(
+                f,
+                "the system {} is ordered against {} which does not exist",
+                system, name
+            ),
+            StageError::OtherStage(system, name) => write!(
+                f,
+                "the system {} is ordered against {} which runs in another stage or set of systems",
+                system, name
+            ),
+        }
+    }
+}
+
+impl Error for StageError {}
+
+#[derive(Clone, Default, Debug)]
+pub(crate) struct Ordering {
+    pub(crate) name: Option<String>,
+    pub(crate) before: Vec<String>,
+    pub(crate) after: Vec<String>,
+}
+
+fn display_name(ordering: &Ordering) -> String {
+    ordering
+        .name
+        .clone()
+        .unwrap_or_else(|| "<unnamed>".to_owned())
+}
+
+fn indices_of<'a>(orderings: &'a [Ordering], name: &'a str) -> impl Iterator<Item = usize> + 'a {
+    orderings
+        .iter()
+        .enumerate()
+        .filter(move |(_, ordering)| ordering.name.as_deref() == Some(name))
+        .map(|(index, _)| index)
+}
+
+// returns the indices in execution order, systems without constraints keep their insertion order,
+// constraints on names that are not part of the stage are ignored as check_names rejects them
+pub(crate) fn sort(orderings: &[Ordering]) -> Result<Vec<usize>, StageError> {
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); orderings.len()];
+    let mut predecessors = vec![0; orderings.len()];
+
+    for (index, ordering) in orderings.iter().enumerate() {
+        for name in &ordering.after {
+            for other in indices_of(orderings, name) {
+                successors[other].push(index);
+                predecessors[index] += 1;
+            }
+        }
+
+        for name in &ordering.before {
+            for other in indices_of(orderings, name) {
+                successors[index].push(other);
+                predecessors[other] += 1;
+            }
+        }
+    }
+
+    let mut order = Vec::with_capacity(orderings.len());
+    let mut done = vec![false; orderings.len()];
+
+    while let Some(index) =
+        (0..orderings.len()).find(|&index| !done[index] && predecessors[index] == 0)
+    {
+        done[index] = true;
+        order.push(index);
+
+        for &successor in &successors[index] {
+            predecessors[successor] -= 1;
+        }
+    }
+
+    if order.len() < orderings.len() {
+        let systems = orderings
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !done[*index])
+            .map(|(_, ordering)| display_name(ordering))
+            .collect();
+
+        return Err(StageError::CyclicOrdering(systems));
+    }
+
+    Ok(order)
+}
+
+// the orderings of one stage can only reference each other, the names of all systems tell the
+// systems of other stages apart from missing ones
+pub(crate) fn check_names(orderings: &[Ordering], names: &HashSet<&str>) -> Result<(), StageError> {
+    for ordering in orderings {
+        for name in ordering.before.iter().chain(&ordering.after) {
+            if indices_of(orderings, name).next().is_some() {
+                continue;
+            }
+
+            let system = display_name(ordering);
+            if names.contains(name.as_str()) {
+                return Err(StageError::OtherStage(system, name.clone()));
+            }
+            return Err(StageError::UnknownSystem(system, name.clone()));
+        }
+    }
+
+    Ok(())
+}
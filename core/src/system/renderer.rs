@@ -1,10 +1,965 @@
+extern crate graphic;
+extern crate image;
 extern crate legion;
+extern crate math;
 
+use std::mem;
+
+use graphic::api;
+use graphic::api::BlendMode;
+use graphic::api::Rect;
+use graphic::api::RenderState;
+use graphic::api::StateCache;
+use graphic::data_buffer::buffer_element::BufferDataType;
+use graphic::data_buffer::buffer_element::BufferElement;
+use graphic::data_buffer::DataBuffer;
+use graphic::framebuffer::Framebuffer;
+use graphic::index_buffer::IndexBuffer;
+use graphic::post_process::PostPass;
+use graphic::post_process::PostProcessChain;
+use graphic::program::Program;
+use graphic::region::Region;
+use graphic::shader::Shader;
+use graphic::shader::ShaderType;
+use graphic::texture::Texture;
+use graphic::vertex_array::VertexArray;
+use image::Rgba;
+use image::RgbaImage;
+use legion::entity::Entity;
+use legion::query::IntoQuery;
+use legion::query::Read;
+use legion::query::TryRead;
 use legion::systems::schedule::Schedule;
 use legion::systems::SystemBuilder;
+use math::Mat4;
+use math::Vec2;
+use math::Vec3;
+
+use crate::component::Camera;
+use crate::component::Sprite;
+use crate::component::SpriteAnimation;
+use crate::component::Text;
+use crate::component::Tilemap;
+use crate::component::Transform;
+use crate::debug_draw::DebugDraw;
+use crate::debug_draw::DebugShape;
+use crate::debug_draw::DebugSpace;
+use crate::events::Events;
+use crate::font_management::FontManagement;
+use crate::glyph_cache::GlyphSource;
+use crate::post_processing::PostEffect;
+use crate::post_processing::PostProcessing;
+use crate::render_cache::RenderCache;
+use crate::screen;
+use crate::screen::Screen;
+use crate::screenshot;
+use crate::screenshot::CameraCaptured;
+use crate::screenshot::Screenshots;
+use crate::sprite_batch;
+use crate::sprite_batch::Batch;
+use crate::sprite_batch::Quad;
+use crate::sprite_batch::SpriteBatch;
+use crate::sprite_batch::Vertex;
+use crate::sprite_management::SpriteManagement;
+use crate::window::WindowKey;
+
+// larger batches are drawn in several calls
+const MAX_QUADS: usize = 2048;
+
+const VERTEX_SHADER: &str = r#"#version 330 core
+layout (location = 0) in vec3 position;
+layout (location = 1) in vec2 uv;
+layout (location = 2) in vec4 color;
+
+uniform mat4 projection;
+
+out vec2 vertex_uv;
+out vec4 vertex_color;
+
+void main() {
+    gl_Position = projection * vec4(position, 1.0);
+    vertex_uv = uv;
+    vertex_color = color;
+}
+"#;
+
+const FRAGMENT_SHADER: &str = r#"#version 330 core
+in vec2 vertex_uv;
+in vec4 vertex_color;
+
+uniform sampler2D sprite;
+
+out vec4 color;
+
+void main() {
+    color = texture(sprite, vertex_uv) * vertex_color;
+}
+"#;
+
+struct SpriteRenderer {
+    program: Program,
+    vertex_array: VertexArray,
+    vertices: DataBuffer,
+    indices: IndexBuffer,
+    state: StateCache,
+}
+
+impl SpriteRenderer {
+    fn new() -> Option<Self> {
+        let vertex_shader = Shader::from_source(VERTEX_SHADER, ShaderType::VertexShader).ok()?;
+        let fragment_shader =
+            Shader::from_source(FRAGMENT_SHADER, ShaderType::FragmentShader).ok()?;
+        let program = Program::new(vertex_shader, fragment_shader).ok()?;
+
+        let vertex_array = VertexArray::new();
+        vertex_array.bind();
+
+        let mut vertices = DataBuffer::dynamic(MAX_QUADS * 4 * mem::size_of::<Vertex>());
+        configure_vertices(&mut vertices);
+
+        let indices = sprite_batch::quad_indices(MAX_QUADS);
+        let indices = IndexBuffer::new(indices.as_ptr(), indices.len() * mem::size_of::<u32>());
+
+        vertex_array.unbind();
+
+        Some(Self {
+            program,
+            vertex_array,
+            vertices,
+            indices,
+            state: StateCache::new(),
+        })
+    }
+
+    fn begin(&mut self) {
+        self.program.bind();
+        self.program.set_int("sprite", 0);
+        self.state
+            .apply(&RenderState::new().with_blend_mode(Some(BlendMode::Alpha)));
+    }
+
+    fn end(&self) {
+        self.vertex_array.unbind();
+        self.program.unbind();
+    }
+
+    fn draw_batch(
+        &self,
+        projection: &Mat4,
+        sprite_management: &SpriteManagement,
+        vertices: &[Vertex],
+        batch: &Batch,
+    ) {
+        let texture = match sprite_management.texture(batch.texture()) {
+            Some(texture) => texture,
+            None => return,
+        };
+        texture.bind_at_position(0);
+        self.program.set_mat4f("projection", projection);
+        self.vertex_array.bind();
+
+        let end = batch.first() + batch.count();
+        let mut first = batch.first();
+        while first < end {
+            let count = (end - first).min(MAX_QUADS);
+            self.vertices
+                .set_data(&vertices[first * 4..(first + count) * 4]);
+            api::draw_indexed(count * 6, 0);
+            first += count;
+        }
+    }
+
+    fn draw_chunk(
+        &self,
+        projection: &Mat4,
+        sprite_management: &SpriteManagement,
+        chunk: &TileChunk,
+    ) {
+        let (mesh, texture) = match (&chunk.mesh, sprite_management.texture(chunk.texture)) {
+            (Some(mesh), Some(texture)) => (mesh, texture),
+            _ => return,
+        };
+        texture.bind_at_position(0);
+        self.program
+            .set_mat4f("projection", &(*projection * chunk.model));
+        mesh.vertex_array.bind();
+        api::draw_indexed(mesh.quads * 6, 0);
+    }
+
+    // tiles are drawn below the sprites of the same layer, nothing of the skipped texture is drawn
+    #[allow(clippy::too_many_arguments)]
+    fn draw_scene(
+        &self,
+        projection: &Mat4,
+        sprite_management: &SpriteManagement,
+        vertices: &[Vertex],
+        batches: &[Batch],
+        visible_chunks: &[(i32, ChunkKey)],
+        chunks: &RenderCache<ChunkKey, TileChunk>,
+        skipped: Option<usize>,
+    ) {
+        let mut next_chunk = 0;
+        for batch in batches {
+            while next_chunk < visible_chunks.len() && visible_chunks[next_chunk].0 <= batch.layer()
+            {
+                if let Some(chunk) = chunks.get(&visible_chunks[next_chunk].1) {
+                    if skipped != Some(chunk.texture) {
+                        self.draw_chunk(projection, sprite_management, chunk);
+                    }
+                }
+                next_chunk += 1;
+            }
+
+            if skipped != Some(batch.texture()) {
+                self.draw_batch(projection, sprite_management, vertices, batch);
+            }
+        }
+        for chunk in visible_chunks[next_chunk..]
+            .iter()
+            .filter_map(|(_, key)| chunks.get(key))
+        {
+            if skipped != Some(chunk.texture) {
+                self.draw_chunk(projection, sprite_management, chunk);
+            }
+        }
+    }
+}
+
+// the framebuffers a camera renders into, multisampled ones are resolved into the one of the
+// texture
+struct RenderTarget {
+    // the name of the sprite showing it
+    name: String,
+    texture: usize,
+    // none when they could not be created, they are not tried again until the camera changes
+    framebuffer: Option<Framebuffer>,
+    multisampled: Option<Framebuffer>,
+    // the size and samples of the camera
+    settings: (u32, u32, u32),
+}
+
+impl RenderTarget {
+    fn new(camera: &Camera, sprite_management: &mut SpriteManagement) -> Self {
+        let settings = settings(camera);
+        let texture = match Texture::render_target(camera.width(), camera.height()) {
+            Ok(texture) => texture,
+            Err(_) => {
+                return Self {
+                    name: camera.target().to_owned(),
+                    texture: 0,
+                    framebuffer: None,
+                    multisampled: None,
+                    settings,
+                }
+            }
+        };
+
+        let framebuffer = Framebuffer::new(&texture, None).ok();
+        let multisampled = match camera.samples() {
+            0 => None,
+            samples => {
+                Framebuffer::multisampled(camera.width(), camera.height(), samples, false).ok()
+            }
+        };
+
+        // multisampled cameras are not drawn without their samples
+        let framebuffer = match framebuffer {
+            Some(framebuffer) if camera.samples() > 0 && multisampled.is_none() => {
+                framebuffer.delete();
+                None
+            }
+            framebuffer => framebuffer,
+        };
+
+        Self {
+            name: camera.target().to_owned(),
+            texture: sprite_management.set_render_target(camera.target(), texture),
+            framebuffer,
+            multisampled,
+            settings,
+        }
+    }
+
+    fn delete(&self) {
+        self.framebuffer.iter().for_each(Framebuffer::delete);
+        self.multisampled.iter().for_each(Framebuffer::delete);
+    }
+}
+
+fn settings(camera: &Camera) -> (u32, u32, u32) {
+    (camera.width(), camera.height(), camera.samples())
+}
+
+// cameras are told apart by their target, windows of other sizes need chains of their own
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+enum ChainKey {
+    Camera(String),
+    Window(WindowKey),
+}
+
+// the post processing of a camera or the window
+struct PostChain {
+    // none when it could not be created, it is not tried again until the size changes
+    chain: Option<PostProcessChain>,
+    settings: (u32, u32, u32),
+    // the shaders of the effects and the revision of the shaders the passes were compiled from
+    shaders: Vec<String>,
+    revision: u64,
+}
+
+impl PostChain {
+    fn delete(&self) {
+        self.chain.iter().for_each(PostProcessChain::delete);
+    }
+}
+
+// none when no effect is enabled, the passes are compiled again when the effects or their shaders
+// changed and take the enabled state and uniforms of the effects every frame
+fn post_chain<'a>(
+    chains: &'a mut RenderCache<ChainKey, PostChain>,
+    key: ChainKey,
+    settings: (u32, u32, u32),
+    effects: &[PostEffect],
+    post_processing: &PostProcessing,
+) -> Option<&'a PostProcessChain> {
+    if !effects.iter().any(PostEffect::is_enabled) {
+        if let Some(chain) = chains.remove(&key) {
+            chain.delete();
+        }
+        return None;
+    }
+
+    let stale = match chains.get(&key) {
+        Some(chain) => chain.settings != settings,
+        None => true,
+    };
+    if stale {
+        let (width, height, samples) = settings;
+        let chain = PostChain {
+            chain: PostProcessChain::new(width, height, samples).ok(),
+            settings,
+            shaders: Vec::new(),
+            revision: 0,
+        };
+        if let Some(chain) = chains.insert(key.clone(), chain) {
+            chain.delete();
+        }
+    }
+
+    let post_chain = chains.get_mut(&key)?;
+    let chain = post_chain.chain.as_mut()?;
+    let shaders: Vec<String> = effects
+        .iter()
+        .map(|effect| effect.shader().to_owned())
+        .collect();
+    if post_chain.shaders != shaders || post_chain.revision != post_processing.revision() {
+        for shader in &post_chain.shaders {
+            if let Some(pass) = chain.remove_pass(shader) {
+                pass.delete();
+            }
+        }
+        // effects with unknown or broken shaders are left out
+        for shader in &shaders {
+            if let Some(Ok(pass)) = post_processing.shader(shader).map(PostPass::from_source) {
+                chain.add_pass(shader, pass);
+            }
+        }
+        post_chain.shaders = shaders;
+        post_chain.revision = post_processing.revision();
+    }
+
+    for effect in effects {
+        if let Some(pass) = chain.pass_mut(effect.shader()) {
+            pass.set_enabled(effect.is_enabled());
+            for (name, value) in effect.uniforms() {
+                pass.set_uniform(name, *value);
+            }
+        }
+    }
+
+    if chain.is_active() {
+        Some(chain)
+    } else {
+        None
+    }
+}
+
+// the vertices of a chunk of tiles in the space of its tilemap
+struct TileMesh {
+    vertex_array: VertexArray,
+    vertices: DataBuffer,
+    quads: usize,
+}
+
+impl TileMesh {
+    // none for chunks without tiles
+    fn new(renderer: &SpriteRenderer, vertices: &[Vertex]) -> Option<Self> {
+        if vertices.is_empty() {
+            return None;
+        }
 
+        let vertex_array = VertexArray::new();
+        vertex_array.bind();
+
+        let mut buffer = DataBuffer::new(vertices.as_ptr(), mem::size_of_val(vertices));
+        configure_vertices(&mut buffer);
+        // a chunk never has more quads than the shared index buffer
+        renderer.indices.bind();
+
+        vertex_array.unbind();
+
+        Some(Self {
+            vertex_array,
+            vertices: buffer,
+            quads: vertices.len() / 4,
+        })
+    }
+
+    fn delete(&self) {
+        self.vertex_array.delete();
+        self.vertices.delete();
+    }
+}
+
+// scene, tilemap entity, index of the layer and position of the chunk
+type ChunkKey = (usize, Entity, usize, u32, u32);
+
+struct TileChunk {
+    revision: u64,
+    texture: usize,
+    image: Region,
+    mesh: Option<TileMesh>,
+    layer: i32,
+    model: Mat4,
+}
+
+fn configure_vertices(buffer: &mut DataBuffer) {
+    buffer.add_element(BufferElement::new(
+        BufferDataType::Float3,
+        "position",
+        false,
+    ));
+    buffer.add_element(BufferElement::new(BufferDataType::Float2, "uv", false));
+    buffer.add_element(BufferElement::new(BufferDataType::Float4, "color", false));
+    buffer.configure_by_index();
+}
+
+// moves the tiles of a chunk to the tilemap's place in the world
+fn model(transform: &Transform) -> Mat4 {
+    let scale = transform.scale();
+
+    Mat4::from_translate(transform.position())
+        * Mat4::from_rotation_z(transform.rotation().x)
+        * Mat4::from_scale(Vec3::new(scale.x, scale.y, 1.0))
+}
+
+// world units are pixels, the origin is in the center of the screen
+fn projection(screen: &Screen) -> Mat4 {
+    let half_width = screen.width() as f32 / 2.0;
+    let half_height = screen.height() as f32 / 2.0;
+
+    Mat4::ortho(
+        -half_width,
+        half_width,
+        -half_height,
+        half_height,
+        -screen::DEPTH_RANGE,
+        screen::DEPTH_RANGE,
+    )
+}
+
+// pixels from the bottom left corner of the window
+fn screen_projection(screen: &Screen) -> Mat4 {
+    Mat4::ortho(
+        0.0,
+        screen.width() as f32,
+        0.0,
+        screen.height() as f32,
+        -screen::DEPTH_RANGE,
+        screen::DEPTH_RANGE,
+    )
+}
+
+fn white_texture(sprite_management: &mut SpriteManagement) -> Option<usize> {
+    let texture = Texture::from_image(&RgbaImage::from_pixel(1, 1, Rgba([255; 4]))).ok()?;
+    Some(sprite_management.add_texture(texture))
+}
+
+// glyphs of unknown fonts and missing pages are skipped
+fn push_text(
+    sprite_batch: &mut SpriteBatch,
+    font_management: &mut FontManagement,
+    sprite_management: &mut SpriteManagement,
+    text: &Text,
+    transform: &Transform,
+) {
+    let (layout, glyphs) = match font_management.layout(text) {
+        Some(layout) => layout,
+        None => return,
+    };
+    // glyphs are placed relative to the top left corner of the text
+    let origin = Vec2::new(
+        -text.pivot().x * layout.width(),
+        (1.0 - text.pivot().y) * layout.height(),
+    );
+
+    for glyph in glyphs {
+        let (texture, region) = match glyph.source {
+            GlyphSource::Page(page) => match font_management
+                .page(text.font(), page)
+                .and_then(|page| sprite_management.get(page))
+            {
+                Some(asset) => (
+                    asset.texture,
+                    glyph.region.offset(asset.image.x(), asset.image.y()),
+                ),
+                None => continue,
+            },
+            GlyphSource::Cache(page) => match font_management.texture(page, sprite_management) {
+                Some(texture) => (texture, glyph.region),
+                None => continue,
+            },
+        };
+        let (width, height) = match sprite_management.texture(texture) {
+            Some(texture) => (texture.width(), texture.height()),
+            None => continue,
+        };
+
+        sprite_batch.push(Quad::new(
+            texture,
+            text.layer(),
+            text.order(),
+            sprite_batch::glyph_vertices(
+                transform,
+                origin + glyph.position,
+                glyph.size,
+                region,
+                text.color(),
+                width,
+                height,
+            ),
+        ));
+    }
+}
+
+// the gl objects are created on the first frame, when the context is current
 pub fn create() -> Schedule {
-    let system = SystemBuilder::new("RendererSystem").build(|_, _, _, _| ());
+    // none inside when the renderer could not be created, it is not tried again
+    let mut renderer: Option<Option<SpriteRenderer>> = None;
+    let mut sprite_batch = SpriteBatch::new();
+    let mut world_overlay = SpriteBatch::new();
+    let mut screen_overlay = SpriteBatch::new();
+    // the texture lines of debug shapes are drawn with
+    let mut white: Option<Option<usize>> = None;
+    // tiles only get new vertices when their chunk changed
+    let mut chunks: RenderCache<ChunkKey, TileChunk> = RenderCache::new();
+    // by the name of the sprite showing them
+    let mut targets: RenderCache<String, RenderTarget> = RenderCache::new();
+    let mut post_chains: RenderCache<ChainKey, PostChain> = RenderCache::new();
+
+    let system = SystemBuilder::new("RendererSystem")
+        .write_resource::<SpriteManagement>()
+        .write_resource::<FontManagement>()
+        .read_resource::<Screen>()
+        .read_resource::<DebugDraw>()
+        .read_resource::<PostProcessing>()
+        .write_resource::<Screenshots>()
+        .write_resource::<Events<CameraCaptured>>()
+        .with_query(<(Read<Sprite>, Read<Transform>, TryRead<SpriteAnimation>)>::query())
+        .with_query(<(Read<Tilemap>, Read<Transform>)>::query())
+        .with_query(<(Read<Text>, Read<Transform>)>::query())
+        .with_query(<(Read<Camera>, Read<Transform>)>::query())
+        .build(
+            move |_,
+                  mut world,
+                  (
+                sprite_management,
+                font_management,
+                screen,
+                debug_draw,
+                post_processing,
+                screenshots,
+                captured,
+            ),
+                  (sprites, tilemaps, texts, cameras)| {
+                let renderer = match renderer.get_or_insert_with(SpriteRenderer::new) {
+                    Some(renderer) => renderer,
+                    None => return,
+                };
+                chunks.set_frame(screen.frame());
+                targets.set_frame(screen.frame());
+                post_chains.set_frame(screen.frame());
+                // the engine sets the viewport and the masks of every window without the cache
+                renderer.state.invalidate();
+
+                for (sprite, transform, animation) in sprites.iter_mut(&mut world) {
+                    let asset = match sprite_management.get(sprite.name()) {
+                        Some(asset) => asset,
+                        None => continue,
+                    };
+                    let (width, height) = match sprite_management.texture(asset.texture) {
+                        Some(texture) => (texture.width(), texture.height()),
+                        None => continue,
+                    };
+
+                    // animated frames and source rectangles are relative to the sprite's image
+                    let frame = animation
+                        .and_then(|animation| animation.region())
+                        .or_else(|| sprite.region())
+                        .map(|frame| frame.offset(asset.image.x(), asset.image.y()))
+                        .unwrap_or(asset.region);
+                    let pivot = sprite
+                        .pivot()
+                        .or(asset.pivot)
+                        .unwrap_or_else(|| Vec2::new(0.5, 0.5));
+
+                    sprite_batch.push(Quad::new(
+                        asset.texture,
+                        sprite.layer(),
+                        sprite.order(),
+                        sprite_batch::sprite_vertices(
+                            &transform,
+                            &sprite,
+                            frame,
+                            pivot,
+                            asset.flipped,
+                            width,
+                            height,
+                        ),
+                    ));
+                }
+
+                for (text, transform) in texts.iter_mut(&mut world) {
+                    push_text(
+                        &mut sprite_batch,
+                        font_management,
+                        sprite_management,
+                        &text,
+                        &transform,
+                    );
+                }
+
+                // debug shapes are drawn on top of everything, labels on top of the lines, once
+                // after the last scene of their window
+                let shapes: &[DebugShape] =
+                    if screen.window() == debug_draw.window() && screen.is_last_scene() {
+                        debug_draw.shapes()
+                    } else {
+                        &[]
+                    };
+                for shape in shapes {
+                    let overlay = match shape.space() {
+                        DebugSpace::World => &mut world_overlay,
+                        DebugSpace::Screen => &mut screen_overlay,
+                    };
+
+                    if let Some((position, label)) = shape.label() {
+                        let text = Text::new(label, debug_draw.font())
+                            .with_size(debug_draw.text_size())
+                            .with_color(shape.color())
+                            .with_pivot(Vec2::zero())
+                            .with_order(1);
+                        let transform = Transform::new(
+                            Vec3::new(position.x, position.y, 0.0),
+                            Vec2::zero(),
+                            Vec2::new(1.0, 1.0),
+                        );
+                        push_text(
+                            overlay,
+                            font_management,
+                            sprite_management,
+                            &text,
+                            &transform,
+                        );
+                        continue;
+                    }
+
+                    let texture =
+                        match white.get_or_insert_with(|| white_texture(sprite_management)) {
+                            Some(texture) => *texture,
+                            None => continue,
+                        };
+                    for (from, to) in shape.segments() {
+                        overlay.push(Quad::new(
+                            texture,
+                            0,
+                            0,
+                            sprite_batch::line_vertices(
+                                *from,
+                                *to,
+                                shape.thickness(),
+                                shape.color(),
+                            ),
+                        ));
+                    }
+                }
+                font_management.upload(&sprite_management);
+
+                // the transition covers everything, the debug shapes included
+                let transition = screen.transition().filter(|(_, coverage)| *coverage > 0.0);
+                if let Some((kind, coverage)) = transition {
+                    if let Some(texture) =
+                        *white.get_or_insert_with(|| white_texture(sprite_management))
+                    {
+                        screen_overlay.push(Quad::new(
+                            texture,
+                            i32::MAX,
+                            0,
+                            sprite_batch::transition_vertices(
+                                kind,
+                                coverage,
+                                screen.width(),
+                                screen.height(),
+                            ),
+                        ));
+                    }
+                }
+
+                let mut visible_chunks: Vec<(i32, ChunkKey)> = Vec::new();
+                for (entity, (tilemap, transform)) in tilemaps.iter_entities_mut(&mut world) {
+                    let asset = match sprite_management.get(tilemap.tileset().sprite()) {
+                        Some(asset) => asset,
+                        None => continue,
+                    };
+                    let (width, height) = match sprite_management.texture(asset.texture) {
+                        Some(texture) => (texture.width(), texture.height()),
+                        None => continue,
+                    };
+
+                    for (index, layer) in tilemap.layers().iter().enumerate() {
+                        if !layer.is_visible() {
+                            continue;
+                        }
+
+                        let (columns, rows) = layer.chunks();
+                        for chunk_y in 0..rows {
+                            for chunk_x in 0..columns {
+                                let key = (screen.scene(), entity, index, chunk_x, chunk_y);
+                                let revision = layer.revision(chunk_x, chunk_y);
+
+                                let stale = match chunks.get(&key) {
+                                    Some(chunk) => {
+                                        chunk.revision != revision
+                                            || chunk.texture != asset.texture
+                                            || chunk.image != asset.image
+                                    }
+                                    None => true,
+                                };
+
+                                if stale {
+                                    let vertices = sprite_batch::tile_vertices(
+                                        &tilemap,
+                                        layer,
+                                        chunk_x,
+                                        chunk_y,
+                                        asset.image,
+                                        width,
+                                        height,
+                                    );
+                                    let chunk = TileChunk {
+                                        revision,
+                                        texture: asset.texture,
+                                        image: asset.image,
+                                        mesh: TileMesh::new(renderer, &vertices),
+                                        layer: layer.layer(),
+                                        model: Mat4::identity(),
+                                    };
+                                    if let Some(TileChunk {
+                                        mesh: Some(mesh), ..
+                                    }) = chunks.insert(key, chunk)
+                                    {
+                                        mesh.delete();
+                                    }
+                                }
+
+                                if let Some(chunk) = chunks.get_mut(&key) {
+                                    chunk.layer = layer.layer();
+                                    chunk.model = model(&transform);
+                                    if chunk.mesh.is_some() {
+                                        visible_chunks.push((chunk.layer, key));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // chunks of removed tilemaps and of scenes that are not rendered anymore
+                for chunk in chunks.evict() {
+                    if let Some(mesh) = chunk.mesh {
+                        mesh.delete();
+                    }
+                }
+                // targets and effects of removed cameras
+                for target in targets.evict() {
+                    target.delete();
+                    sprite_management.remove_render_target(&target.name);
+                }
+                for chain in post_chains.evict() {
+                    chain.delete();
+                }
+
+                let cameras: Vec<(Camera, Transform)> = cameras
+                    .iter_mut(&mut world)
+                    .map(|(camera, transform)| ((*camera).clone(), *transform))
+                    .collect();
+
+                if sprite_batch.is_empty()
+                    && visible_chunks.is_empty()
+                    && world_overlay.is_empty()
+                    && screen_overlay.is_empty()
+                    && cameras.is_empty()
+                    // the effects of the window are drawn after its last scene
+                    && !post_processing.effects().iter().any(PostEffect::is_enabled)
+                {
+                    return;
+                }
+
+                // stable, so the layers of a tilemap keep their order
+                visible_chunks.sort_by_key(|(layer, _)| *layer);
+                let (vertices, batches) = sprite_batch.build();
+                let projection = projection(&screen);
+
+                renderer.begin();
+
+                // cameras render before the scene so that sprites show their current frame, the
+                // scene is drawn without the camera's own target
+                for (camera, transform) in &cameras {
+                    let stale = match targets.get(camera.target()) {
+                        Some(target) => target.settings != settings(camera),
+                        None => true,
+                    };
+                    if stale {
+                        let target = RenderTarget::new(camera, sprite_management);
+                        if let Some(target) = targets.insert(camera.target().to_owned(), target) {
+                            target.delete();
+                        }
+                    }
+
+                    // the target is kept for this frame
+                    let target = match targets.get_mut(camera.target()) {
+                        Some(target) => &*target,
+                        None => continue,
+                    };
+                    let framebuffer = match &target.framebuffer {
+                        Some(framebuffer) => framebuffer,
+                        None => continue,
+                    };
+                    let chain = post_chain(
+                        &mut post_chains,
+                        ChainKey::Camera(camera.target().to_owned()),
+                        settings(camera),
+                        camera.effects(),
+                        &post_processing,
+                    );
+                    // the target is only drawn into directly without samples and effects
+                    let skipped = match (&target.multisampled, chain) {
+                        (None, None) => Some(target.texture),
+                        _ => None,
+                    };
+
+                    match chain {
+                        Some(chain) => chain.begin(&mut renderer.state),
+                        None => {
+                            target.multisampled.as_ref().unwrap_or(framebuffer).bind();
+                            renderer.state.set_viewport(Rect::new(
+                                0,
+                                0,
+                                camera.width() as i32,
+                                camera.height() as i32,
+                            ));
+                        }
+                    }
+                    let color = camera.clear_color();
+                    api::clear_color(color.x, color.y, color.z, color.w);
+
+                    renderer.draw_scene(
+                        &camera.projection(transform),
+                        &sprite_management,
+                        &vertices,
+                        &batches,
+                        &visible_chunks,
+                        &chunks,
+                        skipped,
+                    );
+
+                    match chain {
+                        Some(chain) => {
+                            chain.end(&mut renderer.state, Some(framebuffer));
+                            renderer.begin();
+                        }
+                        None => {
+                            if let Some(multisampled) = &target.multisampled {
+                                multisampled.resolve(framebuffer);
+                            }
+                        }
+                    }
+                    if screenshot::take_camera(screenshots, camera.target()) {
+                        if let Ok(image) = framebuffer.read_pixels() {
+                            captured.send(CameraCaptured::new(camera.target(), image));
+                        }
+                    }
+                    framebuffer.unbind();
+                }
+
+                // the scenes of a window are drawn into its chain, which is cleared before the
+                // first one and drawn into the window after the last one
+                let chain = post_chain(
+                    &mut post_chains,
+                    ChainKey::Window(screen.window()),
+                    (screen.width(), screen.height(), 0),
+                    post_processing.effects(),
+                    &post_processing,
+                );
+                match chain {
+                    Some(chain) => {
+                        chain.begin(&mut renderer.state);
+                        if screen.is_first_scene() {
+                            let [red, green, blue, alpha] = screen::CLEAR_COLOR;
+                            api::clear_color(red, green, blue, alpha);
+                        }
+                    }
+                    None if !cameras.is_empty() => {
+                        renderer.state.set_viewport(Rect::new(
+                            0,
+                            0,
+                            screen.width() as i32,
+                            screen.height() as i32,
+                        ));
+                    }
+                    None => {}
+                }
+
+                renderer.draw_scene(
+                    &projection,
+                    &sprite_management,
+                    &vertices,
+                    &batches,
+                    &visible_chunks,
+                    &chunks,
+                    None,
+                );
+                // debug drawings stay clear of the effects
+                match chain {
+                    Some(chain) if screen.is_last_scene() => {
+                        chain.end(&mut renderer.state, None);
+                        renderer.begin();
+                    }
+                    _ => {}
+                }
+
+                let (vertices, batches) = world_overlay.build();
+                for batch in &batches {
+                    renderer.draw_batch(&projection, &sprite_management, &vertices, batch);
+                }
+                let (vertices, batches) = screen_overlay.build();
+                let projection = screen_projection(&screen);
+                for batch in &batches {
+                    renderer.draw_batch(&projection, &sprite_management, &vertices, batch);
+                }
+                renderer.end();
+            },
+        );
 
     Schedule::builder().add_system(system).build()
 }
@@ -0,0 +1,158 @@
+extern crate legion;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use legion::systems::resource::Resources;
+use legion::systems::schedule::Schedule;
+use legion::world::World;
+
+use crate::stage;
+use crate::stage::Ordering;
+use crate::stage::Stage;
+use crate::stage::StageError;
+
+pub struct SystemDescriptor {
+    schedule: Schedule,
+    stage: Stage,
+    ordering: Ordering,
+    // global update systems run once on the current scene unless they run on every updated scene
+    each_scene: bool,
+}
+
+impl SystemDescriptor {
+    // only named systems can be referenced by the before and after constraints of other systems
+    pub fn new(name: &str, schedule: Schedule) -> Self {
+        Self {
+            schedule,
+            stage: Stage::Update,
+            ordering: Ordering {
+                name: Some(name.to_owned()),
+                ..Ordering::default()
+            },
+            each_scene: false,
+        }
+    }
+
+    pub fn in_stage(mut self, stage: Stage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    pub fn before(mut self, system: &str) -> Self {
+        self.ordering.before.push(system.to_owned());
+        self
+    }
+
+    pub fn after(mut self, system: &str) -> Self {
+        self.ordering.after.push(system.to_owned());
+        self
+    }
+
+    // for global systems that work on the entities of a scene, e.g. to advance animations in the
+    // scenes of other windows too
+    pub fn for_each_scene(mut self) -> Self {
+        self.each_scene = true;
+        self
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.ordering.name.as_deref()
+    }
+
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+}
+
+impl From<Schedule> for SystemDescriptor {
+    fn from(schedule: Schedule) -> Self {
+        Self {
+            schedule,
+            stage: Stage::Update,
+            ordering: Ordering::default(),
+            each_scene: false,
+        }
+    }
+}
+
+pub(crate) struct Stages {
+    systems: HashMap<Stage, Vec<SystemDescriptor>>,
+}
+
+impl Stages {
+    pub(crate) fn new() -> Self {
+        Self {
+            systems: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn add(&mut self, system: SystemDescriptor) {
+        self.systems
+            .entry(system.stage)
+            .or_insert_with(Vec::new)
+            .push(system);
+    }
+
+    pub(crate) fn sort(&mut self) -> Result<(), StageError> {
+        for systems in self.systems.values_mut() {
+            let orderings: Vec<Ordering> = systems
+                .iter()
+                .map(|system| system.ordering.clone())
+                .collect();
+            let order = stage::sort(&orderings)?;
+
+            let mut unsorted: Vec<Option<SystemDescriptor>> = systems.drain(..).map(Some).collect();
+            systems.extend(order.into_iter().filter_map(|index| unsorted[index].take()));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.systems
+            .values()
+            .flatten()
+            .filter_map(|system| system.name())
+    }
+
+    // systems that run for each scene can not be ordered against the ones that run once
+    pub(crate) fn check_names(&self, names: &HashSet<&str>) -> Result<(), StageError> {
+        for systems in self.systems.values() {
+            for each_scene in &[false, true] {
+                let orderings: Vec<Ordering> = systems
+                    .iter()
+                    .filter(|system| system.each_scene == *each_scene)
+                    .map(|system| system.ordering.clone())
+                    .collect();
+                stage::check_names(&orderings, names)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn execute(&mut self, stage: Stage, world: &mut World, resources: &mut Resources) {
+        if let Some(systems) = self.systems.get_mut(&stage) {
+            systems
+                .iter_mut()
+                .for_each(|system| system.schedule.execute(world, resources));
+        }
+    }
+
+    // only the systems that run once or only the ones that run for each scene
+    pub(crate) fn execute_where(
+        &mut self,
+        stage: Stage,
+        each_scene: bool,
+        world: &mut World,
+        resources: &mut Resources,
+    ) {
+        if let Some(systems) = self.systems.get_mut(&stage) {
+            systems
+                .iter_mut()
+                .filter(|system| system.each_scene == each_scene)
+                .for_each(|system| system.schedule.execute(world, resources));
+        }
+    }
+}
@@ -0,0 +1,195 @@
+extern crate math;
+
+use math::Vec2;
+
+use crate::component::Text;
+use crate::component::TextAlignment;
+use crate::font::Font;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PlacedGlyph {
+    character: char,
+    position: Vec2,
+    line: usize,
+}
+
+impl PlacedGlyph {
+    pub fn character(&self) -> char {
+        self.character
+    }
+
+    // the pen position on the baseline, relative to the top left corner of the text with y up
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+#[derive(Copy, Clone)]
+struct Entry {
+    character: char,
+    x: f32,
+    advance: f32,
+}
+
+// the glyphs of a text broken into lines and aligned, whitespace is not part of the glyphs
+#[derive(Clone, PartialEq, Debug)]
+pub struct TextLayout {
+    glyphs: Vec<PlacedGlyph>,
+    line_widths: Vec<f32>,
+    width: f32,
+    height: f32,
+}
+
+impl TextLayout {
+    pub fn new(font: &Font, text: &Text) -> Self {
+        let size = text.size();
+        let bounds = text.bounds();
+
+        let mut lines = Vec::new();
+        for paragraph in text.content().split('\n') {
+            wrap(
+                font,
+                size,
+                paragraph.trim_end_matches('\r'),
+                bounds.map(|bounds| bounds.x),
+                &mut lines,
+            );
+        }
+
+        let ascent = font.ascent(size);
+        let line_height = font.line_height(size);
+        let step = line_height * text.line_spacing();
+
+        if let Some(bounds) = bounds {
+            let fitting = (0..lines.len())
+                .take_while(|index| *index as f32 * step + line_height <= bounds.y)
+                .count();
+            lines.truncate(fitting);
+        }
+
+        let line_widths: Vec<f32> = lines.iter().map(|line| line_width(line)).collect();
+        let (width, height) = match bounds {
+            Some(bounds) => (bounds.x, bounds.y),
+            None if lines.is_empty() => (0.0, 0.0),
+            None => (
+                line_widths
+                    .iter()
+                    .fold(0.0, |width: f32, line| width.max(*line)),
+                (lines.len() - 1) as f32 * step + line_height,
+            ),
+        };
+
+        let factor = match text.alignment() {
+            TextAlignment::Left => 0.0,
+            TextAlignment::Center => 0.5,
+            TextAlignment::Right => 1.0,
+        };
+
+        let mut glyphs = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            let offset = (width - line_widths[index]) * factor;
+            let baseline = -(ascent + index as f32 * step);
+
+            glyphs.extend(
+                line.iter()
+                    .filter(|entry| !entry.character.is_whitespace())
+                    .map(|entry| PlacedGlyph {
+                        character: entry.character,
+                        position: Vec2::new(offset + entry.x, baseline),
+                        line: index,
+                    }),
+            );
+        }
+
+        Self {
+            glyphs,
+            line_widths,
+            width,
+            height,
+        }
+    }
+
+    pub fn glyphs(&self) -> &[PlacedGlyph] {
+        &self.glyphs
+    }
+
+    pub fn lines(&self) -> usize {
+        self.line_widths.len()
+    }
+
+    // without trailing whitespace
+    pub fn line_width(&self, line: usize) -> f32 {
+        self.line_widths.get(line).copied().unwrap_or(0.0)
+    }
+
+    // the bounds of the text when it has some
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+}
+
+// breaks after the last whitespace that keeps the line inside the width, words longer than it
+// are broken between characters
+fn wrap(font: &Font, size: f32, paragraph: &str, width: Option<f32>, lines: &mut Vec<Vec<Entry>>) {
+    let mut line: Vec<Entry> = Vec::new();
+    let mut pen = 0.0;
+    // the glyphs from here on move to the next line when the line gets too long
+    let mut break_at: Option<usize> = None;
+
+    for character in paragraph.chars() {
+        let advance = font.advance(character, size);
+        let kerning = line
+            .last()
+            .map_or(0.0, |last| font.kerning(last.character, character, size));
+
+        if character.is_whitespace() {
+            line.push(Entry {
+                character,
+                x: pen + kerning,
+                advance,
+            });
+            pen += kerning + advance;
+            break_at = Some(line.len());
+            continue;
+        }
+
+        let overflows = matches!(width, Some(width) if pen + kerning + advance > width);
+        if overflows && line.iter().any(|entry| !entry.character.is_whitespace()) {
+            let rest = line.split_off(break_at.unwrap_or(line.len()));
+            lines.push(line);
+            line = rest;
+            break_at = None;
+
+            let shift = line.first().map_or(pen, |first| first.x);
+            for entry in line.iter_mut() {
+                entry.x -= shift;
+            }
+            pen -= shift;
+        }
+
+        let x = if line.is_empty() { 0.0 } else { pen + kerning };
+        line.push(Entry {
+            character,
+            x,
+            advance,
+        });
+        pen = x + advance;
+    }
+
+    lines.push(line);
+}
+
+fn line_width(line: &[Entry]) -> f32 {
+    line.iter()
+        .rev()
+        .find(|entry| !entry.character.is_whitespace())
+        .map_or(0.0, |entry| entry.x + entry.advance)
+}
@@ -1,40 +1,127 @@
 use std::time::SystemTime;
 
 pub struct Tick {
-    time_until_start: SystemTime,
     previous_time: SystemTime,
     delta_time: f32,
+    unscaled_delta_time: f32,
+    elapsed: f32,
+    scaled_elapsed: f32,
+    time_scale: f32,
+    paused: bool,
+    fixed_delta_time: f32,
+    accumulator: f32,
 }
 
+// limits the catch up after a long frame
+const MAX_FIXED_STEPS: u32 = 8;
+
 pub(crate) fn update(tick: &mut Tick) {
     let time = SystemTime::now();
 
-    tick.delta_time = match time.duration_since(tick.previous_time) {
+    let delta_time = match time.duration_since(tick.previous_time) {
         Ok(time) => (time.as_millis() as f64 / 1000.0) as f32,
         Err(_) => 0.0,
     };
 
     tick.previous_time = SystemTime::now();
+    set_delta_time(tick, delta_time);
+}
+
+// advances the time by a fixed amount instead of the measured one
+pub(crate) fn advance(tick: &mut Tick, delta_time: f32) {
+    tick.previous_time = SystemTime::now();
+    set_delta_time(tick, delta_time);
+}
+
+fn set_delta_time(tick: &mut Tick, delta_time: f32) {
+    tick.unscaled_delta_time = delta_time;
+    tick.delta_time = if tick.paused {
+        0.0
+    } else {
+        delta_time * tick.time_scale
+    };
+    tick.elapsed += tick.unscaled_delta_time;
+    tick.scaled_elapsed += tick.delta_time;
+}
+
+// the number of fixed updates that fit into the time since the last frame
+pub(crate) fn fixed_steps(tick: &mut Tick) -> u32 {
+    tick.accumulator += tick.delta_time;
+
+    let mut steps = 0;
+    while tick.accumulator >= tick.fixed_delta_time && steps < MAX_FIXED_STEPS {
+        tick.accumulator -= tick.fixed_delta_time;
+        steps += 1;
+    }
+
+    if steps == MAX_FIXED_STEPS {
+        tick.accumulator = 0.0;
+    }
+
+    steps
 }
 
 impl Tick {
     pub(crate) fn new() -> Tick {
         Tick {
-            time_until_start: SystemTime::now(),
             previous_time: SystemTime::now(),
             delta_time: 0.0,
+            unscaled_delta_time: 0.0,
+            elapsed: 0.0,
+            scaled_elapsed: 0.0,
+            time_scale: 1.0,
+            paused: false,
+            fixed_delta_time: 1.0 / 60.0,
+            accumulator: 0.0,
         }
     }
 
+    // scaled by the time scale and zero while paused
     pub fn delta_time(&self) -> f32 {
         self.delta_time
     }
 
-    pub fn time(&self) -> f32 {
-        let time = SystemTime::now();
-        match time.duration_since(self.time_until_start) {
-            Ok(time) => time.as_secs() as f32,
-            Err(_) => 0.0,
+    pub fn unscaled_delta_time(&self) -> f32 {
+        self.unscaled_delta_time
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time_scale
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f32) {
+        self.time_scale = time_scale.max(0.0);
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn fixed_delta_time(&self) -> f32 {
+        self.fixed_delta_time
+    }
+
+    pub fn set_fixed_delta_time(&mut self, fixed_delta_time: f32) {
+        if fixed_delta_time > 0.0 {
+            self.fixed_delta_time = fixed_delta_time;
         }
     }
+
+    // seconds since the engine started
+    pub fn time(&self) -> f32 {
+        self.elapsed
+    }
+
+    // only counts the time that passed for the scaled delta time
+    pub fn scaled_time(&self) -> f32 {
+        self.scaled_elapsed
+    }
 }
@@ -0,0 +1,1039 @@
+extern crate base64;
+extern crate math;
+extern crate miniz_oxide;
+extern crate serde;
+extern crate serde_json;
+extern crate xml;
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+use math::Vec2;
+use math::Vec3;
+use math::Vec4;
+use serde::Deserialize;
+use serde_json::Value;
+use xml::reader::EventReader;
+use xml::reader::XmlEvent;
+
+use crate::component::MapObject;
+use crate::component::Property;
+use crate::component::Sprite;
+use crate::component::Tile;
+use crate::component::TileLayer;
+use crate::component::Tilemap;
+use crate::component::Tileset;
+use crate::component::Transform;
+use crate::serialization::SerializationError;
+
+// the upper bits of a global tile id flip the tile, the rest identifies it
+const FLIPPED_HORIZONTALLY: u32 = 0x8000_0000;
+const FLIPPED_VERTICALLY: u32 = 0x4000_0000;
+const FLIPPED_DIAGONALLY: u32 = 0x2000_0000;
+const TILE_ID: u32 = 0x0fff_ffff;
+
+#[derive(Debug)]
+pub enum TiledError {
+    OpeningFileFailed,
+    ReadingFileFailed,
+    ParsingFailed(String),
+    UnsupportedOrientation(String),
+    UnsupportedEncoding(String),
+    InfiniteMap,
+    // tilesets made of single images are not supported
+    MissingTilesetImage(String),
+    UnknownTile(u32),
+    SpawningFailed(SerializationError),
+}
+
+impl From<SerializationError> for TiledError {
+    fn from(error: SerializationError) -> Self {
+        TiledError::SpawningFailed(error)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TiledTileset {
+    name: String,
+    first_gid: u32,
+    tile_count: u32,
+    image_width: u32,
+    image_height: u32,
+    // the sprite of the tileset is named after the path of its image
+    tileset: Tileset,
+}
+
+impl TiledTileset {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn first_gid(&self) -> u32 {
+        self.first_gid
+    }
+
+    pub fn tile_count(&self) -> u32 {
+        self.tile_count
+    }
+
+    // relative to the working directory when the map was loaded from a file
+    pub fn image(&self) -> &str {
+        self.tileset.sprite()
+    }
+
+    pub fn tileset(&self) -> &Tileset {
+        &self.tileset
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct TileLayerData {
+    name: String,
+    width: u32,
+    height: u32,
+    // row by row from the top left tile
+    gids: Vec<u32>,
+    opacity: f32,
+    visible: bool,
+    properties: BTreeMap<String, Property>,
+}
+
+// positions are in pixels of the map, with the origin at its top left corner and y pointing down
+#[derive(Clone, PartialEq, Debug)]
+pub struct TiledObject {
+    id: u32,
+    name: String,
+    class: String,
+    position: Vec2,
+    size: Vec2,
+    // in degrees clockwise around the position
+    rotation: f32,
+    gid: Option<u32>,
+    visible: bool,
+    properties: BTreeMap<String, Property>,
+}
+
+impl TiledObject {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // the type of the object in older versions of tiled
+    pub fn class(&self) -> &str {
+        &self.class
+    }
+
+    // the top left corner, the bottom left one for tile objects
+    pub fn position(&self) -> Vec2 {
+        self.position
+    }
+
+    pub fn size(&self) -> Vec2 {
+        self.size
+    }
+
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    // set for objects showing a tile, with the flip flags
+    pub fn gid(&self) -> Option<u32> {
+        self.gid
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub fn property(&self, name: &str) -> Option<&Property> {
+        self.properties.get(name)
+    }
+
+    // properties named "<component>.<field>" are left out, they set fields of the entity instead
+    pub fn map_object(&self) -> MapObject {
+        self.properties
+            .iter()
+            .filter(|(name, _)| !name.contains('.'))
+            .fold(
+                MapObject::new(self.id, &self.name, &self.class, self.size),
+                |object, (name, property)| object.with_property(name, property.clone()),
+            )
+    }
+
+    // component, field and value of the properties named "<component>.<field>"
+    pub fn field_overrides(&self) -> impl Iterator<Item = (&str, &str, &Property)> {
+        self.properties.iter().filter_map(|(name, property)| {
+            let mut parts = name.splitn(2, '.');
+            match (parts.next(), parts.next()) {
+                (Some(component), Some(field)) => Some((component, field, property)),
+                _ => None,
+            }
+        })
+    }
+}
+
+// an orthogonal map of the tiled editor, read from tmx or json files
+#[derive(Clone, PartialEq, Debug)]
+pub struct TiledMap {
+    width: u32,
+    height: u32,
+    tile_width: u32,
+    tile_height: u32,
+    // sorted by their first gid
+    tilesets: Vec<TiledTileset>,
+    // group layers are flattened
+    tile_layers: Vec<TileLayerData>,
+    objects: Vec<TiledObject>,
+    properties: BTreeMap<String, Property>,
+}
+
+impl TiledMap {
+    // .tmx files are read as xml, everything else as json, paths inside are relative to the file
+    pub fn from_file(path: &str) -> Result<Self, TiledError> {
+        let text = read_file(Path::new(path))?;
+        let directory = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+
+        if is_xml(Path::new(path)) {
+            parse_tmx(&text, directory)
+        } else {
+            parse_json(&text, directory)
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, TiledError> {
+        parse_json(json, Path::new(""))
+    }
+
+    pub fn from_tmx(tmx: &str) -> Result<Self, TiledError> {
+        parse_tmx(tmx, Path::new(""))
+    }
+
+    // in tiles
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn tile_width(&self) -> u32 {
+        self.tile_width
+    }
+
+    pub fn tile_height(&self) -> u32 {
+        self.tile_height
+    }
+
+    pub fn tilesets(&self) -> &[TiledTileset] {
+        &self.tilesets
+    }
+
+    pub fn objects(&self) -> &[TiledObject] {
+        &self.objects
+    }
+
+    pub fn property(&self, name: &str) -> Option<&Property> {
+        self.properties.get(name)
+    }
+
+    // one tilemap per used tileset, its bottom left corner is the bottom left corner of the map
+    // the draw layer of a tile layer is its int property "layer"
+    pub fn tilemaps(&self) -> Result<Vec<Tilemap>, TiledError> {
+        let mut tilemaps: Vec<Tilemap> = self
+            .tilesets
+            .iter()
+            .map(|tileset| Tilemap::new(tileset.tileset.clone(), self.tile_width, self.tile_height))
+            .collect();
+
+        for data in &self.tile_layers {
+            let draw_layer = data
+                .properties
+                .get("layer")
+                .and_then(|property| property.as_int())
+                .unwrap_or(0) as i32;
+            let mut layers: Vec<Option<TileLayer>> = vec![None; self.tilesets.len()];
+
+            for (index, gid) in data.gids.iter().enumerate() {
+                let (tileset, tile) = match self.resolve(*gid)? {
+                    Some(resolved) => resolved,
+                    None => continue,
+                };
+
+                let x = index as u32 % data.width.max(1);
+                let row = index as u32 / data.width.max(1);
+                let layer = layers[tileset].get_or_insert_with(|| {
+                    TileLayer::new(&data.name, data.width, data.height)
+                        .with_layer(draw_layer)
+                        .with_opacity(data.opacity)
+                        .with_visible(data.visible)
+                });
+                layer.set_tile(x as i32, data.height as i32 - 1 - row as i32, Some(tile));
+            }
+
+            for (tileset, layer) in layers.into_iter().enumerate() {
+                if let Some(layer) = layer {
+                    tilemaps[tileset].add_layer(layer);
+                }
+            }
+        }
+
+        Ok(tilemaps
+            .into_iter()
+            .filter(|tilemap| !tilemap.layers().is_empty())
+            .collect())
+    }
+
+    // placed at the center of the object, with y pointing up like the world
+    pub fn object_transform(&self, object: &TiledObject) -> Transform {
+        let angle = object.rotation.to_radians();
+        let (sin, cos) = angle.sin_cos();
+
+        // tile objects are anchored at their bottom left corner, everything else at the top left
+        let half_width = object.size.x / 2.0;
+        let half_height = if object.gid.is_some() {
+            -object.size.y / 2.0
+        } else {
+            object.size.y / 2.0
+        };
+        let center_x = object.position.x + half_width * cos - half_height * sin;
+        let center_y = object.position.y + half_width * sin + half_height * cos;
+
+        let scale = match self.object_tile(object) {
+            Some((tileset, _)) => Vec2::new(
+                object.size.x / tileset.tileset.tile_width().max(1) as f32,
+                object.size.y / tileset.tileset.tile_height().max(1) as f32,
+            ),
+            None => Vec2::one(),
+        };
+
+        Transform::new(
+            Vec3::new(
+                center_x,
+                (self.height * self.tile_height) as f32 - center_y,
+                0.0,
+            ),
+            Vec2::new(-angle, 0.0),
+            scale,
+        )
+    }
+
+    // the tile shown by a tile object, diagonal flips are not supported by sprites
+    pub fn object_sprite(&self, object: &TiledObject) -> Option<Sprite> {
+        let (tileset, tile) = self.object_tile(object)?;
+        let region =
+            tileset
+                .tileset
+                .region(tile.index(), tileset.image_width, tileset.image_height)?;
+
+        Some(Sprite::new(tileset.image()).with_region(region).with_flip(
+            object.gid? & FLIPPED_HORIZONTALLY != 0,
+            object.gid? & FLIPPED_VERTICALLY != 0,
+        ))
+    }
+
+    fn object_tile(&self, object: &TiledObject) -> Option<(&TiledTileset, Tile)> {
+        let (tileset, tile) = self.resolve(object.gid?).ok()??;
+        Some((&self.tilesets[tileset], tile))
+    }
+
+    // the index of the tileset and the tile, none for empty tiles
+    fn resolve(&self, gid: u32) -> Result<Option<(usize, Tile)>, TiledError> {
+        let id = gid & TILE_ID;
+        if id == 0 {
+            return Ok(None);
+        }
+
+        let tileset = self
+            .tilesets
+            .iter()
+            .rposition(|tileset| tileset.first_gid <= id)
+            .ok_or(TiledError::UnknownTile(id))?;
+
+        let index = id - self.tilesets[tileset].first_gid;
+        if index >= self.tilesets[tileset].tile_count {
+            return Err(TiledError::UnknownTile(id));
+        }
+
+        Ok(Some((tileset, tile(index, gid))))
+    }
+}
+
+// tiled flips diagonally first and then horizontally and vertically, tiles flip first and rotate
+// afterwards, a diagonal flip is a horizontal flip followed by a quarter turn counter clockwise
+fn tile(index: u32, gid: u32) -> Tile {
+    let horizontal = gid & FLIPPED_HORIZONTALLY != 0;
+    let vertical = gid & FLIPPED_VERTICALLY != 0;
+
+    if gid & FLIPPED_DIAGONALLY == 0 {
+        return Tile::new(index).with_flip(horizontal, vertical);
+    }
+
+    let flip_x = !horizontal;
+    let rotation = if flip_x != vertical { 3 } else { 1 };
+    Tile::new(index)
+        .with_flip(flip_x, vertical)
+        .with_rotation(rotation)
+}
+
+fn read_file(path: &Path) -> Result<String, TiledError> {
+    let mut file = File::open(path).map_err(|_| TiledError::OpeningFileFailed)?;
+    let mut text = String::new();
+    file.read_to_string(&mut text)
+        .map_err(|_| TiledError::ReadingFileFailed)?;
+
+    Ok(text)
+}
+
+fn is_xml(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("tmx") | Some("tsx") | Some("xml")
+    )
+}
+
+fn resolve_path(directory: &Path, path: &str) -> String {
+    directory.join(path).to_string_lossy().into_owned()
+}
+
+fn check_map(orientation: &str, infinite: bool) -> Result<(), TiledError> {
+    if orientation != "orthogonal" {
+        return Err(TiledError::UnsupportedOrientation(orientation.to_owned()));
+    }
+    if infinite {
+        return Err(TiledError::InfiniteMap);
+    }
+
+    Ok(())
+}
+
+fn decode(data: &str, encoding: &str, compression: &str) -> Result<Vec<u32>, TiledError> {
+    match encoding {
+        "csv" => data
+            .split(',')
+            .map(str::trim)
+            .filter(|gid| !gid.is_empty())
+            .map(|gid| {
+                gid.parse::<u32>()
+                    .map_err(|_| TiledError::ParsingFailed(format!("invalid tile {}", gid)))
+            })
+            .collect(),
+        "base64" => {
+            let data: String = data.split_whitespace().collect();
+            let bytes = base64::decode(&data)
+                .map_err(|error| TiledError::ParsingFailed(error.to_string()))?;
+            let bytes = match compression {
+                "" => bytes,
+                "zlib" => miniz_oxide::inflate::decompress_to_vec_zlib(&bytes)
+                    .map_err(|_| TiledError::ParsingFailed("invalid zlib data".to_owned()))?,
+                "gzip" => inflate_gzip(&bytes)?,
+                compression => {
+                    return Err(TiledError::UnsupportedEncoding(compression.to_owned()));
+                }
+            };
+
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|gid| u32::from_le_bytes([gid[0], gid[1], gid[2], gid[3]]))
+                .collect())
+        }
+        encoding => Err(TiledError::UnsupportedEncoding(encoding.to_owned())),
+    }
+}
+
+// skips the gzip header, the checksum at the end is not verified
+fn inflate_gzip(bytes: &[u8]) -> Result<Vec<u8>, TiledError> {
+    let invalid = || TiledError::ParsingFailed("invalid gzip data".to_owned());
+    if bytes.len() < 10 || bytes[0] != 0x1f || bytes[1] != 0x8b || bytes[2] != 8 {
+        return Err(invalid());
+    }
+
+    let flags = bytes[3];
+    let mut start = 10;
+    if flags & 0x04 != 0 {
+        let extra = bytes.get(start..start + 2).ok_or_else(invalid)?;
+        start += 2 + u16::from_le_bytes([extra[0], extra[1]]) as usize;
+    }
+    // file name and comment end with a zero byte
+    for flag in &[0x08, 0x10] {
+        if flags & flag != 0 {
+            let end = bytes
+                .get(start..)
+                .and_then(|rest| rest.iter().position(|byte| *byte == 0))
+                .ok_or_else(invalid)?;
+            start += end + 1;
+        }
+    }
+    if flags & 0x02 != 0 {
+        start += 2;
+    }
+
+    let deflated = bytes.get(start..).ok_or_else(invalid)?;
+    miniz_oxide::inflate::decompress_to_vec(deflated).map_err(|_| invalid())
+}
+
+// none for unset colors and property types without a value, e.g. classes
+fn property(kind: &str, value: &str) -> Option<Property> {
+    match kind {
+        "" | "string" => Some(Property::String(value.to_owned())),
+        "bool" => Some(Property::Bool(value == "true")),
+        "int" => value.parse().ok().map(Property::Int),
+        "float" => value.parse().ok().map(Property::Float),
+        "color" => color(value).map(Property::Color),
+        "file" => Some(Property::File(value.to_owned())),
+        "object" => value.parse().ok().map(Property::Object),
+        _ => None,
+    }
+}
+
+// #aarrggbb or #rrggbb
+fn color(value: &str) -> Option<Vec4> {
+    let value = value.trim_start_matches('#');
+    let channel = |index: usize| {
+        u8::from_str_radix(value.get(index * 2..index * 2 + 2)?, 16)
+            .ok()
+            .map(|channel| channel as f32 / 255.0)
+    };
+
+    match value.len() {
+        6 => Some(Vec4::new(channel(0)?, channel(1)?, channel(2)?, 1.0)),
+        8 => Some(Vec4::new(
+            channel(1)?,
+            channel(2)?,
+            channel(3)?,
+            channel(0)?,
+        )),
+        _ => None,
+    }
+}
+
+fn default_orientation() -> String {
+    "orthogonal".to_owned()
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+fn default_visible() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct JsonMap {
+    width: u32,
+    height: u32,
+    tilewidth: u32,
+    tileheight: u32,
+    #[serde(default = "default_orientation")]
+    orientation: String,
+    #[serde(default)]
+    infinite: bool,
+    #[serde(default)]
+    tilesets: Vec<JsonTileset>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonTileset {
+    #[serde(default)]
+    firstgid: u32,
+    // external tilesets only have their first gid and the path
+    source: Option<String>,
+    #[serde(default)]
+    name: String,
+    image: Option<String>,
+    #[serde(default)]
+    imagewidth: u32,
+    #[serde(default)]
+    imageheight: u32,
+    #[serde(default)]
+    tilewidth: u32,
+    #[serde(default)]
+    tileheight: u32,
+    #[serde(default)]
+    margin: u32,
+    #[serde(default)]
+    spacing: u32,
+    #[serde(default)]
+    tilecount: u32,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonData {
+    Gids(Vec<u32>),
+    Encoded(String),
+}
+
+#[derive(Deserialize)]
+struct JsonLayer {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    width: u32,
+    #[serde(default)]
+    height: u32,
+    data: Option<JsonData>,
+    #[serde(default)]
+    encoding: String,
+    #[serde(default)]
+    compression: String,
+    #[serde(default = "default_opacity")]
+    opacity: f32,
+    #[serde(default = "default_visible")]
+    visible: bool,
+    #[serde(default)]
+    objects: Vec<JsonObject>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonObject {
+    #[serde(default)]
+    id: u32,
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    class: String,
+    x: f32,
+    y: f32,
+    #[serde(default)]
+    width: f32,
+    #[serde(default)]
+    height: f32,
+    #[serde(default)]
+    rotation: f32,
+    gid: Option<u32>,
+    #[serde(default = "default_visible")]
+    visible: bool,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonProperty {
+    name: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+    #[serde(default)]
+    value: Value,
+}
+
+fn parse_json(json: &str, directory: &Path) -> Result<TiledMap, TiledError> {
+    let data: JsonMap =
+        serde_json::from_str(json).map_err(|error| TiledError::ParsingFailed(error.to_string()))?;
+    check_map(&data.orientation, data.infinite)?;
+
+    let mut map = TiledMap {
+        width: data.width,
+        height: data.height,
+        tile_width: data.tilewidth,
+        tile_height: data.tileheight,
+        tilesets: Vec::new(),
+        tile_layers: Vec::new(),
+        objects: Vec::new(),
+        properties: json_properties(data.properties),
+    };
+
+    for tileset in data.tilesets {
+        let first_gid = tileset.firstgid;
+        let tileset = match tileset.source.clone() {
+            Some(source) => external_tileset(first_gid, directory, &source)?,
+            None => json_tileset(tileset, first_gid, directory)?,
+        };
+        map.tilesets.push(tileset);
+    }
+    map.tilesets.sort_by_key(|tileset| tileset.first_gid);
+
+    for layer in data.layers {
+        add_json_layer(&mut map, layer, 1.0, true)?;
+    }
+
+    Ok(map)
+}
+
+fn json_tileset(
+    tileset: JsonTileset,
+    first_gid: u32,
+    directory: &Path,
+) -> Result<TiledTileset, TiledError> {
+    let image = match &tileset.image {
+        Some(image) => image,
+        None => return Err(TiledError::MissingTilesetImage(tileset.name)),
+    };
+    let sprite = Tileset::new(
+        &resolve_path(directory, image),
+        tileset.tilewidth,
+        tileset.tileheight,
+    )
+    .with_margin(tileset.margin)
+    .with_spacing(tileset.spacing);
+
+    Ok(TiledTileset {
+        tile_count: match tileset.tilecount {
+            0 => sprite.count(tileset.imagewidth, tileset.imageheight),
+            count => count,
+        },
+        name: tileset.name,
+        first_gid,
+        image_width: tileset.imagewidth,
+        image_height: tileset.imageheight,
+        tileset: sprite,
+    })
+}
+
+// .tsx and .json tilesets, their image is relative to the tileset file
+fn external_tileset(
+    first_gid: u32,
+    directory: &Path,
+    source: &str,
+) -> Result<TiledTileset, TiledError> {
+    let path = directory.join(source);
+    let text = read_file(&path)?;
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+
+    if is_xml(&path) {
+        tmx_tileset(&parse_xml(&text)?, first_gid, directory)
+    } else {
+        let tileset: JsonTileset = serde_json::from_str(&text)
+            .map_err(|error| TiledError::ParsingFailed(error.to_string()))?;
+        json_tileset(tileset, first_gid, directory)
+    }
+}
+
+// groups pass their opacity and visibility on to their layers
+fn add_json_layer(
+    map: &mut TiledMap,
+    layer: JsonLayer,
+    opacity: f32,
+    visible: bool,
+) -> Result<(), TiledError> {
+    let opacity = opacity * layer.opacity;
+    let visible = visible && layer.visible;
+
+    match layer.kind.as_str() {
+        "tilelayer" => {
+            let gids = match layer.data {
+                Some(JsonData::Gids(gids)) => gids,
+                Some(JsonData::Encoded(data)) => {
+                    decode(&data, &layer.encoding, &layer.compression)?
+                }
+                None => Vec::new(),
+            };
+            map.tile_layers.push(TileLayerData {
+                name: layer.name,
+                width: layer.width,
+                height: layer.height,
+                gids,
+                opacity,
+                visible,
+                properties: json_properties(layer.properties),
+            });
+        }
+        "objectgroup" => {
+            for object in layer.objects {
+                map.objects.push(TiledObject {
+                    id: object.id,
+                    name: object.name,
+                    class: if object.class.is_empty() {
+                        object.kind
+                    } else {
+                        object.class
+                    },
+                    position: Vec2::new(object.x, object.y),
+                    size: Vec2::new(object.width, object.height),
+                    rotation: object.rotation,
+                    gid: object.gid,
+                    visible: visible && object.visible,
+                    properties: json_properties(object.properties),
+                });
+            }
+        }
+        "group" => {
+            for child in layer.layers {
+                add_json_layer(map, child, opacity, visible)?;
+            }
+        }
+        // image layers
+        _ => (),
+    }
+
+    Ok(())
+}
+
+fn json_properties(properties: Vec<JsonProperty>) -> BTreeMap<String, Property> {
+    properties
+        .into_iter()
+        .filter_map(|data| {
+            let value = match &data.value {
+                Value::String(value) => value.clone(),
+                value => value.to_string(),
+            };
+            property(&data.kind, &value).map(|property| (data.name, property))
+        })
+        .collect()
+}
+
+// just enough of a document tree for tmx and tsx files
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn string(&self, name: &str) -> String {
+        self.attribute(name).unwrap_or_default().to_owned()
+    }
+
+    fn number<T: FromStr>(&self, name: &str, default: T) -> Result<T, TiledError> {
+        match self.attribute(name) {
+            Some(value) => value
+                .parse()
+                .map_err(|_| TiledError::ParsingFailed(format!("invalid {} {}", name, value))),
+            None => Ok(default),
+        }
+    }
+
+    // visible and similar flags are written as 0 and 1
+    fn flag(&self, name: &str, default: bool) -> bool {
+        match self.attribute(name) {
+            Some(value) => value == "1" || value == "true",
+            None => default,
+        }
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+}
+
+fn parse_xml(text: &str) -> Result<Element, TiledError> {
+    let mut open: Vec<Element> = Vec::new();
+
+    for event in EventReader::from_str(text) {
+        match event.map_err(|error| TiledError::ParsingFailed(error.to_string()))? {
+            XmlEvent::StartElement {
+                name, attributes, ..
+            } => open.push(Element {
+                name: name.local_name,
+                attributes: attributes
+                    .into_iter()
+                    .map(|attribute| (attribute.name.local_name, attribute.value))
+                    .collect(),
+                children: Vec::new(),
+                text: String::new(),
+            }),
+            XmlEvent::EndElement { .. } => {
+                let element = open
+                    .pop()
+                    .ok_or_else(|| TiledError::ParsingFailed("unexpected end".to_owned()))?;
+                match open.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                if let Some(element) = open.last_mut() {
+                    element.text.push_str(&text);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Err(TiledError::ParsingFailed("missing root element".to_owned()))
+}
+
+fn parse_tmx(tmx: &str, directory: &Path) -> Result<TiledMap, TiledError> {
+    let root = parse_xml(tmx)?;
+    if root.name != "map" {
+        return Err(TiledError::ParsingFailed(format!(
+            "expected a map instead of {}",
+            root.name
+        )));
+    }
+    check_map(
+        root.attribute("orientation").unwrap_or("orthogonal"),
+        root.flag("infinite", false),
+    )?;
+
+    let mut map = TiledMap {
+        width: root.number("width", 0)?,
+        height: root.number("height", 0)?,
+        tile_width: root.number("tilewidth", 0)?,
+        tile_height: root.number("tileheight", 0)?,
+        tilesets: Vec::new(),
+        tile_layers: Vec::new(),
+        objects: Vec::new(),
+        properties: tmx_properties(&root),
+    };
+
+    for element in root.children.iter().filter(|child| child.name == "tileset") {
+        let first_gid = element.number("firstgid", 1)?;
+        let tileset = match element.attribute("source") {
+            Some(source) => external_tileset(first_gid, directory, source)?,
+            None => tmx_tileset(element, first_gid, directory)?,
+        };
+        map.tilesets.push(tileset);
+    }
+    map.tilesets.sort_by_key(|tileset| tileset.first_gid);
+
+    add_tmx_layers(&mut map, &root, 1.0, true)?;
+
+    Ok(map)
+}
+
+fn tmx_tileset(
+    element: &Element,
+    first_gid: u32,
+    directory: &Path,
+) -> Result<TiledTileset, TiledError> {
+    let name = element.string("name");
+    let image = element
+        .child("image")
+        .ok_or_else(|| TiledError::MissingTilesetImage(name.clone()))?;
+    let source = image
+        .attribute("source")
+        .ok_or_else(|| TiledError::MissingTilesetImage(name.clone()))?;
+
+    let image_width = image.number("width", 0)?;
+    let image_height = image.number("height", 0)?;
+    let tileset = Tileset::new(
+        &resolve_path(directory, source),
+        element.number("tilewidth", 0)?,
+        element.number("tileheight", 0)?,
+    )
+    .with_margin(element.number("margin", 0)?)
+    .with_spacing(element.number("spacing", 0)?);
+
+    Ok(TiledTileset {
+        tile_count: match element.number("tilecount", 0)? {
+            0 => tileset.count(image_width, image_height),
+            count => count,
+        },
+        name,
+        first_gid,
+        image_width,
+        image_height,
+        tileset,
+    })
+}
+
+fn add_tmx_layers(
+    map: &mut TiledMap,
+    parent: &Element,
+    opacity: f32,
+    visible: bool,
+) -> Result<(), TiledError> {
+    for element in &parent.children {
+        let opacity = opacity * element.number("opacity", 1.0)?;
+        let visible = visible && element.flag("visible", true);
+
+        match element.name.as_str() {
+            "layer" => {
+                let gids = match element.child("data") {
+                    Some(data) => match data.attribute("encoding") {
+                        Some(encoding) => decode(
+                            &data.text,
+                            encoding,
+                            data.attribute("compression").unwrap_or_default(),
+                        )?,
+                        None => data
+                            .children
+                            .iter()
+                            .map(|tile| tile.number("gid", 0))
+                            .collect::<Result<_, _>>()?,
+                    },
+                    None => Vec::new(),
+                };
+                map.tile_layers.push(TileLayerData {
+                    name: element.string("name"),
+                    width: element.number("width", 0)?,
+                    height: element.number("height", 0)?,
+                    gids,
+                    opacity,
+                    visible,
+                    properties: tmx_properties(element),
+                });
+            }
+            "objectgroup" => {
+                for object in element
+                    .children
+                    .iter()
+                    .filter(|child| child.name == "object")
+                {
+                    let class = match object.attribute("class") {
+                        Some(class) if !class.is_empty() => class.to_owned(),
+                        _ => object.string("type"),
+                    };
+                    map.objects.push(TiledObject {
+                        id: object.number("id", 0)?,
+                        name: object.string("name"),
+                        class,
+                        position: Vec2::new(object.number("x", 0.0)?, object.number("y", 0.0)?),
+                        size: Vec2::new(
+                            object.number("width", 0.0)?,
+                            object.number("height", 0.0)?,
+                        ),
+                        rotation: object.number("rotation", 0.0)?,
+                        gid: match object.attribute("gid") {
+                            Some(_) => Some(object.number("gid", 0)?),
+                            None => None,
+                        },
+                        visible: visible && object.flag("visible", true),
+                        properties: tmx_properties(object),
+                    });
+                }
+            }
+            "group" => add_tmx_layers(map, element, opacity, visible)?,
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+// long string values are written as text instead of the value attribute
+fn tmx_properties(element: &Element) -> BTreeMap<String, Property> {
+    let properties = match element.child("properties") {
+        Some(properties) => properties,
+        None => return BTreeMap::new(),
+    };
+
+    properties
+        .children
+        .iter()
+        .filter_map(|data| {
+            let value = data.attribute("value").unwrap_or(&data.text);
+            property(data.attribute("type").unwrap_or_default(), value)
+                .map(|property| (data.string("name"), property))
+        })
+        .collect()
+}
@@ -0,0 +1,149 @@
+extern crate legion;
+
+use std::collections::HashMap;
+
+use legion::query::IntoQuery;
+use legion::query::Write;
+use legion::systems::resource::Resources;
+use legion::systems::schedule::Schedule;
+use legion::systems::SystemBuilder;
+
+use crate::component::Timer;
+use crate::events::Events;
+use crate::tick::Tick;
+
+type Callback = Box<dyn FnMut(&mut Resources) + Send + Sync>;
+
+// sent by the timers resource whenever one of its timers finishes
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimerFinished {
+    name: String,
+}
+
+impl TimerFinished {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+struct TimerEntry {
+    timer: Timer,
+    callback: Option<Callback>,
+}
+
+// named timers, finished once timers are kept until they get stopped
+pub struct Timers {
+    timers: HashMap<String, TimerEntry>,
+}
+
+impl Timers {
+    pub(crate) fn new() -> Self {
+        Self {
+            timers: HashMap::new(),
+        }
+    }
+
+    // replaces a running timer with the same name
+    pub fn start(&mut self, name: &str, timer: Timer) {
+        self.timers.insert(
+            name.to_owned(),
+            TimerEntry {
+                timer,
+                callback: None,
+            },
+        );
+    }
+
+    // the callback is called every time the timer finishes
+    pub fn start_with<F>(&mut self, name: &str, timer: Timer, callback: F)
+    where
+        F: FnMut(&mut Resources) + Send + Sync + 'static,
+    {
+        self.timers.insert(
+            name.to_owned(),
+            TimerEntry {
+                timer,
+                callback: Some(Box::new(callback)),
+            },
+        );
+    }
+
+    pub fn stop(&mut self, name: &str) -> Option<Timer> {
+        self.timers.remove(name).map(|entry| entry.timer)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Timer> {
+        self.timers.get(name).map(|entry| &entry.timer)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Timer> {
+        self.timers.get_mut(name).map(|entry| &mut entry.timer)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.timers.contains_key(name)
+    }
+
+    pub fn just_finished(&self, name: &str) -> bool {
+        self.get(name).map_or(false, |timer| timer.just_finished())
+    }
+}
+
+// callbacks are taken out of the resource while they are called
+pub(crate) fn update(resources: &mut Resources, delta_time: f32) {
+    let mut finished: Vec<(String, u32, Option<Callback>)> = Vec::new();
+
+    match resources.get_mut::<Timers>() {
+        Some(ref mut timers) => {
+            for (name, entry) in timers.timers.iter_mut() {
+                let times = entry.timer.tick(delta_time);
+
+                if times > 0 {
+                    finished.push((name.clone(), times, entry.callback.take()));
+                }
+            }
+        }
+        None => return,
+    }
+
+    if let Some(ref mut events) = resources.get_mut::<Events<TimerFinished>>() {
+        for (name, times, _) in &finished {
+            for _ in 0..*times {
+                events.send(TimerFinished { name: name.clone() });
+            }
+        }
+    }
+
+    for (name, times, callback) in finished {
+        let mut callback = match callback {
+            Some(callback) => callback,
+            None => continue,
+        };
+
+        for _ in 0..times {
+            callback(resources);
+        }
+
+        if let Some(ref mut timers) = resources.get_mut::<Timers>() {
+            if let Some(entry) = timers.timers.get_mut(&name) {
+                if entry.callback.is_none() {
+                    entry.callback = Some(callback);
+                }
+            }
+        }
+    }
+}
+
+// ticks the timer components of every updated scene
+pub(crate) fn create_system() -> Schedule {
+    let system = SystemBuilder::new("TimerSystem")
+        .read_resource::<Tick>()
+        .with_query(<Write<Timer>>::query())
+        .build(|_, mut world, tick, query| {
+            for mut timer in query.iter_mut(&mut world) {
+                timer.tick(tick.delta_time());
+            }
+        });
+
+    Schedule::builder().add_system(system).build()
+}
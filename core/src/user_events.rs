@@ -0,0 +1,111 @@
+extern crate context;
+
+use std::any::Any;
+use std::any::TypeId;
+
+use context::canvas::CanvasLoop;
+use context::event::EventError;
+use context::Event;
+
+type UserEvent = Box<dyn Any + Send + Sync>;
+type SentEvent = Box<dyn Any + Send>;
+
+#[derive(Copy, Clone)]
+pub(crate) struct UserEventType {
+    type_id: TypeId,
+    register: fn(&CanvasLoop) -> Result<(), EventError>,
+    convert: fn(&Event) -> Option<UserEvent>,
+    downcast: fn(SentEvent) -> Result<UserEvent, SentEvent>,
+}
+
+impl UserEventType {
+    pub(crate) fn new<T: Any + Send + Sync>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            register: register::<T>,
+            convert: convert::<T>,
+            downcast: downcast::<T>,
+        }
+    }
+
+    pub(crate) fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    pub(crate) fn register(&self, canvas_loop: &CanvasLoop) -> Result<(), EventError> {
+        (self.register)(canvas_loop)
+    }
+
+    pub(crate) fn convert(&self, event: &Event) -> Option<UserEvent> {
+        (self.convert)(event)
+    }
+
+    // hands the event back when it is of another type
+    pub(crate) fn downcast(&self, event: SentEvent) -> Result<UserEvent, SentEvent> {
+        (self.downcast)(event)
+    }
+}
+
+fn register<T: Any + Send + Sync>(canvas_loop: &CanvasLoop) -> Result<(), EventError> {
+    canvas_loop.register_event::<T>()
+}
+
+fn convert<T: Any + Send + Sync>(event: &Event) -> Option<UserEvent> {
+    event
+        .as_user_event_type::<T>()
+        .map(|event| Box::new(event) as UserEvent)
+}
+
+fn downcast<T: Any + Send + Sync>(event: SentEvent) -> Result<UserEvent, SentEvent> {
+    event.downcast::<T>().map(|event| event as UserEvent)
+}
+
+// events sent through the channel of the event sender, it only takes events of registered types
+pub(crate) fn receive(
+    user_events: &mut UserEvents,
+    user_event_types: &[UserEventType],
+    mut event: SentEvent,
+) {
+    for user_event_type in user_event_types {
+        match user_event_type.downcast(event) {
+            Ok(user_event) => {
+                push(user_events, user_event);
+                return;
+            }
+            Err(other) => event = other,
+        }
+    }
+}
+
+pub(crate) fn push(user_events: &mut UserEvents, event: UserEvent) {
+    user_events.events.push(event);
+}
+
+pub(crate) fn clear(user_events: &mut UserEvents) {
+    user_events.events.clear();
+}
+
+// user events of the current frame, in the order they were polled
+pub struct UserEvents {
+    events: Vec<UserEvent>,
+}
+
+impl UserEvents {
+    pub(crate) fn new() -> Self {
+        Self { events: Vec::new() }
+    }
+
+    pub fn read<T: Any>(&self) -> impl Iterator<Item = &T> {
+        self.events
+            .iter()
+            .filter_map(|event| event.downcast_ref::<T>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
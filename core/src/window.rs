@@ -0,0 +1,16 @@
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WindowKey(usize);
+
+impl WindowKey {
+    pub(crate) fn new(index: usize) -> Self {
+        Self(index)
+    }
+
+    pub fn main() -> Self {
+        Self(0)
+    }
+
+    pub(crate) fn index(self) -> usize {
+        self.0
+    }
+}
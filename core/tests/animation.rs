@@ -0,0 +1,124 @@
+extern crate core;
+
+mod common;
+
+use common::Scene;
+use core::component::AnimationClip;
+use core::component::AnimationStateMachine;
+use core::component::Condition;
+use core::component::PlaybackMode;
+use core::component::SpriteAnimation;
+use core::resource::Events;
+use core::AnimationFinished;
+use core::Engine;
+use core::EventReader;
+use core::Region;
+use core::WindowConfig;
+
+fn headless_engine() -> Engine<Scene> {
+    let mut engine = common::headless_engine();
+
+    let frames = Region::grid(64, 32, 16, 16);
+    engine.add_animation(
+        "idle",
+        AnimationClip::from_regions(&frames[0..4], 0.1, PlaybackMode::Loop),
+    );
+    engine.add_animation(
+        "jump",
+        AnimationClip::from_regions(&frames[4..6], 0.1, PlaybackMode::Once),
+    );
+    engine
+}
+
+fn animation_in(engine: &Engine<Scene>, scene: Scene) -> SpriteAnimation {
+    let world = engine.world(scene).unwrap();
+    let entity = world.iter_entities().next().unwrap();
+    world
+        .get_component::<SpriteAnimation>(entity)
+        .unwrap()
+        .clone()
+}
+
+fn animation(engine: &Engine<Scene>) -> SpriteAnimation {
+    animation_in(engine, Scene::Main)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn animation_sends_finished_event() {
+        let mut engine = headless_engine();
+        engine.add_entities(Scene::Main, (), vec![(SpriteAnimation::new("jump"),)]);
+        let mut reader = EventReader::<AnimationFinished>::new();
+        let mut finished = Vec::new();
+
+        for _ in 0..30 {
+            engine.step().unwrap();
+            let events = engine
+                .resources()
+                .get::<Events<AnimationFinished>>()
+                .unwrap();
+            finished.extend(
+                events
+                    .read(&mut reader)
+                    .map(|event| event.clip().to_owned()),
+            );
+        }
+
+        assert_eq!(finished, vec!["jump"]);
+        assert!(animation(&engine).is_finished());
+        assert_eq!(
+            animation(&engine).region(),
+            Some(Region::new(16, 16, 16, 16))
+        );
+    }
+
+    #[test]
+    fn animation_state_machine_switches_clips() {
+        let mut engine = headless_engine();
+        let machine = AnimationStateMachine::new("idle", "idle")
+            .with_state("jump", "jump")
+            .with_any_transition("jump", vec![Condition::trigger("jump")])
+            .with_transition("jump", "idle", vec![Condition::Finished]);
+        engine.add_entities(
+            Scene::Main,
+            (),
+            vec![(SpriteAnimation::new("idle"), machine)],
+        );
+
+        engine.step_frames(5).unwrap();
+        assert_eq!(animation(&engine).clip(), "idle");
+
+        {
+            let world = engine.world_mut(Scene::Main).unwrap();
+            let entity = world.iter_entities().next().unwrap();
+            world
+                .get_component_mut::<AnimationStateMachine>(entity)
+                .unwrap()
+                .set_trigger("jump");
+        }
+        engine.step().unwrap();
+        assert_eq!(animation(&engine).clip(), "jump");
+
+        engine.step_frames(30).unwrap();
+        assert_eq!(animation(&engine).clip(), "idle");
+    }
+
+    #[test]
+    fn animation_plays_in_scenes_of_other_windows() {
+        let mut engine = headless_engine();
+        engine.create_scene(Scene::Editor);
+        engine.create_window(WindowConfig::default());
+        let window = engine.create_window(WindowConfig::default());
+        engine.set_scene_window(Scene::Editor, window);
+        engine.add_entities(Scene::Editor, (), vec![(SpriteAnimation::new("idle"),)]);
+
+        engine.step_frames(15).unwrap();
+
+        let animation = animation_in(&engine, Scene::Editor);
+        assert_eq!(animation.frame(), 2);
+        assert_eq!(animation.region(), Some(Region::new(32, 0, 16, 16)));
+    }
+}
@@ -0,0 +1,108 @@
+extern crate core;
+extern crate math;
+extern crate serde_json;
+
+mod common;
+
+use common::headless_engine;
+use common::Scene;
+use core::component::Camera;
+use core::component::Transform;
+use core::resource::Events;
+use core::resource::Screenshots;
+use core::CameraCaptured;
+use core::EventReader;
+use core::ScreenshotTaken;
+use core::WindowKey;
+use math::Vec3;
+use math::Vec4;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn camera_renders_into_its_target() {
+        let camera = Camera::new("minimap", 160, 90)
+            .with_samples(4)
+            .with_clear_color(Vec4::new(0.0, 0.0, 0.0, 1.0))
+            .with_zoom(0.25);
+
+        assert_eq!(camera.target(), "minimap");
+        assert_eq!((camera.width(), camera.height()), (160, 90));
+        assert_eq!((camera.samples(), camera.zoom()), (4, 0.25));
+
+        let json = serde_json::to_string(&camera).unwrap();
+        let copy: Camera = serde_json::from_str(&json).unwrap();
+        assert_eq!(copy, camera);
+
+        let mut mirror = Camera::new("mirror", 64, 64);
+        assert_eq!((mirror.samples(), mirror.zoom()), (0, 1.0));
+        mirror.set_size(128, 32);
+        assert_eq!((mirror.width(), mirror.height()), (128, 32));
+    }
+
+    #[test]
+    fn screenshots_wait_for_a_rendered_frame() {
+        let mut engine = headless_engine();
+        let second = engine.create_window(Default::default());
+
+        {
+            let mut screenshots = engine.resources_mut().get_mut::<Screenshots>().unwrap();
+            screenshots.request(WindowKey::main());
+            screenshots.request(WindowKey::main());
+            assert!(screenshots.is_requested(WindowKey::main()));
+            assert!(!screenshots.is_requested(second));
+        }
+
+        // headless engines render nothing
+        let mut reader = EventReader::<ScreenshotTaken>::new();
+        engine.step().unwrap();
+
+        let screenshots = engine.resources().get::<Screenshots>().unwrap();
+        assert!(screenshots.is_requested(WindowKey::main()));
+        let events = engine.resources().get::<Events<ScreenshotTaken>>().unwrap();
+        assert_eq!(events.read(&mut reader).count(), 0);
+    }
+
+    #[test]
+    fn camera_captures_wait_for_a_rendered_frame() {
+        let mut engine = headless_engine();
+        let camera = Camera::new("minimap", 16, 8);
+        engine.add_entities(
+            Scene::Main,
+            (),
+            vec![(camera, Transform::from_position(Vec3::new(0.0, 0.0, 0.0)))],
+        );
+        engine
+            .resources_mut()
+            .get_mut::<Screenshots>()
+            .unwrap()
+            .request_camera("minimap");
+        let mut reader = EventReader::<CameraCaptured>::new();
+
+        engine.step().unwrap();
+
+        let screenshots = engine.resources().get::<Screenshots>().unwrap();
+        assert!(screenshots.is_camera_requested("minimap"));
+        let events = engine.resources().get::<Events<CameraCaptured>>().unwrap();
+        assert_eq!(events.read(&mut reader).count(), 0);
+    }
+
+    #[test]
+    fn camera_projection_keeps_the_top_up() {
+        // two pixels wide and one high, like a sprite of a left and a right half
+        let camera = Camera::new("mirror", 2, 1);
+        let transform = Transform::from_position(Vec3::new(10.0, 5.0, 0.0));
+        let projection = camera.projection(&transform);
+
+        let left = projection * Vec4::new(9.5, 5.0, 0.0, 1.0);
+        let right = projection * Vec4::new(10.5, 5.0, 0.0, 1.0);
+        assert!((left.x + 0.5).abs() < 1e-5);
+        assert!((right.x - 0.5).abs() < 1e-5);
+
+        // read back images start with the top row, i.e. the one above the camera
+        let above = projection * Vec4::new(10.0, 5.25, 0.0, 1.0);
+        assert!((above.y - 0.5).abs() < 1e-5);
+    }
+}
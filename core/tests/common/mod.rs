@@ -0,0 +1,21 @@
+// shared by the integration tests, each of them uses only a part
+#![allow(dead_code)]
+
+extern crate core;
+
+use core::Engine;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Scene {
+    Main,
+    Menu,
+    Editor,
+}
+
+pub fn headless_engine() -> Engine<Scene> {
+    Engine::builder()
+        .headless()
+        .scene(Scene::Main)
+        .build()
+        .expect("headless engine")
+}
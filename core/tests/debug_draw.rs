@@ -0,0 +1,140 @@
+extern crate core;
+extern crate math;
+
+mod common;
+
+use common::Scene;
+use core::resource::DebugDraw;
+use core::DebugSpace;
+use core::Engine;
+use core::WindowConfig;
+use core::WindowKey;
+use math::Vec2;
+use math::Vec4;
+
+// release builds start with debug drawing disabled
+fn headless_engine() -> Engine<Scene> {
+    let mut engine = common::headless_engine();
+    engine
+        .resources_mut()
+        .get_mut::<DebugDraw>()
+        .unwrap()
+        .set_enabled(true);
+    engine
+}
+
+fn shapes(engine: &Engine<Scene>) -> usize {
+    engine
+        .resources()
+        .get::<DebugDraw>()
+        .unwrap()
+        .shapes()
+        .len()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn debug_draw_builds_shapes_from_lines() {
+        let mut engine = headless_engine();
+        let mut debug_draw = engine.resources_mut().get_mut::<DebugDraw>().unwrap();
+        let red = Vec4::new(1.0, 0.0, 0.0, 1.0);
+
+        debug_draw
+            .rect(Vec2::new(10.0, 10.0), Vec2::new(4.0, 2.0), red)
+            .in_screen_space()
+            .with_thickness(2.0);
+        debug_draw.arrow(Vec2::new(0.0, 0.0), Vec2::new(100.0, 0.0), red);
+        debug_draw.circle(Vec2::zero(), 5.0, red);
+        debug_draw.polygon(&[Vec2::zero(), Vec2::new(1.0, 0.0)], red);
+        debug_draw.grid(Vec2::zero(), Vec2::new(16.0, 8.0), 3, 2, red);
+        debug_draw.text(Vec2::new(5.0, 5.0), "hp 10", red);
+
+        let shapes = debug_draw.shapes();
+        assert_eq!(shapes.len(), 6);
+
+        let rect = &shapes[0];
+        assert_eq!(rect.space(), DebugSpace::Screen);
+        assert_eq!(rect.thickness(), 2.0);
+        assert_eq!(
+            rect.segments()[0],
+            (Vec2::new(8.0, 9.0), Vec2::new(12.0, 9.0))
+        );
+        assert_eq!(rect.segments()[3].1, Vec2::new(8.0, 9.0));
+
+        // the head of the arrow points back from its tip
+        let arrow = shapes[1].segments();
+        assert_eq!(arrow.len(), 3);
+        assert_eq!(arrow[1].0, Vec2::new(100.0, 0.0));
+        assert!((arrow[1].1.x - (100.0 - 8.0 * 0.75_f32.sqrt())).abs() < 1e-4);
+        assert!((arrow[1].1.y + arrow[2].1.y).abs() < 1e-4);
+
+        assert_eq!(shapes[2].segments().len(), 32);
+        assert_eq!(shapes[3].segments().len(), 1);
+        assert_eq!(shapes[4].segments().len(), 7);
+        assert_eq!(
+            shapes[4].segments()[3],
+            (Vec2::new(48.0, 0.0), Vec2::new(48.0, 16.0))
+        );
+
+        assert_eq!(shapes[5].space(), DebugSpace::World);
+        assert!(shapes[5].segments().is_empty());
+        assert_eq!(shapes[5].label(), Some((Vec2::new(5.0, 5.0), "hp 10")));
+    }
+
+    #[test]
+    fn debug_draw_keeps_shapes_for_their_duration() {
+        let mut engine = headless_engine();
+        {
+            let mut debug_draw = engine.resources_mut().get_mut::<DebugDraw>().unwrap();
+            let white = Vec4::new(1.0, 1.0, 1.0, 1.0);
+            debug_draw.line(Vec2::zero(), Vec2::new(1.0, 1.0), white);
+            debug_draw
+                .line(Vec2::zero(), Vec2::new(0.0, 1.0), white)
+                .for_seconds(0.04);
+        }
+
+        engine.step().unwrap();
+        assert_eq!(shapes(&engine), 1);
+
+        engine.step().unwrap();
+        assert_eq!(shapes(&engine), 1);
+
+        engine.step().unwrap();
+        assert_eq!(shapes(&engine), 0);
+    }
+
+    #[test]
+    fn debug_draw_ignores_shapes_while_disabled() {
+        let mut engine = headless_engine();
+        let mut debug_draw = engine.resources_mut().get_mut::<DebugDraw>().unwrap();
+        let white = Vec4::new(1.0, 1.0, 1.0, 1.0);
+
+        debug_draw
+            .circle(Vec2::zero(), 1.0, white)
+            .for_seconds(10.0);
+        debug_draw.set_enabled(false);
+        assert!(debug_draw.shapes().is_empty());
+
+        debug_draw.line(Vec2::zero(), Vec2::new(1.0, 1.0), white);
+        assert!(debug_draw.shapes().is_empty());
+
+        debug_draw.set_enabled(true);
+        debug_draw.line(Vec2::zero(), Vec2::new(1.0, 1.0), white);
+        assert_eq!(debug_draw.shapes().len(), 1);
+    }
+
+    #[test]
+    fn debug_draw_picks_the_window_it_draws_into() {
+        let mut engine = headless_engine();
+        engine.create_window(WindowConfig::default());
+        let window = engine.create_window(WindowConfig::default());
+        let mut debug_draw = engine.resources_mut().get_mut::<DebugDraw>().unwrap();
+
+        assert_eq!(debug_draw.window(), WindowKey::main());
+        debug_draw.set_window(window);
+        assert_eq!(debug_draw.window(), window);
+    }
+}
@@ -0,0 +1,145 @@
+extern crate core;
+extern crate legion;
+
+mod common;
+
+use common::headless_engine;
+use common::Scene;
+use core::AnimationPlugin;
+use core::Engine;
+use core::EngineError;
+use core::Plugin;
+use core::SceneKey;
+use core::Stage;
+use core::StageError;
+use core::SystemDescriptor;
+use legion::systems::schedule::Schedule;
+
+struct Needs(&'static str, &'static str);
+
+impl<K: SceneKey> Plugin<K> for Needs {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    fn dependencies(&self) -> Vec<&str> {
+        vec![self.1]
+    }
+
+    fn build(&self, _engine: &mut Engine<K>) {}
+}
+
+fn empty_system(name: &str) -> SystemDescriptor {
+    SystemDescriptor::new(name, Schedule::builder().build())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn builder_rejects_unknown_initial_scene() {
+        let result = Engine::builder()
+            .headless()
+            .scene(Scene::Main)
+            .initial_scene(Scene::Menu)
+            .build();
+
+        match result {
+            Err(EngineError::SceneNotFound) => (),
+            _ => panic!("the initial scene has to be created"),
+        }
+    }
+
+    #[test]
+    fn builder_reports_missing_plugin_dependencies() {
+        let result = Engine::builder()
+            .headless()
+            .scene(Scene::Main)
+            .without_default_plugins()
+            .plugin(AnimationPlugin)
+            .build();
+
+        match result {
+            Err(EngineError::MissingPluginDependency(plugin, dependency)) => {
+                assert_eq!(
+                    (plugin.as_str(), dependency.as_str()),
+                    ("animation", "time")
+                )
+            }
+            _ => panic!("the animation plugin needs the time plugin"),
+        }
+    }
+
+    #[test]
+    fn builder_reports_cyclic_plugin_dependencies() {
+        let result = Engine::builder()
+            .headless()
+            .scene(Scene::Main)
+            .plugin(Needs("physics", "audio"))
+            .plugin(Needs("audio", "physics"))
+            .build();
+
+        match result {
+            Err(EngineError::OrderingPluginsFailed(_)) => (),
+            _ => panic!("cyclic plugin dependencies must be detected"),
+        }
+    }
+
+    #[test]
+    fn engines_without_scene_fail() {
+        let mut engine = Engine::<Scene>::builder().headless().build().unwrap();
+        match engine.step() {
+            Err(EngineError::NoScene) => (),
+            _ => panic!("a headless engine needs a scene to step"),
+        }
+
+        // checked before any window is opened
+        match Engine::<Scene>::new().unwrap().run() {
+            Err(EngineError::NoScene) => (),
+            _ => panic!("an engine needs a scene to run"),
+        }
+    }
+
+    #[test]
+    fn systems_ordered_against_unknown_systems_fail() {
+        let mut engine = headless_engine();
+        engine.add_system(empty_system("input").before("physics"));
+        engine.add_system(empty_system("physics"));
+        engine.add_scene_system(Scene::Main, empty_system("camera").after("player"));
+
+        match engine.step() {
+            Err(EngineError::OrderingSystemsFailed(StageError::UnknownSystem(system, name))) => {
+                assert_eq!((system.as_str(), name.as_str()), ("camera", "player"))
+            }
+            _ => panic!("ordering against a missing system must fail"),
+        }
+    }
+
+    #[test]
+    fn systems_ordered_against_other_stages_fail() {
+        // the fixed update runs before the update, the order can not be kept
+        let mut engine = headless_engine();
+        engine.add_system(empty_system("input").before("physics"));
+        engine.add_system(empty_system("physics").in_stage(Stage::FixedUpdate));
+
+        match engine.step() {
+            Err(EngineError::OrderingSystemsFailed(StageError::OtherStage(system, name))) => {
+                assert_eq!((system.as_str(), name.as_str()), ("input", "physics"))
+            }
+            _ => panic!("ordering against a system of another stage must fail"),
+        }
+
+        // global systems run before the ones of the scenes
+        let mut engine = headless_engine();
+        engine.add_system(empty_system("input"));
+        engine.add_scene_system(Scene::Main, empty_system("ai").before("input"));
+
+        match engine.step() {
+            Err(EngineError::OrderingSystemsFailed(StageError::OtherStage(system, name))) => {
+                assert_eq!((system.as_str(), name.as_str()), ("ai", "input"))
+            }
+            _ => panic!("ordering a scene system against a global system must fail"),
+        }
+    }
+}
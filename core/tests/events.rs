@@ -0,0 +1,97 @@
+extern crate core;
+extern crate legion;
+
+mod common;
+
+use common::Scene;
+use core::resource::Events;
+use core::Engine;
+use core::EventReader;
+use core::Stage;
+use core::SystemDescriptor;
+use legion::systems::schedule::Schedule;
+use legion::systems::SystemBuilder;
+
+struct Died(u32);
+
+#[derive(Default)]
+struct Received(Vec<u32>);
+
+fn engine_with_sender(lifetime: u32) -> Engine<Scene> {
+    let mut engine = common::headless_engine();
+    engine.register_channel::<Died>(lifetime);
+
+    let mut id = 0;
+    let sender = SystemBuilder::new("sender")
+        .write_resource::<Events<Died>>()
+        .build(move |_, _, events, _| {
+            events.send(Died(id));
+            id += 1;
+        });
+    engine.add_system(SystemDescriptor::new(
+        "sender",
+        Schedule::builder().add_system(sender).build(),
+    ));
+
+    engine
+}
+
+fn receiver(name: &'static str) -> Schedule {
+    let mut reader = EventReader::<Died>::new();
+    let system = SystemBuilder::new(name)
+        .read_resource::<Events<Died>>()
+        .write_resource::<Received>()
+        .build(move |_, _, (events, received), _| {
+            received
+                .0
+                .extend(events.read(&mut reader).map(|died| died.0));
+        });
+
+    Schedule::builder().add_system(system).build()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn events_are_received_once_by_every_reader() {
+        let mut engine = engine_with_sender(2);
+        engine.add_resource(Received::default());
+        engine.add_system(
+            SystemDescriptor::new("first", receiver("first")).in_stage(Stage::PostUpdate),
+        );
+        engine.add_system(
+            SystemDescriptor::new("second", receiver("second")).in_stage(Stage::PostUpdate),
+        );
+
+        engine.step_frames(3).unwrap();
+
+        let received = engine.resources().get::<Received>().unwrap();
+        assert_eq!(received.0, vec![0, 0, 1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn events_are_dropped_after_their_lifetime() {
+        let mut engine = engine_with_sender(2);
+
+        engine.step_frames(5).unwrap();
+        assert_eq!(engine.resources().get::<Events<Died>>().unwrap().len(), 2);
+
+        engine.register_channel::<Died>(1);
+        engine.step().unwrap();
+        assert_eq!(engine.resources().get::<Events<Died>>().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn events_sent_later_in_the_frame_are_read_in_the_next_one() {
+        let mut engine = engine_with_sender(2);
+        engine.add_resource(Received::default());
+        engine.add_system(SystemDescriptor::new("early", receiver("early")).before("sender"));
+
+        engine.step_frames(2).unwrap();
+
+        let received = engine.resources().get::<Received>().unwrap();
+        assert_eq!(received.0, vec![0]);
+    }
+}
@@ -0,0 +1,184 @@
+extern crate context;
+extern crate core;
+extern crate legion;
+
+mod common;
+
+use common::Scene;
+use context::keyboard::Key;
+use core::resource::Commands;
+use core::resource::Input;
+use core::resource::Tick;
+use core::Engine;
+use core::EngineError;
+use core::Stage;
+use core::SystemDescriptor;
+use core::WindowConfig;
+use legion::query::IntoQuery;
+use legion::query::Read;
+use legion::systems::schedule::Schedule;
+use legion::systems::SystemBuilder;
+
+struct Counter(u32);
+
+// the markers the post update system found in the last frame
+struct Seen(Vec<u32>);
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Marker(u32);
+
+fn headless_engine() -> Engine<Scene> {
+    let mut engine = common::headless_engine();
+    engine.add_resource(Counter(0));
+    engine
+}
+
+fn counter_system(name: &'static str) -> Schedule {
+    let system = SystemBuilder::new(name)
+        .write_resource::<Counter>()
+        .build(|_, _, counter, _| counter.0 += 1);
+
+    Schedule::builder().add_system(system).build()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn headless_step_runs_fixed_update_once_per_frame() {
+        let mut engine = headless_engine();
+        engine.add_system(
+            SystemDescriptor::new("count", counter_system("count")).in_stage(Stage::FixedUpdate),
+        );
+
+        engine.step_frames(10).unwrap();
+
+        assert_eq!(engine.resources().get::<Counter>().unwrap().0, 10);
+
+        let tick = engine.resources().get::<Tick>().unwrap();
+        assert!((tick.time() - 10.0 / 60.0).abs() < 1e-4);
+        assert!((tick.delta_time() - 1.0 / 60.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn global_systems_run_once_with_scenes_in_other_windows() {
+        let mut engine = headless_engine();
+        engine.create_scene(Scene::Editor);
+        engine.create_window(WindowConfig::default());
+        let window = engine.create_window(WindowConfig::default());
+        engine.set_scene_window(Scene::Editor, window);
+        engine.add_system(SystemDescriptor::new("count", counter_system("count")));
+
+        engine.step_frames(10).unwrap();
+
+        assert_eq!(engine.resources().get::<Counter>().unwrap().0, 10);
+    }
+
+    #[test]
+    fn headless_step_requires_headless_engine() {
+        let mut engine = Engine::<Scene>::new().unwrap();
+        engine.create_scene(Scene::Main);
+
+        match engine.step() {
+            Err(EngineError::NotHeadless) => (),
+            _ => panic!("a windowed engine must not be stepped"),
+        }
+    }
+
+    #[test]
+    fn headless_run_fails() {
+        let engine = headless_engine();
+
+        match engine.run() {
+            Err(EngineError::IsHeadless) => (),
+            _ => panic!("a headless engine must not be run"),
+        }
+    }
+
+    #[test]
+    fn headless_synthetic_input() {
+        let mut engine = headless_engine();
+
+        engine.press_key(Key::A);
+        engine.step().unwrap();
+        assert!(engine
+            .resources()
+            .get::<Input>()
+            .unwrap()
+            .is_key_hold(Key::A));
+
+        engine.step_frames(29).unwrap();
+        engine.release_key(Key::A);
+        engine.step().unwrap();
+
+        let input = engine.resources().get::<Input>().unwrap();
+        assert!(!input.is_key_hold(Key::A));
+        assert!((input.last_hold_duration(Key::A).as_secs_f32() - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn headless_commands_spawn_into_scene() {
+        let mut engine = headless_engine();
+        engine.add_entities(Scene::Main, (), vec![(Marker(0),)]);
+
+        let system = SystemBuilder::new("spawn")
+            .write_resource::<Commands<Scene>>()
+            .build(|_, _, commands, _| commands.spawn((), vec![(Marker(1),)]));
+        engine.add_system(Schedule::builder().add_system(system).build());
+
+        engine.step_frames(3).unwrap();
+
+        let world = engine.world(Scene::Main).unwrap();
+        assert_eq!(world.iter_entities().count(), 4);
+    }
+
+    #[test]
+    fn commands_apply_before_the_next_stage() {
+        let mut engine = headless_engine();
+        engine.add_resource(Seen(Vec::new()));
+        engine.add_entities(Scene::Main, (), vec![(Marker(0),)]);
+
+        let replace = SystemBuilder::new("replace")
+            .write_resource::<Commands<Scene>>()
+            .with_query(<Read<Marker>>::query())
+            .build(|_, mut world, commands, query| {
+                for (entity, marker) in query.iter_entities_mut(&mut world) {
+                    if *marker == Marker(0) {
+                        commands.despawn(entity);
+                        commands.spawn((), vec![(Marker(1),)]);
+                    }
+                }
+            });
+        engine.add_system(Schedule::builder().add_system(replace).build());
+
+        let observe = SystemBuilder::new("observe")
+            .write_resource::<Seen>()
+            .with_query(<Read<Marker>>::query())
+            .build(|_, mut world, seen, query| {
+                seen.0 = query.iter_mut(&mut world).map(|marker| marker.0).collect();
+            });
+        engine.add_system(
+            SystemDescriptor::new("observe", Schedule::builder().add_system(observe).build())
+                .in_stage(Stage::PostUpdate),
+        );
+
+        engine.step().unwrap();
+        assert_eq!(engine.resources().get::<Seen>().unwrap().0, vec![1]);
+
+        engine.step().unwrap();
+        assert_eq!(engine.resources().get::<Seen>().unwrap().0, vec![1]);
+    }
+
+    #[test]
+    fn headless_cyclic_systems_fail() {
+        let mut engine = headless_engine();
+        engine.add_system(SystemDescriptor::new("a", counter_system("a")).after("b"));
+        engine.add_system(SystemDescriptor::new("b", counter_system("b")).after("a"));
+
+        match engine.step() {
+            Err(EngineError::OrderingSystemsFailed(_)) => (),
+            _ => panic!("cyclic system ordering must be detected"),
+        }
+    }
+}
@@ -0,0 +1,89 @@
+extern crate core;
+
+mod common;
+
+use common::Scene;
+use core::resource::Tick;
+use core::resource::Timers;
+use core::Engine;
+use core::EngineError;
+use core::Plugin;
+use core::SceneKey;
+use core::TimePlugin;
+
+// the names of the recording plugins, in the order they were built
+struct Installed(Vec<String>);
+
+struct Record(&'static str, Vec<&'static str>);
+
+impl<K: SceneKey> Plugin<K> for Record {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    fn dependencies(&self) -> Vec<&str> {
+        self.1.clone()
+    }
+
+    fn build(&self, engine: &mut Engine<K>) {
+        if engine.resources().get::<Installed>().is_none() {
+            engine.add_resource(Installed(Vec::new()));
+        }
+        if let Some(ref mut installed) = engine.resources_mut().get_mut::<Installed>() {
+            installed.0.push(self.0.to_owned());
+        }
+    }
+}
+
+fn installed(engine: &Engine<Scene>) -> Vec<String> {
+    engine.resources().get::<Installed>().unwrap().0.clone()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn plugins_install_after_their_dependencies() {
+        let engine = Engine::builder()
+            .headless()
+            .scene(Scene::Main)
+            .plugin(Record("audio", vec!["physics", "assets"]))
+            .plugin(Record("physics", vec!["assets"]))
+            .plugin(Record("assets", Vec::new()))
+            .build()
+            .unwrap();
+
+        assert_eq!(installed(&engine), vec!["assets", "physics", "audio"]);
+        assert!(engine.has_plugin("audio"));
+    }
+
+    #[test]
+    fn plugins_replace_default_plugins_of_the_same_name() {
+        let mut engine = Engine::builder()
+            .headless()
+            .scene(Scene::Main)
+            .plugin(Record("time", Vec::new()))
+            .build()
+            .unwrap();
+
+        // the animation plugin still finds its dependency and the tick it plays with
+        assert_eq!(installed(&engine), vec!["time"]);
+        assert!(engine.has_plugin("animation"));
+        assert!(engine.resources().get::<Timers>().is_none());
+
+        engine.step().unwrap();
+        let tick = engine.resources().get::<Tick>().unwrap();
+        assert!((tick.time() - 1.0 / 60.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn plugins_are_installed_once() {
+        let mut engine = Engine::<Scene>::headless().unwrap();
+
+        match engine.add_plugin(TimePlugin) {
+            Err(EngineError::DuplicatePlugin(name)) => assert_eq!(name, "time"),
+            _ => panic!("a plugin with an installed name must be rejected"),
+        }
+    }
+}
@@ -0,0 +1,83 @@
+extern crate core;
+extern crate math;
+extern crate serde_json;
+
+mod common;
+
+use common::headless_engine;
+use core::component::Camera;
+use core::resource::PostProcessing;
+use core::PostEffect;
+use core::Uniform;
+use math::Vec2;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn effect_uniforms_replace_each_other() {
+        let mut effect = PostEffect::new("vignette")
+            .with_uniform("radius", Uniform::Float(0.5))
+            .with_uniform("intensity", Uniform::Float(0.25));
+        effect.set_uniform("radius", Uniform::Float(0.9));
+
+        assert_eq!(effect.shader(), "vignette");
+        assert!(effect.is_enabled());
+        assert_eq!(effect.uniforms().len(), 2);
+        assert_eq!(effect.uniform("radius"), Some(Uniform::Float(0.9)));
+        assert_eq!(effect.uniform("softness"), None);
+
+        let json = serde_json::to_string(&effect).unwrap();
+        let copy: PostEffect = serde_json::from_str(&json).unwrap();
+        assert_eq!(copy, effect);
+    }
+
+    #[test]
+    fn cameras_keep_one_effect_per_shader() {
+        let mut camera = Camera::new("minimap", 160, 90)
+            .with_effect(PostEffect::new("crt"))
+            .with_effect(PostEffect::new("bloom"))
+            .with_effect(PostEffect::new("crt").with_uniform("curvature", Uniform::Float(2.0)));
+
+        let shaders: Vec<&str> = camera.effects().iter().map(PostEffect::shader).collect();
+        assert_eq!(shaders, vec!["crt", "bloom"]);
+        assert_eq!(
+            camera.effects()[0].uniform("curvature"),
+            Some(Uniform::Float(2.0))
+        );
+
+        camera.effect_mut("bloom").unwrap().set_enabled(false);
+        assert!(!camera.effects()[1].is_enabled());
+        camera.remove_effect("crt");
+        assert_eq!(camera.effects().len(), 1);
+        assert!(camera.effect_mut("crt").is_none());
+    }
+
+    #[test]
+    fn window_effects_use_registered_shaders() {
+        let mut engine = headless_engine();
+        let mut post_processing = engine.resources_mut().get_mut::<PostProcessing>().unwrap();
+
+        for shader in &["vignette", "crt", "color_grading", "screen_shake", "bloom"] {
+            assert!(post_processing.shader(shader).is_some());
+        }
+        assert!(post_processing.effects().is_empty());
+
+        post_processing.add_shader("invert", "#version 330 core\n");
+        post_processing.add_effect(
+            PostEffect::new("screen_shake")
+                .with_uniform("offset", Uniform::Vec2(Vec2::new(4.0, 0.0))),
+        );
+        post_processing.add_effect(PostEffect::new("invert"));
+        post_processing
+            .effect_mut("screen_shake")
+            .unwrap()
+            .set_enabled(false);
+        assert!(!post_processing.effects()[0].is_enabled());
+
+        post_processing.remove_effect("screen_shake");
+        assert_eq!(post_processing.effects().len(), 1);
+        assert_eq!(post_processing.effects()[0].shader(), "invert");
+    }
+}
@@ -0,0 +1,227 @@
+extern crate core;
+extern crate legion;
+
+mod common;
+
+use common::headless_engine;
+use common::Scene;
+use core::resource;
+use core::resource::Screen;
+use core::Engine;
+use core::SceneError;
+use core::SceneHooks;
+use core::Stage;
+use core::SystemDescriptor;
+use core::Transition;
+use core::TransitionKind;
+use core::WindowConfig;
+use core::WindowKey;
+use legion::systems::resource::Resources;
+use legion::systems::schedule::Schedule;
+use legion::systems::SystemBuilder;
+use legion::world::World;
+
+struct Marker;
+
+// the hooks that were called, in order
+struct Calls(Vec<String>);
+
+struct Recorder(&'static str);
+
+impl Recorder {
+    fn record(&self, resources: &mut Resources, hook: &str) {
+        if let Some(ref mut calls) = resources.get_mut::<Calls>() {
+            calls.0.push(format!("{} {}", hook, self.0));
+        }
+    }
+}
+
+impl SceneHooks for Recorder {
+    fn on_enter(&mut self, _world: &mut World, resources: &mut Resources) {
+        self.record(resources, "enter");
+    }
+
+    fn on_exit(&mut self, _world: &mut World, resources: &mut Resources) {
+        self.record(resources, "exit");
+    }
+
+    fn on_pause(&mut self, _world: &mut World, resources: &mut Resources) {
+        self.record(resources, "pause");
+    }
+
+    fn on_resume(&mut self, _world: &mut World, resources: &mut Resources) {
+        self.record(resources, "resume");
+    }
+}
+
+fn recorded_engine() -> Engine<Scene> {
+    let mut engine = headless_engine();
+    engine.create_scene(Scene::Menu);
+    engine.add_resource(Calls(Vec::new()));
+    engine.set_scene_hooks(Scene::Main, Recorder("main"));
+    engine.set_scene_hooks(Scene::Menu, Recorder("menu"));
+    engine
+}
+
+// records the window of every rendered scene and whether it is its first or last one
+fn render_recorder() -> Schedule {
+    let system = SystemBuilder::new("render recorder")
+        .read_resource::<Screen>()
+        .write_resource::<Calls>()
+        .build(|_, _, (screen, calls), _| {
+            calls.0.push(format!(
+                "render {:?} {} {}",
+                screen.window() == WindowKey::main(),
+                screen.is_first_scene(),
+                screen.is_last_scene()
+            ));
+        });
+
+    Schedule::builder().add_system(system).build()
+}
+
+fn take_calls(engine: &mut Engine<Scene>) -> Vec<String> {
+    let mut calls = engine.resources_mut().get_mut::<Calls>().unwrap();
+    calls.0.drain(..).collect()
+}
+
+fn stack(engine: &Engine<Scene>) -> Vec<Scene> {
+    let scenes = engine.resources().get::<resource::Scene<Scene>>().unwrap();
+    scenes.stack().to_vec()
+}
+
+fn request<F>(engine: &mut Engine<Scene>, request: F) -> Result<(), SceneError>
+where
+    F: FnOnce(&mut resource::Scene<Scene>) -> Result<(), SceneError>,
+{
+    let mut scenes = engine
+        .resources_mut()
+        .get_mut::<resource::Scene<Scene>>()
+        .unwrap();
+    request(&mut scenes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scene_changes_reject_unknown_scenes() {
+        let mut engine = headless_engine();
+
+        assert!(matches!(
+            request(&mut engine, |scenes| scenes.change(Scene::Menu)),
+            Err(SceneError::UnknownScene)
+        ));
+        assert!(matches!(
+            request(&mut engine, |scenes| scenes.push(Scene::Editor)),
+            Err(SceneError::UnknownScene)
+        ));
+        assert!(matches!(
+            request(&mut engine, |scenes| scenes.pop()),
+            Err(SceneError::LastScene)
+        ));
+
+        engine.step().unwrap();
+        assert_eq!(stack(&engine), vec![Scene::Main]);
+    }
+
+    #[test]
+    fn pushed_scenes_pause_the_scene_below() {
+        let mut engine = recorded_engine();
+
+        engine.step().unwrap();
+        assert_eq!(take_calls(&mut engine), vec!["enter main"]);
+
+        request(&mut engine, |scenes| scenes.push(Scene::Menu)).unwrap();
+        engine.step().unwrap();
+        assert_eq!(stack(&engine), vec![Scene::Main, Scene::Menu]);
+        assert_eq!(take_calls(&mut engine), vec!["pause main", "enter menu"]);
+
+        request(&mut engine, |scenes| scenes.pop()).unwrap();
+        engine.step().unwrap();
+        assert_eq!(stack(&engine), vec![Scene::Main]);
+        assert_eq!(take_calls(&mut engine), vec!["exit menu", "resume main"]);
+    }
+
+    #[test]
+    fn transitions_swap_scenes_halfway() {
+        let mut engine = recorded_engine();
+        engine.step().unwrap();
+        take_calls(&mut engine);
+
+        // a second at the default 60 steps per second
+        request(&mut engine, |scenes| {
+            scenes.change_with(Scene::Menu, Transition::fade(1.0))
+        })
+        .unwrap();
+        engine.step_frames(20).unwrap();
+        assert_eq!(stack(&engine), vec![Scene::Main]);
+        assert!(take_calls(&mut engine).is_empty());
+        {
+            let screen = engine.resources().get::<Screen>().unwrap();
+            let (kind, coverage) = screen.transition().unwrap();
+            assert_eq!(kind, TransitionKind::Fade);
+            assert!(coverage > 0.5 && coverage < 1.0);
+        }
+
+        engine.step_frames(20).unwrap();
+        assert_eq!(stack(&engine), vec![Scene::Menu]);
+        assert_eq!(take_calls(&mut engine), vec!["exit main", "enter menu"]);
+        assert!(engine
+            .resources()
+            .get::<Screen>()
+            .unwrap()
+            .transition()
+            .is_some());
+
+        engine.step_frames(30).unwrap();
+        assert!(engine
+            .resources()
+            .get::<Screen>()
+            .unwrap()
+            .transition()
+            .is_none());
+    }
+
+    #[test]
+    fn created_scenes_keep_their_world() {
+        let mut engine = headless_engine();
+        engine.add_entities(Scene::Main, (), vec![(Marker,)]);
+
+        engine.create_scene(Scene::Main);
+
+        let world = engine.world(Scene::Main).unwrap();
+        assert_eq!(world.iter_entities().count(), 1);
+    }
+
+    #[test]
+    fn scenes_know_their_place_in_the_window() {
+        let mut engine = recorded_engine();
+        engine.create_scene(Scene::Editor);
+        engine.create_window(WindowConfig::default());
+        let window = engine.create_window(WindowConfig::default());
+        engine.set_scene_window(Scene::Editor, window);
+        engine.add_system(
+            SystemDescriptor::new("render recorder", render_recorder()).in_stage(Stage::Render),
+        );
+        engine.step().unwrap();
+        take_calls(&mut engine);
+
+        request(&mut engine, |scenes| scenes.push(Scene::Menu)).unwrap();
+        engine.step().unwrap();
+
+        let renders: Vec<String> = take_calls(&mut engine)
+            .into_iter()
+            .filter(|call| call.starts_with("render"))
+            .collect();
+        assert_eq!(
+            renders,
+            vec![
+                "render true true false",
+                "render true false true",
+                "render false true true"
+            ]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+extern crate core;
+extern crate legion;
+extern crate math;
+extern crate serde;
+
+mod common;
+
+use std::env;
+
+use common::headless_engine;
+use common::Scene;
+use core::component::Parent;
+use core::component::Sprite;
+use core::component::Transform;
+use core::Prefab;
+use core::SerializationError;
+use legion::entity::Entity;
+use legion::world::World;
+use math::Vec2;
+use math::Vec3;
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Static;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Health(u32);
+
+// not registered, so it is never saved
+struct Unsaved;
+
+fn find(world: &World, name: &str) -> Entity {
+    world
+        .iter_entities()
+        .find(|entity| {
+            world
+                .get_component::<Sprite>(*entity)
+                .map_or(false, |sprite| sprite.name() == name)
+        })
+        .unwrap()
+}
+
+fn parent(world: &World, entity: Entity) -> Option<Entity> {
+    world
+        .get_component::<Parent>(entity)
+        .map(|parent| parent.entity())
+}
+
+fn enemy() -> Prefab {
+    let glow = Prefab::new()
+        .with_component("sprite", &Sprite::new("glow"))
+        .unwrap();
+    let shadow = Prefab::new()
+        .with_component("sprite", &Sprite::new("shadow"))
+        .unwrap()
+        .with_child(glow);
+
+    Prefab::new()
+        .with_component("sprite", &Sprite::new("enemy"))
+        .unwrap()
+        .with_component(
+            "transform",
+            &Transform::new(Vec3::zero(), Vec2::zero(), Vec2::new(2.0, 2.0)),
+        )
+        .unwrap()
+        .with_child(shadow)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn saved_scenes_load_with_their_hierarchy() {
+        for extension in &["ron", "json"] {
+            let mut engine = headless_engine();
+            engine.add_prefab("enemy", enemy());
+            engine
+                .instantiate(Scene::Main, "enemy", Prefab::new())
+                .unwrap();
+            engine.add_entities(Scene::Main, (), vec![(Sprite::new("tree"),)]);
+
+            let path = env::temp_dir().join(format!("scene_round_trip.{}", extension));
+            let path = path.to_str().unwrap();
+            engine.save_scene(Scene::Main, path).unwrap();
+            engine.load_scene(Scene::Menu, path).unwrap();
+
+            let world = engine.world(Scene::Menu).unwrap();
+            assert_eq!(world.iter_entities().count(), 4);
+
+            let enemy = find(world, "enemy");
+            let shadow = find(world, "shadow");
+            assert_eq!(parent(world, enemy), None);
+            assert_eq!(parent(world, shadow), Some(enemy));
+            assert_eq!(parent(world, find(world, "glow")), Some(shadow));
+            assert_eq!(parent(world, find(world, "tree")), None);
+            assert_eq!(
+                world.get_component::<Transform>(enemy).unwrap().scale(),
+                Vec2::new(2.0, 2.0)
+            );
+        }
+    }
+
+    #[test]
+    fn overrides_merge_into_the_prefab() {
+        let mut engine = headless_engine();
+        engine.add_prefab("enemy", enemy());
+
+        let overrides = Prefab::new()
+            .with_field("transform", "position", &Vec3::new(4.0, 2.0, 0.0))
+            .unwrap()
+            .with_child(
+                Prefab::new()
+                    .with_component("sprite", &Sprite::new("weapon"))
+                    .unwrap(),
+            );
+        let enemy = engine.instantiate(Scene::Main, "enemy", overrides).unwrap();
+
+        let world = engine.world(Scene::Main).unwrap();
+        let transform = world.get_component::<Transform>(enemy).unwrap();
+        assert_eq!(transform.position(), Vec3::new(4.0, 2.0, 0.0));
+        assert_eq!(transform.scale(), Vec2::new(2.0, 2.0));
+        assert_eq!(
+            world.get_component::<Sprite>(enemy).unwrap().name(),
+            "enemy"
+        );
+
+        // the children of the overrides are added to the ones of the prefab
+        assert_eq!(parent(world, find(world, "shadow")), Some(enemy));
+        assert_eq!(parent(world, find(world, "weapon")), Some(enemy));
+        assert_eq!(world.iter_entities().count(), 4);
+    }
+
+    #[test]
+    fn field_overrides_need_the_component_in_the_prefab() {
+        let mut engine = headless_engine();
+        engine.add_prefab(
+            "tree",
+            Prefab::new()
+                .with_component("sprite", &Sprite::new("tree"))
+                .unwrap(),
+        );
+
+        let overrides = Prefab::new()
+            .with_field("transform", "position", &Vec3::new(4.0, 2.0, 0.0))
+            .unwrap();
+        match engine.instantiate(Scene::Main, "tree", overrides) {
+            Err(SerializationError::OverriddenComponentNotFound(name)) => {
+                assert_eq!(name, "transform")
+            }
+            _ => panic!("a field of a missing component must not be overridden"),
+        }
+        assert_eq!(
+            engine.world(Scene::Main).unwrap().iter_entities().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn tag_only_entities_are_saved() {
+        let mut engine = headless_engine();
+        engine.register_tag::<Static>("static");
+        engine.add_entities(Scene::Main, (Static,), vec![(Sprite::new("tree"),)]);
+        engine.add_entities(Scene::Main, (Static,), vec![(Unsaved,)]);
+
+        let path = env::temp_dir().join("scene_tags.ron");
+        let path = path.to_str().unwrap();
+        engine.save_scene(Scene::Main, path).unwrap();
+        engine.load_scene(Scene::Menu, path).unwrap();
+
+        let world = engine.world(Scene::Menu).unwrap();
+        assert_eq!(world.iter_entities().count(), 2);
+        assert!(world
+            .iter_entities()
+            .all(|entity| world.get_tag::<Static>(entity) == Some(&Static)));
+    }
+
+    #[test]
+    fn failed_loads_leave_the_scene_untouched() {
+        let mut engine = headless_engine();
+        engine.register_component::<Health>("health");
+        engine.add_entities(Scene::Main, (), vec![(Sprite::new("tree"),)]);
+        engine.add_entities(Scene::Main, (), vec![(Sprite::new("enemy"), Health(3))]);
+
+        let path = env::temp_dir().join("scene_unknown.ron");
+        let path = path.to_str().unwrap();
+        engine.save_scene(Scene::Main, path).unwrap();
+
+        // the tree loads before the enemy fails on its unknown health
+        let mut engine = headless_engine();
+        engine.add_entities(Scene::Main, (), vec![(Sprite::new("rock"),)]);
+        match engine.load_scene(Scene::Main, path) {
+            Err(SerializationError::UnknownComponent(name)) => assert_eq!(name, "health"),
+            _ => panic!("a scene with an unknown component must not load"),
+        }
+
+        let world = engine.world(Scene::Main).unwrap();
+        assert_eq!(world.iter_entities().count(), 1);
+        find(world, "rock");
+    }
+}
@@ -0,0 +1,62 @@
+extern crate core;
+extern crate math;
+
+mod common;
+
+use common::headless_engine;
+use common::Scene;
+use core::component::Sprite;
+use core::Prefab;
+use core::Region;
+use math::Vec2;
+use math::Vec4;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sprite_defaults() {
+        let sprite = Sprite::new("player");
+
+        assert_eq!(sprite.name(), "player");
+        assert_eq!(sprite.region(), None);
+        assert_eq!(sprite.color(), Vec4::new(1.0, 1.0, 1.0, 1.0));
+        assert_eq!(sprite.pivot(), None);
+        assert_eq!(
+            sprite.clone().with_pivot(Vec2::new(0.5, 0.0)).pivot(),
+            Some(Vec2::new(0.5, 0.0))
+        );
+        assert_eq!((sprite.layer(), sprite.order()), (0, 0));
+    }
+
+    #[test]
+    fn sprite_opacity_applies_to_color() {
+        let sprite = Sprite::new("player")
+            .with_tint(Vec4::new(1.0, 0.5, 0.5, 0.5))
+            .with_opacity(2.0);
+        assert_eq!(sprite.opacity(), 1.0);
+
+        let sprite = sprite.with_opacity(0.5);
+        assert_eq!(sprite.color(), Vec4::new(1.0, 0.5, 0.5, 0.25));
+    }
+
+    #[test]
+    fn sprite_survives_prefab_instantiation() {
+        let mut engine = headless_engine();
+        let sprite = Sprite::new("enemy")
+            .with_region(Region::new(16, 0, 16, 16))
+            .with_flip(true, false)
+            .with_layer(2)
+            .with_order(-1);
+        let prefab = Prefab::new().with_component("sprite", &sprite).unwrap();
+        engine.add_prefab("enemy", prefab);
+
+        let entity = engine
+            .instantiate(Scene::Main, "enemy", Prefab::new())
+            .unwrap();
+
+        let world = engine.world(Scene::Main).unwrap();
+        assert_eq!(*world.get_component::<Sprite>(entity).unwrap(), sprite);
+    }
+}
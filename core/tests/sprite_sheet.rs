@@ -0,0 +1,169 @@
+extern crate core;
+extern crate math;
+
+use core::component::PlaybackMode;
+use core::Region;
+use core::SpriteSheet;
+use core::SpriteSheetError;
+use math::Vec2;
+
+// twelve frames so that sorting by name would put "player 10" before "player 2"
+fn aseprite_json() -> String {
+    let frames: Vec<String> = (0..12)
+        .map(|index| {
+            format!(
+                r#""player {}.aseprite": {{
+                    "frame": {{ "x": {}, "y": 0, "w": 16, "h": 24 }},
+                    "rotated": false,
+                    "trimmed": false,
+                    "spriteSourceSize": {{ "x": 0, "y": 0, "w": 16, "h": 24 }},
+                    "sourceSize": {{ "w": 16, "h": 24 }},
+                    "duration": {}
+                }}"#,
+                index,
+                index * 16,
+                if index == 1 { 250 } else { 100 }
+            )
+        })
+        .collect();
+
+    format!(
+        r#"{{
+            "frames": {{ {} }},
+            "meta": {{
+                "app": "http://www.aseprite.org/",
+                "image": "player.png",
+                "size": {{ "w": 192, "h": 24 }},
+                "frameTags": [
+                    {{ "name": "idle", "from": 0, "to": 1, "direction": "forward" }},
+                    {{ "name": "run", "from": 2, "to": 11, "direction": "pingpong" }},
+                    {{ "name": "rewind", "from": 0, "to": 2, "direction": "reverse" }},
+                    {{ "name": "bounce", "from": 3, "to": 5, "direction": "pingpong_reverse" }}
+                ],
+                "slices": [
+                    {{
+                        "name": "pivot",
+                        "keys": [
+                            {{
+                                "frame": 0,
+                                "bounds": {{ "x": 0, "y": 0, "w": 16, "h": 24 }},
+                                "pivot": {{ "x": 8, "y": 24 }}
+                            }}
+                        ]
+                    }}
+                ]
+            }}
+        }}"#,
+        frames.join(",")
+    )
+}
+
+const TEXTURE_PACKER_JSON: &str = r#"{
+    "frames": [
+        {
+            "filename": "hero/run_01.png",
+            "frame": { "x": 0, "y": 0, "w": 32, "h": 32 },
+            "rotated": false,
+            "trimmed": false,
+            "pivot": { "x": 0.5, "y": 1.0 }
+        },
+        {
+            "filename": "hero/run_02.png",
+            "frame": { "x": 32, "y": 0, "w": 32, "h": 32 },
+            "rotated": false,
+            "trimmed": false,
+            "pivot": { "x": 0.25, "y": 0.75 }
+        }
+    ],
+    "meta": { "image": "hero.png", "size": { "w": 64, "h": 32 } }
+}"#;
+
+// both frames are cut out of the bottom half of a 32 by 32 sprite
+const TRIMMED_JSON: &str = r#"{
+    "frames": [
+        {
+            "filename": "coin_01.png",
+            "frame": { "x": 0, "y": 0, "w": 16, "h": 16 },
+            "rotated": false,
+            "trimmed": true,
+            "spriteSourceSize": { "x": 8, "y": 16, "w": 16, "h": 16 },
+            "sourceSize": { "w": 32, "h": 32 },
+            "pivot": { "x": 0.5, "y": 1.0 }
+        },
+        {
+            "filename": "coin_02.png",
+            "frame": { "x": 16, "y": 0, "w": 16, "h": 16 },
+            "rotated": false,
+            "trimmed": true,
+            "spriteSourceSize": { "x": 8, "y": 16, "w": 16, "h": 16 },
+            "sourceSize": { "w": 32, "h": 32 }
+        }
+    ],
+    "meta": { "image": "coin.png", "size": { "w": 32, "h": 16 } }
+}"#;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sprite_sheet_reads_aseprite_frames_in_file_order() {
+        let sheet = SpriteSheet::from_json(&aseprite_json()).unwrap();
+
+        assert_eq!(sheet.image(), "player.png");
+        assert_eq!(sheet.frames().len(), 12);
+        assert_eq!(sheet.frames()[10].name(), "player 10");
+        assert_eq!(sheet.frames()[10].region(), Region::new(160, 0, 16, 24));
+        assert!((sheet.frames()[1].duration() - 0.25).abs() < 1e-6);
+        assert_eq!(sheet.frames()[5].pivot(), Some(Vec2::new(0.5, 0.0)));
+    }
+
+    #[test]
+    fn sprite_sheet_turns_tags_into_clips() {
+        let sheet = SpriteSheet::from_json(&aseprite_json()).unwrap();
+
+        let run = sheet.tag("run").unwrap();
+        assert_eq!(run.mode(), PlaybackMode::PingPong);
+        assert_eq!(run.frames().len(), 10);
+        assert_eq!(sheet.tag("rewind").unwrap().frames(), &[2, 1, 0]);
+
+        let bounce = sheet.tag("bounce").unwrap();
+        assert_eq!(bounce.mode(), PlaybackMode::PingPong);
+        assert_eq!(bounce.frames(), &[5, 4, 3]);
+
+        let idle = sheet.clip("idle").unwrap();
+        assert_eq!(idle.mode(), PlaybackMode::Loop);
+        assert_eq!(idle.frames()[1].region(), Region::new(16, 0, 16, 24));
+        assert!((idle.duration() - 0.35).abs() < 1e-6);
+        assert!(sheet.clip("jump").is_none());
+    }
+
+    #[test]
+    fn sprite_sheet_reads_texture_packer_arrays() {
+        let sheet = SpriteSheet::from_json(TEXTURE_PACKER_JSON).unwrap();
+
+        let names: Vec<&str> = sheet.frames().iter().map(|frame| frame.name()).collect();
+        assert_eq!(names, vec!["hero/run_01", "hero/run_02"]);
+        assert_eq!(sheet.frames()[0].pivot(), Some(Vec2::new(0.5, 0.0)));
+        assert_eq!(sheet.frames()[1].pivot(), Some(Vec2::new(0.25, 0.25)));
+        assert!(sheet.tags().is_empty());
+    }
+
+    #[test]
+    fn sprite_sheet_places_trimmed_frames_in_their_sprite() {
+        let sheet = SpriteSheet::from_json(TRIMMED_JSON).unwrap();
+
+        assert_eq!(sheet.frames()[0].pivot(), Some(Vec2::new(0.5, 0.0)));
+        assert_eq!(sheet.frames()[1].pivot(), Some(Vec2::new(0.5, 1.0)));
+    }
+
+    #[test]
+    fn sprite_sheet_rejects_rotated_frames() {
+        let json = TEXTURE_PACKER_JSON.replacen("\"rotated\": false", "\"rotated\": true", 1);
+
+        match SpriteSheet::from_json(&json) {
+            Err(SpriteSheetError::RotatedFrame(name)) => assert_eq!(name, "hero/run_01.png"),
+            _ => panic!("rotated frames are not supported"),
+        }
+    }
+}
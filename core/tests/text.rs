@@ -0,0 +1,157 @@
+extern crate core;
+extern crate math;
+
+mod common;
+
+use core::component::Text;
+use core::component::TextAlignment;
+use core::BitmapFont;
+use core::Font;
+use core::FontError;
+use core::Region;
+use core::TextLayout;
+use math::Vec2;
+
+// A, B and V are 7 pixels wide on the first page, g hangs below the baseline on the second
+const BITMAP_FONT: &str = r#"info face="Pixel" size=8 bold=0 italic=0 charset="" unicode=1 stretchH=100 smooth=0 aa=1 padding=0,0,0,0 spacing=1,1
+common lineHeight=10 base=8 scaleW=64 scaleH=64 pages=2 packed=0
+page id=0 file="pixel_0.png"
+page id=1 file="pixel_1.png"
+chars count=6
+char id=-1   x=56 y=0 width=6 height=8 xoffset=0 yoffset=0 xadvance=7 page=0 chnl=15
+char id=32   x=0  y=0 width=0 height=0 xoffset=0 yoffset=0 xadvance=4 page=0 chnl=15
+char id=65   x=0  y=0 width=6 height=8 xoffset=0 yoffset=0 xadvance=7 page=0 chnl=15
+char id=66   x=8  y=0 width=6 height=8 xoffset=1 yoffset=0 xadvance=7 page=0 chnl=15
+char id=86   x=16 y=0 width=6 height=8 xoffset=0 yoffset=0 xadvance=7 page=0 chnl=15
+char id=103  x=0  y=0 width=5 height=9 xoffset=0 yoffset=3 xadvance=6 page=1 chnl=15
+kernings count=1
+kerning first=65  second=86  amount=-2
+"#;
+
+fn font() -> Font {
+    Font::from_bitmap(BitmapFont::parse(BITMAP_FONT).unwrap())
+}
+
+fn positions(font: &Font, text: &Text) -> Vec<(char, f32, f32)> {
+    TextLayout::new(font, text)
+        .glyphs()
+        .iter()
+        .map(|glyph| (glyph.character(), glyph.position().x, glyph.position().y))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bitmap_font_reads_glyphs_and_kernings() {
+        let font = BitmapFont::parse(BITMAP_FONT).unwrap();
+
+        assert_eq!(font.face(), "Pixel");
+        assert_eq!(
+            (font.size(), font.line_height(), font.base()),
+            (8.0, 10.0, 8.0)
+        );
+        assert_eq!(font.pages(), &["pixel_0.png", "pixel_1.png"]);
+
+        let glyph = font.glyph('g').unwrap();
+        assert_eq!(glyph.page(), 1);
+        assert_eq!(glyph.region(), Region::new(0, 0, 5, 9));
+        assert_eq!((glyph.y_offset(), glyph.advance()), (3.0, 6.0));
+
+        assert_eq!(font.kerning('A', 'V'), -2.0);
+        assert_eq!(font.kerning('V', 'A'), 0.0);
+        assert!(font.glyph('x').is_none());
+
+        let missing_page = BITMAP_FONT.replace("page=1 chnl", "page=2 chnl");
+        assert!(matches!(
+            BitmapFont::parse(&missing_page),
+            Err(FontError::UnknownPage(2))
+        ));
+    }
+
+    #[test]
+    fn text_layout_applies_kerning_and_size() {
+        let font = font();
+
+        let text = Text::new("AV", "pixel").with_size(8.0);
+        assert_eq!(
+            positions(&font, &text),
+            vec![('A', 0.0, -8.0), ('V', 5.0, -8.0)]
+        );
+
+        let layout = TextLayout::new(&font, &text);
+        assert_eq!((layout.width(), layout.height()), (12.0, 10.0));
+
+        // bitmap fonts are scaled from the size they got exported with
+        let text = Text::new("AV", "pixel").with_size(16.0);
+        assert_eq!(
+            positions(&font, &text),
+            vec![('A', 0.0, -16.0), ('V', 10.0, -16.0)]
+        );
+    }
+
+    #[test]
+    fn text_layout_breaks_and_aligns_lines() {
+        let font = font();
+
+        let text = Text::new("AB\nA", "pixel")
+            .with_size(8.0)
+            .with_alignment(TextAlignment::Center)
+            .with_line_spacing(1.5);
+        let layout = TextLayout::new(&font, &text);
+        assert_eq!(layout.lines(), 2);
+        assert_eq!((layout.width(), layout.height()), (14.0, 25.0));
+        assert_eq!(
+            positions(&font, &text),
+            vec![('A', 0.0, -8.0), ('B', 7.0, -8.0), ('A', 3.5, -23.0)]
+        );
+    }
+
+    #[test]
+    fn text_layout_wraps_words_into_bounds() {
+        let font = font();
+
+        let text = Text::new("AB AB AB", "pixel")
+            .with_size(8.0)
+            .with_bounds(Vec2::new(30.0, 100.0))
+            .with_alignment(TextAlignment::Right);
+        let layout = TextLayout::new(&font, &text);
+        assert_eq!(layout.lines(), 3);
+        assert_eq!(layout.line_width(1), 14.0);
+        assert_eq!((layout.width(), layout.height()), (30.0, 100.0));
+        assert_eq!(
+            positions(&font, &text)[2..4],
+            [('A', 16.0, -18.0), ('B', 23.0, -18.0)]
+        );
+
+        // words longer than the bounds are broken between characters
+        let text = Text::new("AAAAAA", "pixel")
+            .with_size(8.0)
+            .with_bounds(Vec2::new(21.0, 100.0));
+        let layout = TextLayout::new(&font, &text);
+        assert_eq!(layout.lines(), 2);
+        assert_eq!(layout.glyphs()[3].position(), Vec2::new(0.0, -18.0));
+
+        // lines below the bounds are cut off
+        let text = Text::new("AB AB AB", "pixel")
+            .with_size(8.0)
+            .with_bounds(Vec2::new(30.0, 25.0));
+        let layout = TextLayout::new(&font, &text);
+        assert_eq!(layout.lines(), 2);
+        assert_eq!(layout.glyphs().len(), 4);
+    }
+
+    #[test]
+    fn engine_lays_out_text_with_added_fonts() {
+        let mut engine = common::headless_engine();
+        engine.add_font("pixel", font());
+
+        let text = Text::new("AB", "pixel").with_size(8.0);
+        let layout = engine.layout_text(&text).unwrap();
+        assert_eq!(layout.width(), 14.0);
+
+        assert!(engine.layout_text(&Text::new("AB", "serif")).is_none());
+    }
+}
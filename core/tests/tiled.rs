@@ -0,0 +1,326 @@
+extern crate core;
+extern crate math;
+
+mod common;
+
+use common::Scene;
+use core::component::MapObject;
+use core::component::Property;
+use core::component::Sprite;
+use core::component::Tile;
+use core::component::Tilemap;
+use core::component::Transform;
+use core::Prefab;
+use core::Region;
+use core::TiledError;
+use core::TiledMap;
+use math::Vec2;
+use math::Vec3;
+use math::Vec4;
+
+// the ground layer holds 1, 2 flipped horizontally, 0, 5, 0 and 3 flipped diagonally and horizontally
+const JSON_MAP: &str = r#"{
+    "width": 3,
+    "height": 2,
+    "tilewidth": 16,
+    "tileheight": 16,
+    "orientation": "orthogonal",
+    "infinite": false,
+    "tilesets": [
+        {
+            "firstgid": 5,
+            "name": "props",
+            "image": "props.png",
+            "imagewidth": 32,
+            "imageheight": 32,
+            "tilewidth": 16,
+            "tileheight": 16,
+            "tilecount": 4
+        },
+        {
+            "firstgid": 1,
+            "name": "terrain",
+            "image": "terrain.png",
+            "imagewidth": 64,
+            "imageheight": 16,
+            "tilewidth": 16,
+            "tileheight": 16
+        }
+    ],
+    "layers": [
+        {
+            "type": "tilelayer",
+            "name": "ground",
+            "width": 3,
+            "height": 2,
+            "encoding": "base64",
+            "data": "AQAAAAIAAIAAAAAABQAAAAAAAAADAACg",
+            "properties": [{ "name": "layer", "type": "int", "value": -1 }]
+        },
+        {
+            "type": "group",
+            "name": "details",
+            "opacity": 0.5,
+            "layers": [
+                {
+                    "type": "tilelayer",
+                    "name": "decoration",
+                    "width": 3,
+                    "height": 2,
+                    "data": [0, 0, 4, 0, 0, 0]
+                }
+            ]
+        },
+        {
+            "type": "objectgroup",
+            "name": "objects",
+            "objects": [
+                {
+                    "id": 1,
+                    "name": "spawn",
+                    "type": "player",
+                    "x": 32,
+                    "y": 16,
+                    "width": 16,
+                    "height": 32,
+                    "properties": [
+                        { "name": "health", "type": "int", "value": 3 },
+                        { "name": "sprite.layer", "type": "int", "value": 2 }
+                    ]
+                },
+                {
+                    "id": 2,
+                    "name": "crate",
+                    "gid": 2147483654,
+                    "x": 0,
+                    "y": 32,
+                    "width": 32,
+                    "height": 32
+                },
+                {
+                    "id": 3,
+                    "name": "exit",
+                    "point": true,
+                    "x": 48,
+                    "y": 8
+                }
+            ]
+        }
+    ]
+}"#;
+
+// the floor layer holds 0, 1 flipped vertically, 2 and 3
+const TMX_MAP: &str = r##"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="2" height="2" tilewidth="8" tileheight="8" infinite="0">
+ <properties>
+  <property name="music" type="file" value="theme.ogg"/>
+  <property name="tint" type="color" value="#ff336699"/>
+ </properties>
+ <tileset firstgid="1" name="dungeon" tilewidth="8" tileheight="8" spacing="1" margin="1" tilecount="4" columns="2">
+  <image source="dungeon.png" width="19" height="19"/>
+ </tileset>
+ <layer id="1" name="floor" width="2" height="2">
+  <data encoding="base64" compression="zlib">
+   eJxjYGBgYGRgcGAC0sxADAACeABH
+  </data>
+ </layer>
+ <group id="2" name="hidden" visible="0">
+  <layer id="3" name="walls" width="2" height="2">
+   <data encoding="base64" compression="gzip">
+    H4sIADF/1WoC/2NgYGBgZGBwYALSzEAMAK6sHPkQAAAA
+   </data>
+  </layer>
+  <layer id="4" name="roof" width="2" height="2">
+   <data encoding="csv">
+3,0,
+0,0
+   </data>
+  </layer>
+ </group>
+ <objectgroup id="5" name="objects">
+  <object id="1" name="treasure" type="chest" x="4" y="4" width="8" height="8" rotation="90">
+   <properties>
+    <property name="text">first line
+second line</property>
+   </properties>
+  </object>
+ </objectgroup>
+</map>
+"##;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tiled_map_reads_json_tile_layers() {
+        let map = TiledMap::from_json(JSON_MAP).unwrap();
+
+        assert_eq!((map.width(), map.height()), (3, 2));
+        assert_eq!(map.tilesets()[0].name(), "terrain");
+        assert_eq!(map.tilesets()[0].tile_count(), 4);
+        assert_eq!(map.tilesets()[1].first_gid(), 5);
+
+        let tilemaps = map.tilemaps().unwrap();
+        assert_eq!(tilemaps.len(), 2);
+
+        let terrain = &tilemaps[0];
+        assert_eq!(terrain.tileset().sprite(), "terrain.png");
+        let ground = terrain.layer("ground").unwrap();
+        assert_eq!(ground.layer(), -1);
+        assert_eq!(ground.tile(0, 1), Some(Tile::new(0)));
+        assert_eq!(ground.tile(1, 1), Some(Tile::new(1).with_flip(true, false)));
+        assert_eq!(ground.tile(2, 0), Some(Tile::new(2).with_rotation(1)));
+        assert_eq!(ground.tile(0, 0), None);
+
+        let decoration = terrain.layer("decoration").unwrap();
+        assert_eq!(decoration.opacity(), 0.5);
+        assert_eq!(decoration.tile(2, 1), Some(Tile::new(3)));
+
+        let props = &tilemaps[1];
+        assert_eq!(props.layers().len(), 1);
+        assert_eq!(
+            props.layer("ground").unwrap().tile(0, 0),
+            Some(Tile::new(0))
+        );
+    }
+
+    #[test]
+    fn tiled_map_places_objects_in_the_world() {
+        let map = TiledMap::from_json(JSON_MAP).unwrap();
+        let objects = map.objects();
+        assert_eq!(objects.len(), 3);
+
+        let spawn = &objects[0];
+        assert_eq!(spawn.class(), "player");
+        assert_eq!(
+            map.object_transform(spawn).position(),
+            Vec3::new(40.0, 0.0, 0.0)
+        );
+        let object = spawn.map_object();
+        assert_eq!(object.property("health"), Some(&Property::Int(3)));
+        assert_eq!(object.property("sprite.layer"), None);
+        assert_eq!(
+            spawn.field_overrides().collect::<Vec<_>>(),
+            vec![("sprite", "layer", &Property::Int(2))]
+        );
+
+        let crate_object = &objects[1];
+        let transform = map.object_transform(crate_object);
+        assert_eq!(transform.position(), Vec3::new(16.0, 16.0, 0.0));
+        assert_eq!(transform.scale(), Vec2::new(2.0, 2.0));
+        let sprite = map.object_sprite(crate_object).unwrap();
+        assert_eq!(sprite.name(), "props.png");
+        assert_eq!(sprite.region(), Some(Region::new(16, 0, 16, 16)));
+        assert!(sprite.flip_x());
+
+        assert_eq!(
+            map.object_transform(&objects[2]).position(),
+            Vec3::new(48.0, 24.0, 0.0)
+        );
+        assert!(map.object_sprite(&objects[2]).is_none());
+    }
+
+    #[test]
+    fn tiled_map_reads_tmx_files() {
+        let map = TiledMap::from_tmx(TMX_MAP).unwrap();
+
+        assert_eq!(
+            map.property("music"),
+            Some(&Property::File("theme.ogg".to_owned()))
+        );
+        assert_eq!(
+            map.property("tint"),
+            Some(&Property::Color(Vec4::new(0.2, 0.4, 0.6, 1.0)))
+        );
+
+        let tileset = map.tilesets()[0].tileset();
+        assert_eq!((tileset.margin(), tileset.spacing()), (1, 1));
+
+        let tilemaps = map.tilemaps().unwrap();
+        let floor = tilemaps[0].layer("floor").unwrap();
+        assert_eq!(floor.tile(0, 1), None);
+        assert_eq!(floor.tile(1, 1), Some(Tile::new(0).with_flip(false, true)));
+        assert_eq!(floor.tile(0, 0), Some(Tile::new(1)));
+        assert_eq!(floor.tile(1, 0), Some(Tile::new(2)));
+
+        let walls = tilemaps[0].layer("walls").unwrap();
+        assert!(!walls.is_visible());
+        assert_eq!(walls.tiles(), floor.tiles());
+        assert_eq!(
+            tilemaps[0].layer("roof").unwrap().tile(0, 1),
+            Some(Tile::new(2))
+        );
+
+        let treasure = &map.objects()[0];
+        assert_eq!(treasure.class(), "chest");
+        assert_eq!(
+            treasure.property("text"),
+            Some(&Property::String("first line\nsecond line".to_owned()))
+        );
+        let transform = map.object_transform(treasure);
+        let position = transform.position();
+        assert!(position.x.abs() < 1e-4 && (position.y - 8.0).abs() < 1e-4);
+        assert!((transform.rotation().x + std::f32::consts::FRAC_PI_2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn tiled_map_rejects_unsupported_maps() {
+        let isometric = TMX_MAP.replace("orthogonal", "isometric");
+        match TiledMap::from_tmx(&isometric) {
+            Err(TiledError::UnsupportedOrientation(orientation)) => {
+                assert_eq!(orientation, "isometric")
+            }
+            _ => panic!("only orthogonal maps are supported"),
+        }
+
+        let infinite = JSON_MAP.replace("\"infinite\": false", "\"infinite\": true");
+        assert!(matches!(
+            TiledMap::from_json(&infinite),
+            Err(TiledError::InfiniteMap)
+        ));
+
+        let unknown = JSON_MAP.replace("[0, 0, 4, 0, 0, 0]", "[0, 0, 40, 0, 0, 0]");
+        let map = TiledMap::from_json(&unknown).unwrap();
+        assert!(matches!(map.tilemaps(), Err(TiledError::UnknownTile(40))));
+    }
+
+    #[test]
+    fn tiled_map_spawns_entities_from_prefabs() {
+        let mut engine = common::headless_engine();
+        let player = Prefab::new()
+            .with_component("sprite", &Sprite::new("hero"))
+            .unwrap()
+            .with_component("transform", &Transform::default())
+            .unwrap();
+        engine.add_prefab("player", player);
+
+        let map = TiledMap::from_json(JSON_MAP).unwrap();
+        engine.add_tiled_map(Scene::Main, &map).unwrap();
+
+        let world = engine.world(Scene::Main).unwrap();
+        let entities: Vec<_> = world.iter_entities().collect();
+        assert_eq!(entities.len(), 5);
+
+        let tilemaps = entities
+            .iter()
+            .filter(|entity| world.get_component::<Tilemap>(**entity).is_some())
+            .count();
+        assert_eq!(tilemaps, 2);
+
+        let spawn = entities
+            .iter()
+            .find(|entity| {
+                world
+                    .get_component::<MapObject>(**entity)
+                    .map_or(false, |object| object.name() == "spawn")
+            })
+            .unwrap();
+        let sprite = world.get_component::<Sprite>(*spawn).unwrap();
+        assert_eq!((sprite.name(), sprite.layer()), ("hero", 2));
+        assert_eq!(
+            world.get_component::<Transform>(*spawn).unwrap().position(),
+            Vec3::new(40.0, 0.0, 0.0)
+        );
+    }
+}
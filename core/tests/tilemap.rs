@@ -0,0 +1,108 @@
+extern crate core;
+extern crate math;
+
+mod common;
+
+use common::Scene;
+use core::component::Tile;
+use core::component::TileLayer;
+use core::component::Tilemap;
+use core::component::Tileset;
+use core::component::Transform;
+use core::Prefab;
+use core::Region;
+use math::Vec2;
+use math::Vec3;
+
+fn tilemap() -> Tilemap {
+    let mut ground = TileLayer::new("ground", 40, 20).with_layer(-1);
+    ground.fill(Some(Tile::new(0)));
+    ground.set_tile(3, 0, Some(Tile::new(4).with_flip(true, false)));
+
+    Tilemap::new(Tileset::new("tiles", 16, 16), 16, 16)
+        .with_layer(ground)
+        .with_layer(TileLayer::new("decoration", 40, 20).with_opacity(0.5))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tileset_cuts_image_into_tiles() {
+        let tileset = Tileset::new("tiles", 16, 16).with_margin(1).with_spacing(2);
+
+        assert_eq!(tileset.count(54, 36), 6);
+        assert_eq!(tileset.region(4, 54, 36), Some(Region::new(19, 19, 16, 16)));
+        assert_eq!(tileset.region(6, 54, 36), None);
+        assert_eq!(tileset.count(8, 8), 0);
+    }
+
+    #[test]
+    fn tile_layer_sets_tiles_inside_of_it() {
+        let mut layer = TileLayer::new("ground", 4, 3);
+
+        assert!(layer.set_tile(3, 2, Some(Tile::new(7).with_rotation(5))));
+        assert!(!layer.set_tile(4, 0, Some(Tile::new(1))));
+        assert!(!layer.set_tile(-1, 0, Some(Tile::new(1))));
+
+        let tile = layer.tile(3, 2).unwrap();
+        assert_eq!((tile.index(), tile.rotation()), (7, 1));
+        assert_eq!(layer.tiles()[11], Some(tile));
+        assert_eq!(layer.tile(0, 0), None);
+        assert_eq!(layer.tile(0, 3), None);
+    }
+
+    #[test]
+    fn tilemap_converts_between_world_and_tiles() {
+        let tilemap = tilemap();
+        let transform = Transform::new(
+            Vec3::new(100.0, 50.0, 0.0),
+            Vec2::zero(),
+            Vec2::new(2.0, 2.0),
+        );
+
+        assert_eq!(
+            tilemap.tile_to_world(&transform, 1, 2),
+            Vec2::new(148.0, 130.0)
+        );
+        assert_eq!(
+            tilemap.world_to_tile(&transform, Vec2::new(148.0, 130.0)),
+            (1, 2)
+        );
+        assert_eq!(
+            tilemap.world_to_tile(&transform, Vec2::new(99.0, 50.0)),
+            (-1, 0)
+        );
+
+        let rotated = Transform::new(
+            Vec3::zero(),
+            Vec2::new(std::f32::consts::FRAC_PI_2, 0.0),
+            Vec2::one(),
+        );
+        let center = tilemap.tile_to_world(&rotated, 2, 0);
+        assert!((center.x + 8.0).abs() < 1e-4 && (center.y - 40.0).abs() < 1e-4);
+        assert_eq!(tilemap.world_to_tile(&rotated, center), (2, 0));
+    }
+
+    #[test]
+    fn tilemap_survives_prefab_instantiation() {
+        let mut engine = common::headless_engine();
+        let tilemap = tilemap();
+        let prefab = Prefab::new().with_component("tilemap", &tilemap).unwrap();
+        engine.add_prefab("level", prefab);
+
+        let entity = engine
+            .instantiate(Scene::Main, "level", Prefab::new())
+            .unwrap();
+
+        let world = engine.world(Scene::Main).unwrap();
+        let instance = world.get_component::<Tilemap>(entity).unwrap();
+        assert_eq!(*instance, tilemap);
+        assert_eq!(instance.layer("ground").unwrap().layer(), -1);
+        assert_eq!(
+            instance.layer("ground").unwrap().tile(3, 0),
+            Some(Tile::new(4).with_flip(true, false))
+        );
+    }
+}
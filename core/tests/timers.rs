@@ -0,0 +1,103 @@
+extern crate core;
+
+mod common;
+
+use common::Scene;
+use core::component::Timer;
+use core::resource::Events;
+use core::resource::Tick;
+use core::resource::Timers;
+use core::Engine;
+use core::EventReader;
+use core::TimerFinished;
+use core::WindowConfig;
+
+struct Fired(u32);
+
+fn headless_engine() -> Engine<Scene> {
+    let mut engine = common::headless_engine();
+    engine.add_resource(Fired(0));
+    engine
+}
+
+fn start_timer(engine: &mut Engine<Scene>, name: &str, timer: Timer) {
+    engine
+        .resources_mut()
+        .get_mut::<Timers>()
+        .unwrap()
+        .start_with(name, timer, |resources| {
+            if let Some(ref mut fired) = resources.get_mut::<Fired>() {
+                fired.0 += 1;
+            }
+        });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn timers_repeating_callback() {
+        let mut engine = headless_engine();
+        start_timer(&mut engine, "spawn", Timer::repeating(0.25));
+
+        engine.step_frames(70).unwrap();
+
+        assert_eq!(engine.resources().get::<Fired>().unwrap().0, 4);
+    }
+
+    #[test]
+    fn timers_send_finished_events() {
+        let mut engine = headless_engine();
+        start_timer(&mut engine, "cooldown", Timer::once(0.1));
+        let mut reader = EventReader::<TimerFinished>::new();
+
+        engine.step_frames(10).unwrap();
+
+        let events = engine.resources().get::<Events<TimerFinished>>().unwrap();
+        let names: Vec<&str> = events.read(&mut reader).map(|event| event.name()).collect();
+        assert_eq!(names, vec!["cooldown"]);
+    }
+
+    #[test]
+    fn timers_respect_pause_and_time_scale() {
+        let mut engine = headless_engine();
+        start_timer(&mut engine, "cooldown", Timer::once(1.0));
+
+        engine.resources_mut().get_mut::<Tick>().unwrap().pause();
+        engine.step_frames(60).unwrap();
+        assert_eq!(engine.resources().get::<Fired>().unwrap().0, 0);
+
+        {
+            let mut tick = engine.resources_mut().get_mut::<Tick>().unwrap();
+            tick.resume();
+            tick.set_time_scale(2.0);
+        }
+        engine.step_frames(15).unwrap();
+
+        let timers = engine.resources().get::<Timers>().unwrap();
+        let fraction = timers.get("cooldown").unwrap().fraction();
+        assert!((fraction - 0.5).abs() < 1e-3);
+
+        let tick = engine.resources().get::<Tick>().unwrap();
+        assert!((tick.time() - 75.0 / 60.0).abs() < 1e-4);
+        assert!((tick.scaled_time() - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn timers_tick_in_scenes_of_other_windows() {
+        let mut engine = headless_engine();
+        engine.create_scene(Scene::Editor);
+        engine.create_window(WindowConfig::default());
+        let window = engine.create_window(WindowConfig::default());
+        engine.set_scene_window(Scene::Editor, window);
+        engine.add_entities(Scene::Editor, (), vec![(Timer::once(1.0),)]);
+
+        engine.step_frames(30).unwrap();
+
+        let world = engine.world(Scene::Editor).unwrap();
+        let entity = world.iter_entities().next().unwrap();
+        let fraction = world.get_component::<Timer>(entity).unwrap().fraction();
+        assert!((fraction - 0.5).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,61 @@
+extern crate core;
+
+mod common;
+
+use std::thread;
+
+use common::headless_engine;
+use common::Scene;
+use core::resource::EventSender;
+use core::resource::UserEvents;
+use core::Engine;
+
+struct Ping(u32);
+
+struct Unregistered;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn user_events_arrive_from_other_threads() {
+        let mut engine = headless_engine();
+        engine.register_event::<Ping>();
+
+        let sender = EventSender::clone(&engine.resources().get::<EventSender>().unwrap());
+        let workers: Vec<_> = (0..3)
+            .map(|id| {
+                let sender = sender.clone();
+                thread::spawn(move || sender.push(Ping(id)).unwrap())
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        assert!(sender.push(Unregistered).is_err());
+
+        engine.step().unwrap();
+        {
+            let user_events = engine.resources().get::<UserEvents>().unwrap();
+            let mut pings: Vec<u32> = user_events.read::<Ping>().map(|ping| ping.0).collect();
+            pings.sort();
+            assert_eq!(pings, vec![0, 1, 2]);
+            assert_eq!(user_events.len(), 3);
+        }
+
+        // only kept for the frame they arrived in
+        engine.step().unwrap();
+        assert!(engine.resources().get::<UserEvents>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn user_events_can_be_sent_before_the_engine_runs() {
+        let mut engine = Engine::<Scene>::new().unwrap();
+        engine.register_event::<Ping>();
+
+        let sender = engine.resources().get::<EventSender>().unwrap();
+        assert!(sender.push(Ping(0)).is_ok());
+        assert!(sender.push(Unregistered).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 extern crate legion;
 
+pub use crate::legion::entity::Entity;
 pub use crate::legion::query::IntoQuery;
 pub use crate::legion::query::Query;
 pub use crate::legion::query::Read;
@@ -7,6 +8,8 @@ pub use crate::legion::query::Tagged;
 pub use crate::legion::query::TryRead;
 pub use crate::legion::query::TryWrite;
 pub use crate::legion::query::Write;
+pub use crate::legion::systems::resource::Resources;
 pub use crate::legion::systems::schedule::Schedulable;
 pub use crate::legion::systems::schedule::Schedule;
 pub use crate::legion::systems::SystemBuilder;
+pub use crate::legion::world::World;
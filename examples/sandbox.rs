@@ -1,43 +1,81 @@
 extern crate pandix;
+extern crate serde;
 
 use pandix::ecs::*;
 use pandix::*;
+use serde::Deserialize;
+use serde::Serialize;
 
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 enum Scene {
-    Main = 0,
-    Test = 1,
+    Main,
+    Test,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 struct Test(i32);
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 struct Static;
 
 fn main() {
-    let mut engine = Engine::new();
-    engine.create_scene(Scene::Main as u8);
-    engine.create_scene(Scene::Test as u8);
+    let engine = Engine::builder()
+        .window(WindowConfig::new("sandbox", 400, 400).with_resizable(true))
+        .scene(Scene::Main)
+        .scene(Scene::Test)
+        .build();
+
+    let mut engine = match engine {
+        Ok(engine) => engine,
+        Err(error) => {
+            eprintln!("{}", error);
+            return;
+        }
+    };
 
     engine.add_sprite("whatever", "whatever");
     engine.add_sprite("whatever2", "whatever2");
 
     engine.add_entities(
-        Scene::Main as u8,
+        Scene::Main,
         (Static,),
         vec![
             (Test(0), component::Sprite::new("whatever")),
             (Test(2), component::Sprite::new("whatever2")),
         ],
     );
-    engine.add_entities(Scene::Main as u8, (Static,), vec![(Test(1),), (Test(2),)]);
-    engine.add_entities(Scene::Main as u8, (Static,), vec![(Test(3),)]);
+    engine.add_entities(Scene::Main, (Static,), vec![(Test(1),), (Test(2),)]);
+    engine.add_entities(Scene::Main, (Static,), vec![(Test(3),)]);
     engine.add_entities(
-        Scene::Main as u8,
+        Scene::Main,
         (Static,),
         vec![(Test(4),), (Test(5),), (Test(6),)],
     );
 
+    engine.register_component::<Test>("test");
+    engine.register_tag::<Static>("static");
+
+    let enemy = Prefab::new()
+        .with_component("test", &Test(7))
+        .and_then(|prefab| prefab.with_component("transform", &component::Transform::default()))
+        .and_then(|prefab| prefab.with_tag("static", &Static));
+
+    match enemy {
+        Ok(enemy) => engine.add_prefab("enemy", enemy),
+        Err(error) => eprintln!("{:?}", error),
+    }
+
+    for x in 0..3 {
+        let position = math::Vec3::new(x as f32 * 32.0, 0.0, 0.0);
+        let instance = Prefab::new()
+            .with_field("transform", "position", &position)
+            .and_then(|overrides| engine.instantiate(Scene::Main, "enemy", overrides));
+
+        if let Err(error) = instance {
+            eprintln!("{:?}", error);
+        }
+    }
+
     let key_system_test_scheduleable = SystemBuilder::new("door")
         .read_resource::<resource::Input>()
         .read_resource::<resource::Tick>()
@@ -51,6 +89,10 @@ fn main() {
         .add_system(key_system_test_scheduleable)
         .build();
 
-    engine.add_system(key_system_test);
-    engine.run();
+    engine
+        .add_system(SystemDescriptor::new("key_test", key_system_test).in_stage(Stage::PostUpdate));
+
+    if let Err(error) = engine.run() {
+        eprintln!("{}", error);
+    }
 }
@@ -1,4 +1,13 @@
 extern crate gl;
+extern crate image;
+
+use image::imageops;
+use image::RgbaImage;
+
+#[derive(Debug)]
+pub enum ApiError {
+    LoadingFunctionsFailed,
+}
 
 pub fn enable_depth_test() {
     unsafe {
@@ -12,21 +21,646 @@ pub fn disable_depth_test() {
     }
 }
 
+// the write masks and the scissor rectangle apply to clearing as well
 pub fn clear() {
     unsafe {
-        gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+        gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT | gl::STENCIL_BUFFER_BIT);
     }
 }
 
 pub fn clear_color(r: f32, g: f32, b: f32, a: f32) {
     unsafe {
-        gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
         gl::ClearColor(r, g, b, a);
     }
+    clear();
+}
+
+pub fn max_texture_size() -> u32 {
+    let mut size = 0;
+
+    unsafe {
+        gl::GetIntegerv(gl::MAX_TEXTURE_SIZE, &mut size);
+    }
+
+    size.max(0) as u32
+}
+
+// the most samples of multisampled framebuffers
+pub fn max_samples() -> u32 {
+    let mut samples = 0;
+
+    unsafe {
+        gl::GetIntegerv(gl::MAX_SAMPLES, &mut samples);
+    }
+
+    samples.max(0) as u32
+}
+
+// reads from the bound framebuffer, the window when none is bound, the first row of the image is
+// the top of the rectangle
+pub fn read_pixels(rect: Rect) -> RgbaImage {
+    let width = rect.width.max(0) as u32;
+    let height = rect.height.max(0) as u32;
+    let mut pixels = vec![0u8; (width * height * 4) as usize];
+
+    unsafe {
+        gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+        gl::ReadPixels(
+            rect.x,
+            rect.y,
+            width as i32,
+            height as i32,
+            gl::RGBA,
+            gl::UNSIGNED_BYTE,
+            pixels.as_mut_ptr() as *mut std::ffi::c_void,
+        );
+    }
+
+    match RgbaImage::from_raw(width, height, pixels) {
+        Some(image) => imageops::flip_vertical(&image),
+        None => RgbaImage::new(width, height),
+    }
+}
+
+pub fn viewport(x: i32, y: i32, width: i32, height: i32) {
+    unsafe {
+        gl::Viewport(x, y, width, height);
+    }
+}
+
+// blends with straight alpha
+pub fn enable_blending() {
+    unsafe {
+        gl::Enable(gl::BLEND);
+        gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+    }
+}
+
+pub fn disable_blending() {
+    unsafe {
+        gl::Disable(gl::BLEND);
+    }
+}
+
+// draws with the bound vertex array and its u32 index buffer
+pub fn draw_indexed(index_count: usize, first_index: usize) {
+    unsafe {
+        gl::DrawElements(
+            gl::TRIANGLES,
+            index_count as i32,
+            gl::UNSIGNED_INT,
+            (first_index * std::mem::size_of::<u32>()) as *const std::ffi::c_void,
+        );
+    }
+}
+
+// draws triangles from the bound vertex array without indices
+pub fn draw_arrays(vertex_count: usize, first_vertex: usize) {
+    unsafe {
+        gl::DrawArrays(gl::TRIANGLES, first_vertex as i32, vertex_count as i32);
+    }
 }
 
 pub fn load_graphic_functions_from_context<F: FnMut(&'static str) -> *const std::ffi::c_void>(
     mut loadfn: F,
-) {
+) -> Result<(), ApiError> {
     gl::load_with(|s| loadfn(s));
+
+    if gl::Clear::is_loaded() && gl::CreateProgram::is_loaded() {
+        Ok(())
+    } else {
+        Err(ApiError::LoadingFunctionsFailed)
+    }
+}
+
+// all blend modes expect colors with straight alpha except for premultiplied, multiply ignores
+// the alpha of the source
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BlendMode {
+    Alpha,
+    Additive,
+    Multiply,
+    Premultiplied,
+}
+
+impl BlendMode {
+    // the source and destination factors of the color and alpha channels
+    fn factors(self) -> (u32, u32, u32, u32) {
+        match self {
+            BlendMode::Alpha => (
+                gl::SRC_ALPHA,
+                gl::ONE_MINUS_SRC_ALPHA,
+                gl::ONE,
+                gl::ONE_MINUS_SRC_ALPHA,
+            ),
+            BlendMode::Additive => (gl::SRC_ALPHA, gl::ONE, gl::ZERO, gl::ONE),
+            BlendMode::Multiply => (gl::DST_COLOR, gl::ZERO, gl::ZERO, gl::ONE),
+            BlendMode::Premultiplied => (
+                gl::ONE,
+                gl::ONE_MINUS_SRC_ALPHA,
+                gl::ONE,
+                gl::ONE_MINUS_SRC_ALPHA,
+            ),
+        }
+    }
+}
+
+// the faces that are not drawn, front faces wind counter clockwise
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CullMode {
+    Back,
+    Front,
+    FrontAndBack,
+}
+
+impl CullMode {
+    fn gl(self) -> u32 {
+        match self {
+            CullMode::Back => gl::BACK,
+            CullMode::Front => gl::FRONT,
+            CullMode::FrontAndBack => gl::FRONT_AND_BACK,
+        }
+    }
+}
+
+// passes when the incoming value compares like this to the stored one
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum CompareFunction {
+    Never,
+    Less,
+    Equal,
+    LessOrEqual,
+    Greater,
+    NotEqual,
+    GreaterOrEqual,
+    Always,
+}
+
+impl CompareFunction {
+    fn gl(self) -> u32 {
+        match self {
+            CompareFunction::Never => gl::NEVER,
+            CompareFunction::Less => gl::LESS,
+            CompareFunction::Equal => gl::EQUAL,
+            CompareFunction::LessOrEqual => gl::LEQUAL,
+            CompareFunction::Greater => gl::GREATER,
+            CompareFunction::NotEqual => gl::NOTEQUAL,
+            CompareFunction::GreaterOrEqual => gl::GEQUAL,
+            CompareFunction::Always => gl::ALWAYS,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum StencilOp {
+    Keep,
+    Zero,
+    Replace,
+    Increment,
+    IncrementWrap,
+    Decrement,
+    DecrementWrap,
+    Invert,
+}
+
+impl StencilOp {
+    fn gl(self) -> u32 {
+        match self {
+            StencilOp::Keep => gl::KEEP,
+            StencilOp::Zero => gl::ZERO,
+            StencilOp::Replace => gl::REPLACE,
+            StencilOp::Increment => gl::INCR,
+            StencilOp::IncrementWrap => gl::INCR_WRAP,
+            StencilOp::Decrement => gl::DECR,
+            StencilOp::DecrementWrap => gl::DECR_WRAP,
+            StencilOp::Invert => gl::INVERT,
+        }
+    }
+}
+
+// the stencil test of both faces, the reference is compared with the stored value after masking
+// both with the read mask
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct StencilState {
+    function: CompareFunction,
+    reference: i32,
+    read_mask: u32,
+    write_mask: u32,
+    // when the stencil test fails, when it passes but the depth test fails and when both pass
+    fail: StencilOp,
+    depth_fail: StencilOp,
+    pass: StencilOp,
+}
+
+impl StencilState {
+    pub fn new(function: CompareFunction, reference: i32) -> Self {
+        Self {
+            function,
+            reference,
+            read_mask: !0,
+            write_mask: !0,
+            fail: StencilOp::Keep,
+            depth_fail: StencilOp::Keep,
+            pass: StencilOp::Keep,
+        }
+    }
+
+    pub fn with_read_mask(mut self, read_mask: u32) -> Self {
+        self.read_mask = read_mask;
+        self
+    }
+
+    pub fn with_write_mask(mut self, write_mask: u32) -> Self {
+        self.write_mask = write_mask;
+        self
+    }
+
+    pub fn with_ops(mut self, fail: StencilOp, depth_fail: StencilOp, pass: StencilOp) -> Self {
+        self.fail = fail;
+        self.depth_fail = depth_fail;
+        self.pass = pass;
+        self
+    }
+
+    pub fn function(&self) -> CompareFunction {
+        self.function
+    }
+
+    pub fn reference(&self) -> i32 {
+        self.reference
+    }
+
+    pub fn read_mask(&self) -> u32 {
+        self.read_mask
+    }
+
+    pub fn write_mask(&self) -> u32 {
+        self.write_mask
+    }
+
+    pub fn ops(&self) -> (StencilOp, StencilOp, StencilOp) {
+        (self.fail, self.depth_fail, self.pass)
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PolygonMode {
+    Fill,
+    Line,
+    Point,
+}
+
+impl PolygonMode {
+    fn gl(self) -> u32 {
+        match self {
+            PolygonMode::Fill => gl::FILL,
+            PolygonMode::Line => gl::LINE,
+            PolygonMode::Point => gl::POINT,
+        }
+    }
+}
+
+// in pixels from the bottom left corner of the framebuffer
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, width: i32, height: i32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+// the fixed function state of draw calls, none turns the test or feature off
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct RenderState {
+    blend_mode: Option<BlendMode>,
+    cull_mode: Option<CullMode>,
+    depth_test: Option<CompareFunction>,
+    depth_write: bool,
+    color_write: [bool; 4],
+    scissor: Option<Rect>,
+    stencil: Option<StencilState>,
+    polygon_mode: PolygonMode,
+}
+
+// the defaults of a new context
+impl Default for RenderState {
+    fn default() -> Self {
+        Self {
+            blend_mode: None,
+            cull_mode: None,
+            depth_test: None,
+            depth_write: true,
+            color_write: [true; 4],
+            scissor: None,
+            stencil: None,
+            polygon_mode: PolygonMode::Fill,
+        }
+    }
+}
+
+impl RenderState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_blend_mode(mut self, blend_mode: Option<BlendMode>) -> Self {
+        self.blend_mode = blend_mode;
+        self
+    }
+
+    pub fn with_cull_mode(mut self, cull_mode: Option<CullMode>) -> Self {
+        self.cull_mode = cull_mode;
+        self
+    }
+
+    pub fn with_depth_test(mut self, depth_test: Option<CompareFunction>) -> Self {
+        self.depth_test = depth_test;
+        self
+    }
+
+    pub fn with_depth_write(mut self, depth_write: bool) -> Self {
+        self.depth_write = depth_write;
+        self
+    }
+
+    // of the red, green, blue and alpha channels
+    pub fn with_color_write(mut self, color_write: [bool; 4]) -> Self {
+        self.color_write = color_write;
+        self
+    }
+
+    pub fn with_scissor(mut self, scissor: Option<Rect>) -> Self {
+        self.scissor = scissor;
+        self
+    }
+
+    pub fn with_stencil(mut self, stencil: Option<StencilState>) -> Self {
+        self.stencil = stencil;
+        self
+    }
+
+    pub fn with_polygon_mode(mut self, polygon_mode: PolygonMode) -> Self {
+        self.polygon_mode = polygon_mode;
+        self
+    }
+
+    pub fn blend_mode(&self) -> Option<BlendMode> {
+        self.blend_mode
+    }
+
+    pub fn cull_mode(&self) -> Option<CullMode> {
+        self.cull_mode
+    }
+
+    pub fn depth_test(&self) -> Option<CompareFunction> {
+        self.depth_test
+    }
+
+    pub fn depth_write(&self) -> bool {
+        self.depth_write
+    }
+
+    pub fn color_write(&self) -> [bool; 4] {
+        self.color_write
+    }
+
+    pub fn scissor(&self) -> Option<Rect> {
+        self.scissor
+    }
+
+    pub fn stencil(&self) -> Option<StencilState> {
+        self.stencil
+    }
+
+    pub fn polygon_mode(&self) -> PolygonMode {
+        self.polygon_mode
+    }
+
+    // the changes that turn the previous state into this one, all of them when it is unknown
+    pub fn changes(&self, previous: Option<&RenderState>) -> Vec<StateChange> {
+        let changed = |same: fn(&RenderState, &RenderState) -> bool| match previous {
+            Some(previous) => !same(previous, self),
+            None => true,
+        };
+
+        let mut changes = Vec::new();
+        if changed(|lhs, rhs| lhs.blend_mode == rhs.blend_mode) {
+            changes.push(StateChange::BlendMode(self.blend_mode));
+        }
+        if changed(|lhs, rhs| lhs.cull_mode == rhs.cull_mode) {
+            changes.push(StateChange::CullMode(self.cull_mode));
+        }
+        if changed(|lhs, rhs| lhs.depth_test == rhs.depth_test) {
+            changes.push(StateChange::DepthTest(self.depth_test));
+        }
+        if changed(|lhs, rhs| lhs.depth_write == rhs.depth_write) {
+            changes.push(StateChange::DepthWrite(self.depth_write));
+        }
+        if changed(|lhs, rhs| lhs.color_write == rhs.color_write) {
+            changes.push(StateChange::ColorWrite(self.color_write));
+        }
+        if changed(|lhs, rhs| lhs.scissor == rhs.scissor) {
+            changes.push(StateChange::Scissor(self.scissor));
+        }
+        if changed(|lhs, rhs| lhs.stencil == rhs.stencil) {
+            changes.push(StateChange::Stencil(self.stencil));
+        }
+        if changed(|lhs, rhs| lhs.polygon_mode == rhs.polygon_mode) {
+            changes.push(StateChange::PolygonMode(self.polygon_mode));
+        }
+
+        changes
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum StateChange {
+    BlendMode(Option<BlendMode>),
+    CullMode(Option<CullMode>),
+    DepthTest(Option<CompareFunction>),
+    DepthWrite(bool),
+    ColorWrite([bool; 4]),
+    Scissor(Option<Rect>),
+    Stencil(Option<StencilState>),
+    PolygonMode(PolygonMode),
+    Viewport(Rect),
+}
+
+pub fn set_blend_mode(blend_mode: Option<BlendMode>) {
+    match blend_mode {
+        Some(blend_mode) => {
+            let (source, destination, source_alpha, destination_alpha) = blend_mode.factors();
+            unsafe {
+                gl::Enable(gl::BLEND);
+                gl::BlendFuncSeparate(source, destination, source_alpha, destination_alpha);
+            }
+        }
+        None => disable_blending(),
+    }
+}
+
+pub fn set_cull_mode(cull_mode: Option<CullMode>) {
+    unsafe {
+        match cull_mode {
+            Some(cull_mode) => {
+                gl::Enable(gl::CULL_FACE);
+                gl::CullFace(cull_mode.gl());
+            }
+            None => gl::Disable(gl::CULL_FACE),
+        }
+    }
+}
+
+pub fn set_depth_test(depth_test: Option<CompareFunction>) {
+    match depth_test {
+        Some(function) => {
+            enable_depth_test();
+            unsafe {
+                gl::DepthFunc(function.gl());
+            }
+        }
+        None => disable_depth_test(),
+    }
+}
+
+pub fn set_depth_write(depth_write: bool) {
+    unsafe {
+        gl::DepthMask(depth_write as u8);
+    }
+}
+
+pub fn set_color_write(color_write: [bool; 4]) {
+    let [red, green, blue, alpha] = color_write;
+    unsafe {
+        gl::ColorMask(red as u8, green as u8, blue as u8, alpha as u8);
+    }
+}
+
+pub fn set_scissor(scissor: Option<Rect>) {
+    unsafe {
+        match scissor {
+            Some(rect) => {
+                gl::Enable(gl::SCISSOR_TEST);
+                gl::Scissor(rect.x, rect.y, rect.width, rect.height);
+            }
+            None => gl::Disable(gl::SCISSOR_TEST),
+        }
+    }
+}
+
+pub fn set_stencil(stencil: Option<StencilState>) {
+    unsafe {
+        match stencil {
+            Some(stencil) => {
+                gl::Enable(gl::STENCIL_TEST);
+                gl::StencilFunc(stencil.function.gl(), stencil.reference, stencil.read_mask);
+                gl::StencilMask(stencil.write_mask);
+                gl::StencilOp(
+                    stencil.fail.gl(),
+                    stencil.depth_fail.gl(),
+                    stencil.pass.gl(),
+                );
+            }
+            None => gl::Disable(gl::STENCIL_TEST),
+        }
+    }
+}
+
+// the scissor test and the write masks also limit what gets cleared
+pub fn reset_masks() {
+    set_scissor(None);
+    set_color_write([true; 4]);
+    set_depth_write(true);
+    unsafe {
+        gl::StencilMask(!0);
+    }
+}
+
+pub fn set_polygon_mode(polygon_mode: PolygonMode) {
+    unsafe {
+        gl::PolygonMode(gl::FRONT_AND_BACK, polygon_mode.gl());
+    }
+}
+
+pub fn apply_change(change: StateChange) {
+    match change {
+        StateChange::BlendMode(blend_mode) => set_blend_mode(blend_mode),
+        StateChange::CullMode(cull_mode) => set_cull_mode(cull_mode),
+        StateChange::DepthTest(depth_test) => set_depth_test(depth_test),
+        StateChange::DepthWrite(depth_write) => set_depth_write(depth_write),
+        StateChange::ColorWrite(color_write) => set_color_write(color_write),
+        StateChange::Scissor(scissor) => set_scissor(scissor),
+        StateChange::Stencil(stencil) => set_stencil(stencil),
+        StateChange::PolygonMode(polygon_mode) => set_polygon_mode(polygon_mode),
+        StateChange::Viewport(rect) => viewport(rect.x, rect.y, rect.width, rect.height),
+    }
+}
+
+// remembers the state of a context to skip calls that would not change it, the state is unknown
+// until the first apply and after invalidating, which is needed when other code changed it
+#[derive(Default)]
+pub struct StateCache {
+    state: Option<RenderState>,
+    viewport: Option<Rect>,
+}
+
+impl StateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // the changes that got applied
+    pub fn apply(&mut self, state: &RenderState) -> Vec<StateChange> {
+        let changes = self.changes(state);
+        for change in &changes {
+            apply_change(*change);
+        }
+        self.state = Some(*state);
+
+        changes
+    }
+
+    pub fn set_viewport(&mut self, rect: Rect) -> Option<StateChange> {
+        let change = self.viewport_change(rect)?;
+        apply_change(change);
+        self.viewport = Some(rect);
+
+        Some(change)
+    }
+
+    pub fn changes(&self, state: &RenderState) -> Vec<StateChange> {
+        state.changes(self.state.as_ref())
+    }
+
+    pub fn viewport_change(&self, rect: Rect) -> Option<StateChange> {
+        if self.viewport == Some(rect) {
+            None
+        } else {
+            Some(StateChange::Viewport(rect))
+        }
+    }
+
+    pub fn state(&self) -> Option<&RenderState> {
+        self.state.as_ref()
+    }
+
+    pub fn viewport(&self) -> Option<Rect> {
+        self.viewport
+    }
+
+    pub fn invalidate(&mut self) {
+        self.state = None;
+        self.viewport = None;
+    }
 }
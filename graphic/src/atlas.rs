@@ -0,0 +1,347 @@
+extern crate image;
+
+use std::collections::HashMap;
+
+use image::RgbaImage;
+
+use crate::region::Region;
+
+// places rectangles on the lowest free spot of a skyline running along the top of the placed ones
+pub struct SkylinePacker {
+    width: u32,
+    height: u32,
+    // x, y and width of every segment, sorted by x and covering the whole width
+    skyline: Vec<(u32, u32, u32)>,
+}
+
+impl SkylinePacker {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            skyline: vec![(0, 0, width)],
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // returns the top left corner, none when the rectangle does not fit anymore
+    pub fn insert(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        if width == 0 || height == 0 {
+            return Some((0, 0));
+        }
+
+        let mut best: Option<(u32, u32)> = None;
+
+        for index in 0..self.skyline.len() {
+            let x = self.skyline[index].0;
+            let y = match self.fits(index, width, height) {
+                Some(y) => y,
+                None => continue,
+            };
+
+            match best {
+                Some((best_x, best_y)) if (best_y, best_x) <= (y, x) => (),
+                _ => best = Some((x, y)),
+            }
+        }
+
+        let (x, y) = best?;
+        self.place(x, y + height, width);
+
+        Some((x, y))
+    }
+
+    // the bounds of everything placed so far
+    pub fn used_size(&self) -> (u32, u32) {
+        let width = self
+            .skyline
+            .iter()
+            .filter(|(_, y, _)| *y > 0)
+            .map(|(x, _, width)| x + width)
+            .max()
+            .unwrap_or(0);
+        let height = self.skyline.iter().map(|(_, y, _)| *y).max().unwrap_or(0);
+
+        (width, height)
+    }
+
+    // the height the rectangle would be placed at when starting at the segment
+    fn fits(&self, index: usize, width: u32, height: u32) -> Option<u32> {
+        let x = self.skyline[index].0;
+        if x + width > self.width {
+            return None;
+        }
+
+        let mut y = 0;
+        let mut covered = 0;
+        for (_, segment_y, segment_width) in &self.skyline[index..] {
+            y = y.max(*segment_y);
+            covered += segment_width;
+
+            if covered >= width {
+                break;
+            }
+        }
+
+        if y + height > self.height {
+            None
+        } else {
+            Some(y)
+        }
+    }
+
+    fn place(&mut self, x: u32, y: u32, width: u32) {
+        let end = x + width;
+        let mut skyline = Vec::with_capacity(self.skyline.len() + 2);
+
+        for (segment_x, segment_y, segment_width) in self.skyline.drain(..) {
+            let segment_end = segment_x + segment_width;
+
+            if segment_end <= x || segment_x >= end {
+                skyline.push((segment_x, segment_y, segment_width));
+                continue;
+            }
+
+            if segment_x < x {
+                skyline.push((segment_x, segment_y, x - segment_x));
+            }
+            if segment_x <= x {
+                skyline.push((x, y, width));
+            }
+            if segment_end > end {
+                skyline.push((end, segment_y, segment_end - end));
+            }
+        }
+
+        // neighbours at the same height become one segment
+        let mut merged: Vec<(u32, u32, u32)> = Vec::with_capacity(skyline.len());
+        for segment in skyline {
+            match merged.last_mut() {
+                Some(last) if last.1 == segment.1 => last.2 += segment.2,
+                _ => merged.push(segment),
+            }
+        }
+
+        self.skyline = merged;
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AtlasRegion {
+    page: usize,
+    region: Region,
+}
+
+impl AtlasRegion {
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    pub fn region(&self) -> Region {
+        self.region
+    }
+}
+
+pub struct Atlas {
+    pages: Vec<RgbaImage>,
+    regions: HashMap<String, AtlasRegion>,
+    oversized: Vec<(String, RgbaImage)>,
+}
+
+impl Atlas {
+    pub fn pages(&self) -> &[RgbaImage] {
+        &self.pages
+    }
+
+    pub fn region(&self, name: &str) -> Option<AtlasRegion> {
+        self.regions.get(name).copied()
+    }
+
+    // left, top, right and bottom in texture coordinates of the region's page
+    pub fn uv(&self, name: &str) -> Option<[f32; 4]> {
+        let region = self.region(name)?;
+        let page = &self.pages[region.page];
+
+        Some(region.region.uv(page.width(), page.height()))
+    }
+
+    pub fn regions(&self) -> impl Iterator<Item = (&str, AtlasRegion)> {
+        self.regions
+            .iter()
+            .map(|(name, region)| (name.as_str(), *region))
+    }
+
+    // images larger than a page, they need a texture of their own
+    pub fn oversized(&self) -> impl Iterator<Item = (&str, &RgbaImage)> {
+        self.oversized
+            .iter()
+            .map(|(name, image)| (name.as_str(), image))
+    }
+}
+
+// padding is the empty space between images, extrusion repeats their border pixels into it
+pub struct AtlasBuilder {
+    max_size: u32,
+    padding: u32,
+    extrusion: u32,
+    images: Vec<(String, RgbaImage)>,
+}
+
+impl AtlasBuilder {
+    pub fn new(max_size: u32) -> Self {
+        Self {
+            max_size,
+            padding: 0,
+            extrusion: 0,
+            images: Vec::new(),
+        }
+    }
+
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_extrusion(mut self, extrusion: u32) -> Self {
+        self.extrusion = extrusion;
+        self
+    }
+
+    pub fn add(&mut self, name: &str, image: RgbaImage) {
+        self.images.push((name.to_owned(), image));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+
+    // every page is shrunk to the next power of two around its images
+    pub fn build(mut self) -> Atlas {
+        let border = self.extrusion * 2 + self.padding;
+        let max_size = self.max_size;
+
+        // tall images first leave fewer gaps in the skyline
+        self.images.sort_by(|(_, lhs), (_, rhs)| {
+            rhs.height()
+                .cmp(&lhs.height())
+                .then(rhs.width().cmp(&lhs.width()))
+        });
+
+        let mut packers: Vec<SkylinePacker> = Vec::new();
+        let mut placed: Vec<(String, RgbaImage, usize, u32, u32)> = Vec::new();
+        let mut oversized = Vec::new();
+
+        for (name, image) in self.images {
+            let width = image.width() + border;
+            let height = image.height() + border;
+
+            if image.width() + self.extrusion * 2 > max_size
+                || image.height() + self.extrusion * 2 > max_size
+            {
+                oversized.push((name, image));
+                continue;
+            }
+
+            // the padding at the right and bottom border of a page is not needed
+            let width = width.min(max_size);
+            let height = height.min(max_size);
+
+            let mut position = None;
+            for (page, packer) in packers.iter_mut().enumerate() {
+                if let Some((x, y)) = packer.insert(width, height) {
+                    position = Some((page, x, y));
+                    break;
+                }
+            }
+
+            let (page, x, y) = match position {
+                Some(position) => position,
+                None => {
+                    let mut packer = SkylinePacker::new(max_size, max_size);
+                    let (x, y) = match packer.insert(width, height) {
+                        Some(position) => position,
+                        None => {
+                            oversized.push((name, image));
+                            continue;
+                        }
+                    };
+                    packers.push(packer);
+                    (packers.len() - 1, x, y)
+                }
+            };
+
+            placed.push((name, image, page, x, y));
+        }
+
+        let mut pages: Vec<RgbaImage> = packers
+            .iter()
+            .map(|packer| {
+                let (width, height) = packer.used_size();
+                RgbaImage::new(
+                    width.next_power_of_two().min(max_size),
+                    height.next_power_of_two().min(max_size),
+                )
+            })
+            .collect();
+
+        let mut regions = HashMap::new();
+        for (name, image, page, x, y) in placed {
+            let x = x + self.extrusion;
+            let y = y + self.extrusion;
+
+            blit(&mut pages[page], &image, x, y, self.extrusion);
+            regions.insert(
+                name,
+                AtlasRegion {
+                    page,
+                    region: Region::new(x, y, image.width(), image.height()),
+                },
+            );
+        }
+
+        Atlas {
+            pages,
+            regions,
+            oversized,
+        }
+    }
+}
+
+// copies the image and repeats its outermost pixels extrusion times around it
+fn blit(page: &mut RgbaImage, image: &RgbaImage, x: u32, y: u32, extrusion: u32) {
+    let (width, height) = image.dimensions();
+    if width == 0 || height == 0 {
+        return;
+    }
+
+    let extrusion = extrusion as i64;
+    for target_y in -extrusion..height as i64 + extrusion {
+        for target_x in -extrusion..width as i64 + extrusion {
+            let source_x = target_x.clamp(0, width as i64 - 1) as u32;
+            let source_y = target_y.clamp(0, height as i64 - 1) as u32;
+            let page_x = x as i64 + target_x;
+            let page_y = y as i64 + target_y;
+
+            if page_x < 0
+                || page_y < 0
+                || page_x >= page.width() as i64
+                || page_y >= page.height() as i64
+            {
+                continue;
+            }
+
+            page.put_pixel(
+                page_x as u32,
+                page_y as u32,
+                *image.get_pixel(source_x, source_y),
+            );
+        }
+    }
+}
@@ -0,0 +1,209 @@
+extern crate gl;
+extern crate image;
+
+use image::RgbaImage;
+
+use crate::api;
+use crate::api::Rect;
+use crate::texture::Texture;
+
+#[derive(Debug)]
+pub enum FramebufferError {
+    // the status reported by the driver
+    Incomplete(u32),
+    // the samples have to be resolved into another framebuffer first
+    Multisampled,
+}
+
+// renders off screen, either into textures or into multisampled buffers that get resolved into
+// another framebuffer, the attached textures are not owned by it
+pub struct Framebuffer {
+    id: u32,
+    width: u32,
+    height: u32,
+    samples: u32,
+    renderbuffers: Vec<u32>,
+}
+
+impl Framebuffer {
+    // the textures need to have the same size
+    pub fn new(color: &Texture, depth: Option<&Texture>) -> Result<Framebuffer, FramebufferError> {
+        let framebuffer = Self::create(color.width(), color.height(), 0);
+
+        unsafe {
+            gl::FramebufferTexture2D(
+                gl::FRAMEBUFFER,
+                gl::COLOR_ATTACHMENT0,
+                gl::TEXTURE_2D,
+                color.id(),
+                0,
+            );
+            if let Some(depth) = depth {
+                gl::FramebufferTexture2D(
+                    gl::FRAMEBUFFER,
+                    gl::DEPTH_STENCIL_ATTACHMENT,
+                    gl::TEXTURE_2D,
+                    depth.id(),
+                    0,
+                );
+            }
+        }
+
+        framebuffer.complete()
+    }
+
+    // the samples are limited to what the driver supports
+    pub fn multisampled(
+        width: u32,
+        height: u32,
+        samples: u32,
+        depth: bool,
+    ) -> Result<Framebuffer, FramebufferError> {
+        let samples = samples.min(api::max_samples()).max(1);
+        let mut framebuffer = Self::create(width, height, samples);
+
+        framebuffer.attach_renderbuffer(gl::RGBA8, gl::COLOR_ATTACHMENT0);
+        if depth {
+            framebuffer.attach_renderbuffer(gl::DEPTH24_STENCIL8, gl::DEPTH_STENCIL_ATTACHMENT);
+        }
+
+        framebuffer.complete()
+    }
+
+    fn create(width: u32, height: u32, samples: u32) -> Self {
+        let mut id: u32 = 0;
+
+        unsafe {
+            gl::GenFramebuffers(1, &mut id);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, id);
+        }
+
+        Framebuffer {
+            id,
+            width,
+            height,
+            samples,
+            renderbuffers: Vec::new(),
+        }
+    }
+
+    fn attach_renderbuffer(&mut self, format: u32, attachment: u32) {
+        let mut id: u32 = 0;
+
+        unsafe {
+            gl::GenRenderbuffers(1, &mut id);
+            gl::BindRenderbuffer(gl::RENDERBUFFER, id);
+            gl::RenderbufferStorageMultisample(
+                gl::RENDERBUFFER,
+                self.samples as i32,
+                format,
+                self.width as i32,
+                self.height as i32,
+            );
+            gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, attachment, gl::RENDERBUFFER, id);
+            gl::BindRenderbuffer(gl::RENDERBUFFER, 0);
+        }
+
+        self.renderbuffers.push(id);
+    }
+
+    // unbinds the framebuffer again
+    fn complete(self) -> Result<Framebuffer, FramebufferError> {
+        let status = unsafe { gl::CheckFramebufferStatus(gl::FRAMEBUFFER) };
+        self.unbind();
+
+        if status == gl::FRAMEBUFFER_COMPLETE {
+            Ok(self)
+        } else {
+            self.delete();
+            Err(FramebufferError::Incomplete(status))
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // zero when the framebuffer renders into textures
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+
+    pub fn is_multisampled(&self) -> bool {
+        self.samples > 0
+    }
+
+    // the viewport is not changed
+    pub fn bind(&self) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.id);
+        }
+    }
+
+    // draws into the window again
+    pub fn unbind(&self) {
+        unsafe {
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+    }
+
+    // copies the colors into the other framebuffer, the image is stretched when the sizes differ
+    pub fn resolve(&self, target: &Framebuffer) {
+        let filter = if (self.width, self.height) == (target.width, target.height) {
+            gl::NEAREST
+        } else {
+            gl::LINEAR
+        };
+
+        unsafe {
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.id);
+            gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, target.id);
+            gl::BlitFramebuffer(
+                0,
+                0,
+                self.width as i32,
+                self.height as i32,
+                0,
+                0,
+                target.width as i32,
+                target.height as i32,
+                gl::COLOR_BUFFER_BIT,
+                filter,
+            );
+            gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+        }
+    }
+
+    // the first row of the image is the top of the framebuffer
+    pub fn read_pixels(&self) -> Result<RgbaImage, FramebufferError> {
+        if self.is_multisampled() {
+            return Err(FramebufferError::Multisampled);
+        }
+
+        unsafe {
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.id);
+        }
+        let image = api::read_pixels(Rect::new(0, 0, self.width as i32, self.height as i32));
+        unsafe {
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+        }
+
+        Ok(image)
+    }
+
+    // the attached textures are kept
+    pub fn delete(&self) {
+        unsafe {
+            gl::DeleteRenderbuffers(self.renderbuffers.len() as i32, self.renderbuffers.as_ptr());
+            gl::DeleteFramebuffers(1, &self.id);
+        }
+    }
+}
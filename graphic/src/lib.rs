@@ -1,10 +1,14 @@
 pub extern crate gl;
 
 pub mod api;
+pub mod atlas;
 // pub mod camera;
-// pub mod data_buffer;
-// pub mod index_buffer;
+pub mod data_buffer;
+pub mod framebuffer;
+pub mod index_buffer;
+pub mod post_process;
 pub mod program;
+pub mod region;
 pub mod shader;
 pub mod texture;
-// pub mod vertex_array;
+pub mod vertex_array;
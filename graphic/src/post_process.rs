@@ -0,0 +1,352 @@
+extern crate gl;
+extern crate math;
+
+use math::Vec2;
+
+use crate::api;
+use crate::api::Rect;
+use crate::api::RenderState;
+use crate::api::StateCache;
+use crate::framebuffer::Framebuffer;
+use crate::framebuffer::FramebufferError;
+use crate::program::Program;
+use crate::program::ProgramError;
+use crate::program::Uniform;
+use crate::shader::Shader;
+use crate::shader::ShaderError;
+use crate::shader::ShaderType;
+use crate::texture::Texture;
+use crate::vertex_array::VertexArray;
+
+// a triangle covering the viewport, built from the vertex index without any buffers
+pub const FULLSCREEN_VERTEX_SHADER: &str = r#"#version 330 core
+out vec2 uv;
+
+void main() {
+    uv = vec2((gl_VertexID << 1) & 2, gl_VertexID & 2);
+    gl_Position = vec4(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+"#;
+
+const COPY_SHADER: &str = r#"#version 330 core
+in vec2 uv;
+
+uniform sampler2D source;
+
+out vec4 color;
+
+void main() {
+    color = texture(source, uv);
+}
+"#;
+
+#[derive(Debug)]
+pub enum PostProcessError {
+    FailedCompilingShader(String),
+    FailedLinkingShader(String),
+    CreatingTextureFailed,
+    CreatingTargetFailed(FramebufferError),
+}
+
+impl From<FramebufferError> for PostProcessError {
+    fn from(error: FramebufferError) -> Self {
+        PostProcessError::CreatingTargetFailed(error)
+    }
+}
+
+// a full screen shader, its fragment shader gets the uv of the pixel, the output of the previous
+// pass as the source sampler, the unprocessed scene as the scene sampler and the size of the
+// buffers in pixels as the resolution
+pub struct PostPass {
+    program: Program,
+    uniforms: Vec<(String, Uniform)>,
+    enabled: bool,
+}
+
+impl PostPass {
+    pub fn new(program: Program) -> Self {
+        Self {
+            program,
+            uniforms: Vec::new(),
+            enabled: true,
+        }
+    }
+
+    // compiled with the full screen vertex shader
+    pub fn from_source(fragment_shader: &str) -> Result<Self, PostProcessError> {
+        let vertex_shader = compile(FULLSCREEN_VERTEX_SHADER, ShaderType::VertexShader)?;
+        let fragment_shader = compile(fragment_shader, ShaderType::FragmentShader)?;
+
+        match Program::new(vertex_shader, fragment_shader) {
+            Ok(program) => Ok(Self::new(program)),
+            Err(ProgramError::FailedLinkingShader(log)) => {
+                Err(PostProcessError::FailedLinkingShader(log))
+            }
+        }
+    }
+
+    pub fn with_uniform(mut self, name: &str, value: Uniform) -> Self {
+        self.set_uniform(name, value);
+        self
+    }
+
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    // set before every draw, uniforms that never got set keep the value of the shader
+    pub fn uniforms(&self) -> &[(String, Uniform)] {
+        &self.uniforms
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_uniform(&mut self, name: &str, value: Uniform) {
+        match self
+            .uniforms
+            .iter_mut()
+            .find(|(uniform, _)| uniform == name)
+        {
+            Some((_, uniform)) => *uniform = value,
+            None => self.uniforms.push((name.to_owned(), value)),
+        }
+    }
+
+    pub fn remove_uniform(&mut self, name: &str) {
+        self.uniforms.retain(|(uniform, _)| uniform != name);
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn delete(&self) {
+        self.program.delete();
+    }
+
+    fn draw(&self, resolution: Vec2) {
+        self.program.bind();
+        self.program.set_int("source", 0);
+        self.program.set_int("scene", 1);
+        self.program.set_vec2f("resolution", &resolution);
+        for (name, value) in &self.uniforms {
+            self.program.set_uniform(name, value);
+        }
+
+        api::draw_arrays(3, 0);
+    }
+}
+
+fn compile(source: &str, shader_type: ShaderType) -> Result<Shader, PostProcessError> {
+    Shader::from_source(source, shader_type).map_err(|error| match error {
+        ShaderError::FailedCompilingShader(log) => PostProcessError::FailedCompilingShader(log),
+        _ => PostProcessError::FailedCompilingShader(String::new()),
+    })
+}
+
+// a texture with a framebuffer drawing into it
+struct Target {
+    texture: Texture,
+    framebuffer: Framebuffer,
+}
+
+impl Target {
+    fn new(width: u32, height: u32) -> Result<Self, PostProcessError> {
+        let texture = Texture::render_target(width, height)
+            .map_err(|_| PostProcessError::CreatingTextureFailed)?;
+        let framebuffer = match Framebuffer::new(&texture, None) {
+            Ok(framebuffer) => framebuffer,
+            Err(error) => {
+                texture.delete();
+                return Err(error.into());
+            }
+        };
+
+        Ok(Self {
+            texture,
+            framebuffer,
+        })
+    }
+
+    fn delete(&self) {
+        self.framebuffer.delete();
+        self.texture.delete();
+    }
+}
+
+// the scene is rendered into an off screen target between begin and end, end draws it through
+// the enabled passes in their order, every pass reads the output of the one before from one of
+// two buffers and writes into the other one, the last pass writes into the target
+pub struct PostProcessChain {
+    passes: Vec<(String, PostPass)>,
+    // draws the scene unchanged when no pass is enabled
+    copy: PostPass,
+    vertex_array: VertexArray,
+    width: u32,
+    height: u32,
+    scene: Target,
+    multisampled: Option<Framebuffer>,
+    buffers: [Target; 2],
+}
+
+impl PostProcessChain {
+    // the scene is multisampled when the samples are above zero
+    pub fn new(width: u32, height: u32, samples: u32) -> Result<Self, PostProcessError> {
+        let copy = PostPass::from_source(COPY_SHADER)?;
+        let scene = Target::new(width, height)?;
+        let multisampled = match samples {
+            0 => None,
+            samples => Some(Framebuffer::multisampled(width, height, samples, true)?),
+        };
+        let buffers = [Target::new(width, height)?, Target::new(width, height)?];
+
+        Ok(Self {
+            passes: Vec::new(),
+            copy,
+            vertex_array: VertexArray::new(),
+            width,
+            height,
+            scene,
+            multisampled,
+            buffers,
+        })
+    }
+
+    // replaces a pass with the same name
+    pub fn add_pass(&mut self, name: &str, pass: PostPass) {
+        match self.passes.iter_mut().find(|(other, _)| other == name) {
+            Some((_, other)) => {
+                other.delete();
+                *other = pass;
+            }
+            None => self.passes.push((name.to_owned(), pass)),
+        }
+    }
+
+    pub fn remove_pass(&mut self, name: &str) -> Option<PostPass> {
+        let index = self.passes.iter().position(|(other, _)| other == name)?;
+        Some(self.passes.remove(index).1)
+    }
+
+    pub fn pass(&self, name: &str) -> Option<&PostPass> {
+        self.passes
+            .iter()
+            .find(|(other, _)| other == name)
+            .map(|(_, pass)| pass)
+    }
+
+    pub fn pass_mut(&mut self, name: &str) -> Option<&mut PostPass> {
+        self.passes
+            .iter_mut()
+            .find(|(other, _)| other == name)
+            .map(|(_, pass)| pass)
+    }
+
+    // in the order they are drawn
+    pub fn names(&self) -> Vec<&str> {
+        self.passes.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.passes.iter().any(|(_, pass)| pass.is_enabled())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn samples(&self) -> u32 {
+        self.multisampled
+            .as_ref()
+            .map_or(0, |multisampled| multisampled.samples())
+    }
+
+    // binds the off screen target of the scene, it still needs to be cleared
+    pub fn begin(&self, state: &mut StateCache) {
+        match &self.multisampled {
+            Some(multisampled) => multisampled.bind(),
+            None => self.scene.framebuffer.bind(),
+        }
+        state.set_viewport(self.rect());
+    }
+
+    // draws into the window when there is no target, which stays bound afterwards
+    pub fn end(&self, state: &mut StateCache, target: Option<&Framebuffer>) {
+        if let Some(multisampled) = &self.multisampled {
+            multisampled.resolve(&self.scene.framebuffer);
+        }
+
+        state.apply(&RenderState::new());
+        self.vertex_array.bind();
+        self.scene.texture.bind_at_position(1);
+        self.scene.texture.bind_at_position(0);
+
+        let resolution = Vec2::new(self.width as f32, self.height as f32);
+        let passes: Vec<&PostPass> = self
+            .passes
+            .iter()
+            .map(|(_, pass)| pass)
+            .filter(|pass| pass.is_enabled())
+            .collect();
+        let passes = if passes.is_empty() {
+            vec![&self.copy]
+        } else {
+            passes
+        };
+
+        for (index, pass) in passes.iter().enumerate() {
+            if index + 1 < passes.len() {
+                let buffer = &self.buffers[index % 2];
+                buffer.framebuffer.bind();
+                state.set_viewport(self.rect());
+                pass.draw(resolution);
+                buffer.texture.bind_at_position(0);
+                continue;
+            }
+
+            match target {
+                Some(target) => {
+                    target.bind();
+                    state.set_viewport(Rect::new(
+                        0,
+                        0,
+                        target.width() as i32,
+                        target.height() as i32,
+                    ));
+                }
+                None => {
+                    self.scene.framebuffer.unbind();
+                    state.set_viewport(self.rect());
+                }
+            }
+            pass.draw(resolution);
+        }
+
+        self.vertex_array.unbind();
+    }
+
+    pub fn delete(&self) {
+        for (_, pass) in &self.passes {
+            pass.delete();
+        }
+        self.copy.delete();
+        self.vertex_array.delete();
+        self.scene.delete();
+        if let Some(multisampled) = &self.multisampled {
+            multisampled.delete();
+        }
+        for buffer in &self.buffers {
+            buffer.delete();
+        }
+    }
+
+    fn rect(&self) -> Rect {
+        Rect::new(0, 0, self.width as i32, self.height as i32)
+    }
+}
@@ -1,12 +1,35 @@
 extern crate gl;
 extern crate math;
 
+use std::ffi::CString;
+
+use math::Mat4;
+use math::Vec2;
+use math::Vec3;
+use math::Vec4;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
 use crate::shader::Shader;
 
 pub enum ProgramError {
     FailedLinkingShader(String),
 }
 
+// the value of a uniform that is set before a program draws, e.g. by a post processing pass
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Uniform {
+    Bool(bool),
+    Int(i32),
+    Float(f32),
+    Vec2(Vec2),
+    Vec3(Vec3),
+    Vec4(Vec4),
+}
+
 pub struct Program {
     id: u32,
 }
@@ -61,90 +84,86 @@ impl Program {
         self.id
     }
 
-    // use std::ffi::CString;
-
-    // pub fn bind(&self) {
-    //     unsafe {
-    //         gl::UseProgram(self.id);
-    //     }
-    // }
-
-    // pub fn unbind(&self) {
-    //     unsafe {
-    //         gl::UseProgram(0);
-    //     }
-    // }
-
-    // pub fn set_bool(&self, name: &str, value: bool) {
-    //     let name = CString::new(name).unwrap();
-    //     let value = if value { 1 } else { 0 };
-
-    //     unsafe {
-    //         let uniform_location = gl::GetUniformLocation(self.id, name.as_ptr());
-    //         gl::Uniform1i(uniform_location, value);
-    //     };
-    // }
-
-    // pub fn set_int(&self, name: &str, value: i32) {
-    //     let name = CString::new(name).unwrap();
-
-    //     unsafe {
-    //         let uniform_location = gl::GetUniformLocation(self.id, name.as_ptr());
-    //         gl::Uniform1i(uniform_location, value);
-    //     }
-    // }
-
-    // pub fn set_float(&self, name: &str, value: f32) {
-    //     let name = CString::new(name).unwrap();
-
-    //     unsafe {
-    //         let uniform_location = gl::GetUniformLocation(self.id, name.as_ptr());
-    //         gl::Uniform1f(uniform_location, value);
-    //     };
-    // }
-
-    // pub fn set_vec2f(&self, name: &str, value: &Vector2<f32>) {
-    //     let name = CString::new(name).unwrap();
-
-    //     unsafe {
-    //         let uniform_location = gl::GetUniformLocation(self.id, name.as_ptr());
-    //         gl::Uniform2fv(uniform_location, 1, value.as_ptr());
-    //     };
-    // }
-
-    // pub fn set_vec3f(&self, name: &str, value: &Vector3<f32>) {
-    //     let name = CString::new(name).unwrap();
-
-    //     unsafe {
-    //         let uniform_location = gl::GetUniformLocation(self.id, name.as_ptr());
-    //         gl::Uniform3fv(uniform_location, 1, value.as_ptr());
-    //     };
-    // }
-
-    // pub fn set_vec4f(&self, name: &str, value: &Vector4<f32>) {
-    //     let name = CString::new(name).unwrap();
-
-    //     unsafe {
-    //         let uniform_location = gl::GetUniformLocation(self.id, name.as_ptr());
-    //         gl::Uniform4fv(uniform_location, 1, value.as_ptr());
-    //     };
-    // }
-
-    // pub fn set_mat3f(&self, name: &str, value: &Matrix3<f32>) {
-    //     let name = CString::new(name).unwrap();
-
-    //     unsafe {
-    //         let uniform_location = gl::GetUniformLocation(self.id, name.as_ptr());
-    //         gl::UniformMatrix3fv(uniform_location, 1, 0, value.as_ptr());
-    //     };
-    // }
-
-    // pub fn set_mat4f(&self, name: &str, value: &Matrix4<f32>) {
-    //     let name = CString::new(name).unwrap();
-
-    //     unsafe {
-    //         let uniform_location = gl::GetUniformLocation(self.id, name.as_ptr());
-    //         gl::UniformMatrix4fv(uniform_location, 1, 0, value.as_ptr());
-    //     };
-    // }
+    pub fn bind(&self) {
+        unsafe {
+            gl::UseProgram(self.id);
+        }
+    }
+
+    pub fn unbind(&self) {
+        unsafe {
+            gl::UseProgram(0);
+        }
+    }
+
+    pub fn delete(&self) {
+        unsafe {
+            gl::DeleteProgram(self.id);
+        }
+    }
+
+    pub fn set_bool(&self, name: &str, value: bool) {
+        self.set_int(name, if value { 1 } else { 0 });
+    }
+
+    pub fn set_int(&self, name: &str, value: i32) {
+        unsafe {
+            gl::Uniform1i(self.uniform_location(name), value);
+        }
+    }
+
+    pub fn set_float(&self, name: &str, value: f32) {
+        unsafe {
+            gl::Uniform1f(self.uniform_location(name), value);
+        }
+    }
+
+    pub fn set_vec2f(&self, name: &str, value: &Vec2) {
+        unsafe {
+            gl::Uniform2f(self.uniform_location(name), value.x, value.y);
+        }
+    }
+
+    pub fn set_vec3f(&self, name: &str, value: &Vec3) {
+        unsafe {
+            gl::Uniform3f(self.uniform_location(name), value.x, value.y, value.z);
+        }
+    }
+
+    pub fn set_vec4f(&self, name: &str, value: &Vec4) {
+        unsafe {
+            gl::Uniform4f(
+                self.uniform_location(name),
+                value.x,
+                value.y,
+                value.z,
+                value.w,
+            );
+        }
+    }
+
+    pub fn set_mat4f(&self, name: &str, value: &Mat4) {
+        unsafe {
+            gl::UniformMatrix4fv(self.uniform_location(name), 1, 0, value.as_ptr());
+        }
+    }
+
+    pub fn set_uniform(&self, name: &str, value: &Uniform) {
+        match value {
+            Uniform::Bool(value) => self.set_bool(name, *value),
+            Uniform::Int(value) => self.set_int(name, *value),
+            Uniform::Float(value) => self.set_float(name, *value),
+            Uniform::Vec2(value) => self.set_vec2f(name, value),
+            Uniform::Vec3(value) => self.set_vec3f(name, value),
+            Uniform::Vec4(value) => self.set_vec4f(name, value),
+        }
+    }
+
+    // -1 for unknown names, gl ignores uniforms at that location
+    fn uniform_location(&self, name: &str) -> i32 {
+        match CString::new(name) {
+            Ok(name) => unsafe { gl::GetUniformLocation(self.id, name.as_ptr()) },
+            Err(_) => -1,
+        }
+    }
 }
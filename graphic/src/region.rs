@@ -0,0 +1,82 @@
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+// a rectangle of a texture in pixels, the origin is the top left corner
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Region {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl Region {
+    pub fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    // slices a sheet row by row into frames of the same size, cut off frames are skipped
+    pub fn grid(width: u32, height: u32, frame_width: u32, frame_height: u32) -> Vec<Region> {
+        if frame_width == 0 || frame_height == 0 {
+            return Vec::new();
+        }
+
+        let columns = width / frame_width;
+        let rows = height / frame_height;
+
+        (0..rows)
+            .flat_map(|row| {
+                (0..columns).map(move |column| {
+                    Region::new(
+                        column * frame_width,
+                        row * frame_height,
+                        frame_width,
+                        frame_height,
+                    )
+                })
+            })
+            .collect()
+    }
+
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // moves the region, e.g. from sprite into texture coordinates
+    pub fn offset(&self, x: u32, y: u32) -> Region {
+        Region::new(self.x + x, self.y + y, self.width, self.height)
+    }
+
+    // left, top, right and bottom in texture coordinates
+    pub fn uv(&self, texture_width: u32, texture_height: u32) -> [f32; 4] {
+        let texture_width = texture_width.max(1) as f32;
+        let texture_height = texture_height.max(1) as f32;
+
+        [
+            self.x as f32 / texture_width,
+            self.y as f32 / texture_height,
+            (self.x + self.width) as f32 / texture_width,
+            (self.y + self.height) as f32 / texture_height,
+        ]
+    }
+}
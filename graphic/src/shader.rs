@@ -27,8 +27,12 @@ impl Shader {
         shader_file
             .read_to_string(&mut shader_buffer)
             .map_err(|_| ShaderError::FailedReadingFile)?;
-        let shader =
-            CString::new(shader_buffer.as_bytes()).map_err(|_| ShaderError::FailedReadingFile)?;
+
+        Self::from_source(&shader_buffer, shader_type)
+    }
+
+    pub fn from_source(source: &str, shader_type: ShaderType) -> Result<Shader, ShaderError> {
+        let shader = CString::new(source.as_bytes()).map_err(|_| ShaderError::FailedReadingFile)?;
 
         let id: u32 = unsafe {
             let id = match shader_type {
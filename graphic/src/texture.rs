@@ -1,4 +1,7 @@
 extern crate gl;
+extern crate image;
+
+use image::RgbaImage;
 
 pub enum TextureError {
     OpeningTextureFailed,
@@ -6,6 +9,8 @@ pub enum TextureError {
 
 pub struct Texture {
     id: u32,
+    width: u32,
+    height: u32,
 }
 
 #[derive(Copy, Clone)]
@@ -39,9 +44,93 @@ impl Texture {
 
         Ok(Texture {
             id,
+            width,
+            height,
         })
     }
 
+    pub fn from_image(image: &RgbaImage) -> Result<Texture, TextureError> {
+        Self::new(
+            image.as_raw().clone(),
+            TextureFormat::RGBA,
+            image.width(),
+            image.height(),
+        )
+    }
+
+    // an empty texture to render into, sampled linearly without mipmaps
+    pub fn render_target(width: u32, height: u32) -> Result<Texture, TextureError> {
+        Ok(Self::storage(gl::RGBA8, gl::RGBA, gl::UNSIGNED_BYTE, width, height))
+    }
+
+    // holds the depth and stencil values of a framebuffer
+    pub fn depth_stencil(width: u32, height: u32) -> Result<Texture, TextureError> {
+        Ok(Self::storage(
+            gl::DEPTH24_STENCIL8,
+            gl::DEPTH_STENCIL,
+            gl::UNSIGNED_INT_24_8,
+            width,
+            height,
+        ))
+    }
+
+    fn storage(internal_format: u32, format: u32, data_type: u32, width: u32, height: u32) -> Self {
+        let mut id: u32 = 0;
+
+        unsafe {
+            gl::GenTextures(1, &mut id);
+            gl::BindTexture(gl::TEXTURE_2D, id);
+            gl::TexImage2D(
+                gl::TEXTURE_2D,
+                0,
+                internal_format as i32,
+                width as i32,
+                height as i32,
+                0,
+                format,
+                data_type,
+                std::ptr::null(),
+            );
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+        }
+
+        Texture { id, width, height }
+    }
+
+    // replaces the content with an image of the same size
+    pub fn update(&self, image: &RgbaImage) {
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, self.id);
+            gl::TexSubImage2D(
+                gl::TEXTURE_2D,
+                0,
+                0,
+                0,
+                image.width().min(self.width) as i32,
+                image.height().min(self.height) as i32,
+                TextureFormat::RGBA as u32,
+                gl::UNSIGNED_BYTE,
+                image.as_raw().as_ptr() as *const std::ffi::c_void,
+            );
+            gl::GenerateMipmap(gl::TEXTURE_2D);
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
     pub fn bind(&self) {
         unsafe {
             gl::BindTexture(gl::TEXTURE_2D, self.id);
@@ -60,4 +149,10 @@ impl Texture {
             gl::BindTexture(gl::TEXTURE_2D, 0);
         }
     }
+
+    pub fn delete(&self) {
+        unsafe {
+            gl::DeleteTextures(1, &self.id);
+        }
+    }
 }
@@ -0,0 +1,108 @@
+extern crate graphic;
+extern crate image;
+
+use graphic::atlas::AtlasBuilder;
+use graphic::atlas::SkylinePacker;
+use graphic::region::Region;
+use image::Rgba;
+use image::RgbaImage;
+
+fn overlaps(lhs: Region, rhs: Region) -> bool {
+    lhs.x() < rhs.x() + rhs.width()
+        && rhs.x() < lhs.x() + lhs.width()
+        && lhs.y() < rhs.y() + rhs.height()
+        && rhs.y() < lhs.y() + lhs.height()
+}
+
+fn image(width: u32, height: u32, color: u8) -> RgbaImage {
+    RgbaImage::from_pixel(width, height, Rgba([color, color, color, 255]))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn packer_places_without_overlap() {
+        let mut packer = SkylinePacker::new(64, 64);
+        let mut placed = Vec::new();
+
+        for (width, height) in &[(32, 16), (16, 16), (16, 32), (8, 8), (40, 8), (24, 24)] {
+            let (x, y) = packer.insert(*width, *height).unwrap();
+            assert!(x + width <= 64 && y + height <= 64);
+            placed.push(Region::new(x, y, *width, *height));
+        }
+
+        for (index, lhs) in placed.iter().enumerate() {
+            for rhs in &placed[index + 1..] {
+                assert!(!overlaps(*lhs, *rhs), "{:?} overlaps {:?}", lhs, rhs);
+            }
+        }
+    }
+
+    #[test]
+    fn packer_rejects_what_does_not_fit() {
+        let mut packer = SkylinePacker::new(32, 32);
+
+        assert_eq!(packer.insert(32, 32), Some((0, 0)));
+        assert_eq!(packer.insert(1, 1), None);
+        assert_eq!(packer.insert(33, 1), None);
+        assert_eq!(packer.used_size(), (32, 32));
+    }
+
+    #[test]
+    fn atlas_keeps_padding_between_regions() {
+        let mut builder = AtlasBuilder::new(256).with_padding(2);
+        for index in 0..10 {
+            builder.add(&format!("sprite{}", index), image(20, 12, index));
+        }
+
+        let atlas = builder.build();
+        assert_eq!(atlas.pages().len(), 1);
+
+        let regions: Vec<Region> = atlas.regions().map(|(_, region)| region.region()).collect();
+        assert_eq!(regions.len(), 10);
+        for lhs in &regions {
+            let padded = Region::new(lhs.x(), lhs.y(), lhs.width() + 2, lhs.height() + 2);
+            for rhs in regions.iter().filter(|rhs| *rhs != lhs) {
+                assert!(!overlaps(padded, *rhs), "{:?} touches {:?}", lhs, rhs);
+            }
+        }
+    }
+
+    #[test]
+    fn atlas_copies_and_extrudes_images() {
+        let mut builder = AtlasBuilder::new(64).with_extrusion(1);
+        builder.add("white", image(4, 4, 255));
+
+        let atlas = builder.build();
+        let region = atlas.region("white").unwrap().region();
+        let page = &atlas.pages()[0];
+
+        assert_eq!(region, Region::new(1, 1, 4, 4));
+        assert_eq!(page.dimensions(), (8, 8));
+        assert_eq!(page.get_pixel(0, 0), &Rgba([255, 255, 255, 255]));
+        assert_eq!(page.get_pixel(5, 3), &Rgba([255, 255, 255, 255]));
+        assert_eq!(page.get_pixel(7, 7), &Rgba([0, 0, 0, 0]));
+        assert_eq!(atlas.uv("white"), Some([0.125, 0.125, 0.625, 0.625]));
+    }
+
+    #[test]
+    fn atlas_opens_pages_and_skips_oversized_images() {
+        let mut builder = AtlasBuilder::new(32);
+        builder.add("a", image(32, 20, 1));
+        builder.add("b", image(32, 20, 2));
+        builder.add("huge", image(40, 8, 3));
+
+        let atlas = builder.build();
+
+        assert_eq!(atlas.pages().len(), 2);
+        assert_ne!(
+            atlas.region("a").unwrap().page(),
+            atlas.region("b").unwrap().page()
+        );
+        assert!(atlas.region("huge").is_none());
+        let oversized: Vec<&str> = atlas.oversized().map(|(name, _)| name).collect();
+        assert_eq!(oversized, vec!["huge"]);
+    }
+}
@@ -0,0 +1,72 @@
+extern crate graphic;
+
+use graphic::api::BlendMode;
+use graphic::api::CompareFunction;
+use graphic::api::CullMode;
+use graphic::api::PolygonMode;
+use graphic::api::Rect;
+use graphic::api::RenderState;
+use graphic::api::StateCache;
+use graphic::api::StateChange;
+use graphic::api::StencilOp;
+use graphic::api::StencilState;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unknown_state_changes_everything() {
+        let state = RenderState::new();
+        let changes = state.changes(None);
+
+        assert_eq!(changes.len(), 8);
+        assert_eq!(changes[0], StateChange::BlendMode(None));
+        assert_eq!(changes[4], StateChange::ColorWrite([true; 4]));
+        assert_eq!(changes[7], StateChange::PolygonMode(PolygonMode::Fill));
+    }
+
+    #[test]
+    fn only_different_state_changes() {
+        let sprites = RenderState::new().with_blend_mode(Some(BlendMode::Alpha));
+        assert!(sprites.changes(Some(&sprites)).is_empty());
+
+        let stencil = StencilState::new(CompareFunction::Equal, 1)
+            .with_write_mask(0)
+            .with_ops(StencilOp::Keep, StencilOp::Keep, StencilOp::Replace);
+        let masked = sprites
+            .with_blend_mode(Some(BlendMode::Additive))
+            .with_cull_mode(Some(CullMode::Back))
+            .with_scissor(Some(Rect::new(0, 0, 64, 32)))
+            .with_stencil(Some(stencil));
+
+        assert_eq!(
+            masked.changes(Some(&sprites)),
+            vec![
+                StateChange::BlendMode(Some(BlendMode::Additive)),
+                StateChange::CullMode(Some(CullMode::Back)),
+                StateChange::Scissor(Some(Rect::new(0, 0, 64, 32))),
+                StateChange::Stencil(Some(stencil)),
+            ]
+        );
+        assert_eq!(
+            sprites.changes(Some(&masked))[3],
+            StateChange::Stencil(None)
+        );
+    }
+
+    #[test]
+    fn state_cache_starts_unknown() {
+        let cache = StateCache::new();
+        let state = RenderState::new()
+            .with_depth_test(Some(CompareFunction::LessOrEqual))
+            .with_depth_write(false);
+
+        assert!(cache.state().is_none());
+        assert_eq!(cache.changes(&state).len(), 8);
+        assert_eq!(
+            cache.viewport_change(Rect::new(0, 0, 800, 600)),
+            Some(StateChange::Viewport(Rect::new(0, 0, 800, 600)))
+        );
+    }
+}
@@ -3,10 +3,16 @@
 use std::fmt;
 use std::ops;
 
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
 use super::Vec2;
 use super::Vec4;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Vec3 {
     pub x: f32,
     pub y: f32,
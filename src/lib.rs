@@ -4,11 +4,59 @@ pub extern crate ecs;
 pub extern crate math;
 
 pub use core::component;
+pub use core::default_plugins;
 pub use core::resource;
+pub use core::AnimationFinished;
+pub use core::AnimationPlugin;
+pub use core::BitmapFont;
+pub use core::BitmapGlyph;
+pub use core::CameraCaptured;
+pub use core::DebugShape;
+pub use core::DebugSpace;
 pub use core::Engine;
+pub use core::EngineBuilder;
+pub use core::EngineError;
+pub use core::EventReader;
+pub use core::Font;
+pub use core::FontError;
+pub use core::Fullscreen;
+pub use core::InputPlugin;
+pub use core::NullRenderPlugin;
+pub use core::PlacedGlyph;
+pub use core::Plugin;
+pub use core::PostEffect;
+pub use core::Prefab;
+pub use core::Region;
+pub use core::RenderPlugin;
+pub use core::SceneCommands;
+pub use core::SceneError;
+pub use core::SceneHooks;
+pub use core::SceneKey;
+pub use core::ScreenshotTaken;
+pub use core::SerializationError;
+pub use core::SheetFrame;
+pub use core::SheetTag;
+pub use core::SpriteSheet;
+pub use core::SpriteSheetError;
+pub use core::Stage;
+pub use core::StageError;
+pub use core::SystemDescriptor;
+pub use core::TextLayout;
+pub use core::TiledError;
+pub use core::TiledMap;
+pub use core::TiledObject;
+pub use core::TiledTileset;
+pub use core::TimePlugin;
+pub use core::TimerFinished;
+pub use core::Transition;
+pub use core::TransitionKind;
+pub use core::Uniform;
+pub use core::WindowConfig;
+pub use core::WindowKey;
 
 pub mod keyboard {
     pub use super::context::keyboard::Action;
     pub use super::context::keyboard::Key;
     pub use super::context::keyboard::Modifier;
+    pub use super::context::sequence::InputSequence;
 }